@@ -0,0 +1,120 @@
+/**
+ * Window Activity
+ *
+ * Session windows report what their session is doing (idle, thinking,
+ * streaming, failed) so the main window can show one status bar for all of
+ * them. Reports double as heartbeats: each one is forwarded, even if the
+ * activity didn't change.
+ */
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::error::{window_not_found, AppError};
+use super::ipc::emit_to_main_window;
+use super::registry::{with_registry, WindowRegistry};
+
+/// Event sent to the main window for every activity report
+const WINDOW_ACTIVITY_CHANGED_EVENT: &str = "window-activity-changed";
+
+/// What the session in a window is doing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowActivity {
+    #[default]
+    Idle,
+    Thinking,
+    Streaming,
+    Error,
+}
+
+/// Payload of the `window-activity-changed` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct WindowActivityPayload {
+    window_label: String,
+    tab_id: String,
+    activity: WindowActivity,
+}
+
+/// Stores a window's activity and builds the event announcing it
+///
+/// Returns None for windows the registry doesn't know.
+fn record_activity(
+    registry: &WindowRegistry,
+    window_label: &str,
+    activity: WindowActivity,
+) -> Option<WindowActivityPayload> {
+    if !registry.update(window_label, |meta| meta.activity = activity) {
+        return None;
+    }
+
+    registry
+        .get(window_label)
+        .map(|meta| WindowActivityPayload {
+            window_label: meta.window_label,
+            tab_id: meta.tab_id,
+            activity,
+        })
+}
+
+/// Reports what the session in a window is doing
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the session window
+/// * `state` - The current activity
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn report_window_activity(
+    app: AppHandle,
+    window_label: String,
+    state: WindowActivity,
+) -> Result<(), AppError> {
+    reported!(app, "report_window_activity", {
+        let payload = with_registry(&app, |registry| {
+            record_activity(registry, &window_label, state)
+        })
+        .flatten()
+        .ok_or_else(|| window_not_found(&window_label))?;
+
+        emit_to_main_window(&app, WINDOW_ACTIVITY_CHANGED_EVENT, payload)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::registry::WindowMeta;
+    use super::*;
+
+    #[test]
+    fn test_record_activity_updates_registry_and_builds_event() {
+        let registry = WindowRegistry::default();
+        registry.register(WindowMeta::new("session-window-a", "a", None, None, None));
+        assert_eq!(
+            registry.get("session-window-a").unwrap().activity,
+            WindowActivity::Idle
+        );
+
+        let payload = record_activity(&registry, "session-window-a", WindowActivity::Streaming);
+
+        assert_eq!(
+            registry.get("session-window-a").unwrap().activity,
+            WindowActivity::Streaming
+        );
+        assert_eq!(
+            serde_json::to_value(payload.unwrap()).unwrap(),
+            serde_json::json!({
+                "window_label": "session-window-a",
+                "tab_id": "a",
+                "activity": "streaming",
+            })
+        );
+
+        // Nothing is announced for windows the registry doesn't know
+        assert_eq!(
+            record_activity(&registry, "session-window-x", WindowActivity::Error),
+            None
+        );
+    }
+}
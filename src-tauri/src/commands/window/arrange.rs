@@ -0,0 +1,221 @@
+/**
+ * Window Arrangement
+ *
+ * Arranges all visible session windows on the monitor the main window is on
+ * in one go: as a grid, cascaded from the top-left corner, or stacked on top
+ * of each other in the center. Placement goes through the same primitive as
+ * snapping. Arranging can be limited to the windows of a group.
+ */
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use super::error::AppError;
+use super::geometry::{self, WindowGeometry, CASCADE_STEP};
+use super::groups::group_members;
+use super::is_session_window_label;
+use super::snap::place_window;
+
+/// Share of the work area a cascaded or stacked window covers in each dimension
+const FLOATING_WINDOW_SHARE: f64 = 0.6;
+
+/// How `arrange_windows` lays out the session windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArrangeLayout {
+    /// Tile the work area without overlap
+    Grid,
+    /// Overlap diagonally from the top-left corner
+    Cascade,
+    /// Same size and position for all, centered
+    Stack,
+}
+
+/// Columns and rows of a grid holding `count` windows.
+///
+/// Prefers a square grid and puts the longer side along the longer side of
+/// the area, so windows on a wide screen end up side by side.
+fn grid_dimensions(count: usize, area: &WindowGeometry) -> (usize, usize) {
+    if count == 0 {
+        return (0, 0);
+    }
+    let long = (count as f64).sqrt().ceil() as usize;
+    let short = count.div_ceil(long);
+    if area.width >= area.height {
+        (long, short)
+    } else {
+        (short, long)
+    }
+}
+
+/// Computes the geometry of each of `count` windows in `area`
+fn arrangement(layout: ArrangeLayout, area: &WindowGeometry, count: usize) -> Vec<WindowGeometry> {
+    match layout {
+        ArrangeLayout::Grid => {
+            let (columns, rows) = grid_dimensions(count, area);
+            let width = area.width / columns.max(1) as f64;
+            let height = area.height / rows.max(1) as f64;
+            (0..count)
+                .map(|i| WindowGeometry {
+                    x: area.x + (i % columns) as f64 * width,
+                    y: area.y + (i / columns) as f64 * height,
+                    width,
+                    height,
+                })
+                .collect()
+        }
+        ArrangeLayout::Cascade => {
+            let width = area.width * FLOATING_WINDOW_SHARE;
+            let height = area.height * FLOATING_WINDOW_SHARE;
+            // Start over at the corner once the next step would leave the area
+            let steps = (((area.width - width) / CASCADE_STEP)
+                .min((area.height - height) / CASCADE_STEP)
+                .floor() as usize)
+                + 1;
+            (0..count)
+                .map(|i| {
+                    let offset = (i % steps) as f64 * CASCADE_STEP;
+                    WindowGeometry {
+                        x: area.x + offset,
+                        y: area.y + offset,
+                        width,
+                        height,
+                    }
+                })
+                .collect()
+        }
+        ArrangeLayout::Stack => {
+            let width = area.width * FLOATING_WINDOW_SHARE;
+            let height = area.height * FLOATING_WINDOW_SHARE;
+            let (x, y) = geometry::center_in(area, width, height);
+            vec![
+                WindowGeometry {
+                    x,
+                    y,
+                    width,
+                    height,
+                };
+                count
+            ]
+        }
+    }
+}
+
+/// Arranges all visible session windows on the main window's monitor
+///
+/// Minimized and hidden windows are left alone.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `layout` - How to arrange the windows
+/// * `group_id` - Only arrange the windows of this group (default: all)
+///
+/// # Returns
+/// * `Result<usize, AppError>` - The number of windows arranged or an error message
+#[tauri::command]
+pub async fn arrange_windows(
+    app: AppHandle,
+    layout: ArrangeLayout,
+    group_id: Option<String>,
+) -> Result<usize, AppError> {
+    reported!(app, "arrange_windows", {
+        let members = group_id
+            .as_deref()
+            .map(|group_id| group_members(&app, group_id));
+        let monitor = match app.get_webview_window("main") {
+            Some(main) => main
+                .current_monitor()
+                .map_err(|e| format!("Failed to get current monitor: {}", e))?,
+            None => None,
+        };
+        let monitor = match monitor {
+            Some(monitor) => monitor,
+            None => app
+                .primary_monitor()
+                .map_err(|e| format!("Failed to get primary monitor: {}", e))?
+                .ok_or_else(|| "No monitor detected".to_string())?,
+        };
+        let area = geometry::work_area_of(&monitor).area;
+
+        let mut windows: Vec<_> = app
+            .webview_windows()
+            .into_iter()
+            .filter(|(label, window)| {
+                is_session_window_label(label)
+                    && members
+                        .as_ref()
+                        .is_none_or(|members| members.contains(label))
+                    && window.is_visible().unwrap_or(false)
+                    && !window.is_minimized().unwrap_or(false)
+            })
+            .collect();
+        windows.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let targets = arrangement(layout, &area, windows.len());
+        for ((_, window), target) in windows.iter().zip(&targets) {
+            place_window(window, target)?;
+        }
+
+        log::info!(
+            "[Window] Arranged {} session window(s) as {:?}",
+            windows.len(),
+            layout
+        );
+        Ok(windows.len())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: WindowGeometry = WindowGeometry {
+        x: 0.0,
+        y: 0.0,
+        width: 1920.0,
+        height: 1040.0,
+    };
+
+    fn fits(window: &WindowGeometry, area: &WindowGeometry) -> bool {
+        window.x >= area.x
+            && window.y >= area.y
+            && window.x + window.width <= area.x + area.width + 1e-9
+            && window.y + window.height <= area.y + area.height + 1e-9
+    }
+
+    #[test]
+    fn test_grid_dimensions_follow_area_orientation() {
+        assert_eq!(grid_dimensions(0, &AREA), (0, 0));
+        assert_eq!(grid_dimensions(1, &AREA), (1, 1));
+        assert_eq!(grid_dimensions(3, &AREA), (2, 2));
+        assert_eq!(grid_dimensions(5, &AREA), (3, 2));
+        let portrait = WindowGeometry {
+            width: 1080.0,
+            height: 1920.0,
+            ..AREA
+        };
+        assert_eq!(grid_dimensions(5, &portrait), (2, 3));
+    }
+
+    #[test]
+    fn test_grid_tiles_without_overlap() {
+        let cells = arrangement(ArrangeLayout::Grid, &AREA, 4);
+        assert_eq!(cells.len(), 4);
+        assert!(cells.iter().all(|cell| fits(cell, &AREA)));
+        for (i, a) in cells.iter().enumerate() {
+            for b in &cells[i + 1..] {
+                assert!(!a.intersects(b), "{:?} overlaps {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cascade_and_stack_stay_in_area() {
+        let cascade = arrangement(ArrangeLayout::Cascade, &AREA, 50);
+        assert!(cascade.iter().all(|window| fits(window, &AREA)));
+        assert_eq!(cascade[1].x - cascade[0].x, CASCADE_STEP);
+
+        let stack = arrangement(ArrangeLayout::Stack, &AREA, 3);
+        assert!(stack.iter().all(|window| *window == stack[0]));
+        assert!(fits(&stack[0], &AREA));
+    }
+}
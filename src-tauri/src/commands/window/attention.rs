@@ -0,0 +1,137 @@
+/**
+ * Window Attention
+ *
+ * Draws the user's eye to a background window, e.g. when a run finishes in a
+ * session window they aren't looking at. Windows flashes the taskbar button
+ * and caption; other platforms hand a `request-attention` event to the frontend.
+ */
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use super::error::{window_not_found, AppError};
+
+/// How long to keep drawing attention
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttentionMode {
+    /// Flash once
+    #[default]
+    Single,
+    /// Keep flashing until the window is brought to the foreground
+    Continuous,
+}
+
+/// Payload of the `request-attention` event
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct AttentionPayload {
+    mode: AttentionMode,
+}
+
+/// Flash the taskbar button and caption using `FlashWindowEx`
+#[cfg(target_os = "windows")]
+fn request_attention_for_window(
+    window: &tauri::WebviewWindow,
+    mode: AttentionMode,
+) -> Result<(), String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        FlashWindowEx, FLASHWINFO, FLASHW_ALL, FLASHW_TIMERNOFG,
+    };
+
+    let hwnd = window
+        .hwnd()
+        .map_err(|e| format!("Failed to get window handle: {}", e))?;
+    let hwnd = HWND(hwnd.0);
+
+    let (flags, count) = match mode {
+        AttentionMode::Single => (FLASHW_ALL, 1),
+        AttentionMode::Continuous => (FLASHW_ALL | FLASHW_TIMERNOFG, 0),
+    };
+    let info = FLASHWINFO {
+        cbSize: std::mem::size_of::<FLASHWINFO>() as u32,
+        hwnd,
+        dwFlags: flags,
+        uCount: count,
+        dwTimeout: 0,
+    };
+
+    // The return value only reports the previous caption state, not an error
+    unsafe {
+        let _ = FlashWindowEx(&info);
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn request_attention_for_window(
+    window: &tauri::WebviewWindow,
+    mode: AttentionMode,
+) -> Result<(), String> {
+    use tauri::{Emitter, EventTarget};
+
+    window
+        .emit_to(
+            EventTarget::webview_window(window.label()),
+            "request-attention",
+            AttentionPayload { mode },
+        )
+        .map_err(|e| format!("Failed to emit event: {}", e))
+}
+
+/// Draws attention to a window that doesn't have focus
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `mode` - Flash once (default) or until the window is focused
+///
+/// # Returns
+/// * `Result<bool, AppError>` - Whether attention was requested (false if the
+///   window already has focus) or an error message
+#[tauri::command]
+pub async fn notify_window_attention(
+    app: AppHandle,
+    window_label: String,
+    mode: Option<AttentionMode>,
+) -> Result<bool, AppError> {
+    reported!(app, "notify_window_attention", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        if window.is_focused().unwrap_or(false) {
+            return Ok(false);
+        }
+
+        request_attention_for_window(&window, mode.unwrap_or_default())?;
+        window_log!(info, window_label, "Requested attention");
+        Ok(true)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attention_mode_serde() {
+        assert_eq!(AttentionMode::default(), AttentionMode::Single);
+        assert_eq!(
+            serde_json::from_str::<AttentionMode>("\"continuous\"").unwrap(),
+            AttentionMode::Continuous
+        );
+        assert!(serde_json::from_str::<AttentionMode>("\"forever\"").is_err());
+    }
+
+    #[test]
+    fn test_attention_payload_serialization() {
+        assert_eq!(
+            serde_json::to_value(AttentionPayload {
+                mode: AttentionMode::Continuous
+            })
+            .unwrap(),
+            serde_json::json!({ "mode": "continuous" })
+        );
+    }
+}
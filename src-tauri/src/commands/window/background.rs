@@ -0,0 +1,84 @@
+/**
+ * Window Background Color
+ *
+ * While a frameless window is resized, the area the webview hasn't repainted
+ * yet shows the native background, white by default. Session windows are
+ * built with the background of the current theme, and the frontend can match
+ * it to a custom theme at runtime.
+ */
+use tauri::window::Color;
+use tauri::{AppHandle, Manager};
+
+use super::error::{window_not_found, AppError};
+use super::titlebar;
+
+/// Background of the dark theme, `--color-background` in theme.css
+const DARK_BACKGROUND: Color = Color(28, 29, 31, 255);
+
+/// Background of the light theme, `--color-background` in theme.css
+const LIGHT_BACKGROUND: Color = Color(249, 250, 251, 255);
+
+/// Parses a `#RRGGBB` background color; backgrounds are always opaque
+fn parse_background_color(color_hex: &str) -> Result<Color, String> {
+    let (r, g, b) = titlebar::parse_hex_rgb(color_hex)?;
+    Ok(Color(r, g, b, 255))
+}
+
+fn theme_background(is_dark: bool) -> Color {
+    if is_dark {
+        DARK_BACKGROUND
+    } else {
+        LIGHT_BACKGROUND
+    }
+}
+
+/// Background a freshly built window starts with, if the theme is known
+pub(super) fn new_window_background(app: &AppHandle) -> Option<Color> {
+    titlebar::current_theme(app).map(theme_background)
+}
+
+/// Sets the background color shown behind a window's webview
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `color_hex` - The color as `#RRGGBB`
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_background_color(
+    app: AppHandle,
+    window_label: String,
+    color_hex: String,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_background_color", {
+        let color = parse_background_color(&color_hex).map_err(AppError::InvalidParam)?;
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        window
+            .set_background_color(Some(color))
+            .map_err(|e| format!("Failed to set background color: {}", e))?;
+        window_log!(debug, window_label, "Background color set to {}", color_hex);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_background_color() {
+        assert_eq!(parse_background_color("#1C1D1F"), Ok(DARK_BACKGROUND));
+        assert_eq!(parse_background_color("#f9fafb"), Ok(LIGHT_BACKGROUND));
+        // Same validation as the title bar color
+        assert_eq!(
+            parse_background_color("#1C1D1"),
+            Err("Invalid color: #1C1D1 (expected #RRGGBB)".to_string())
+        );
+        assert_eq!(theme_background(true), DARK_BACKGROUND);
+    }
+}
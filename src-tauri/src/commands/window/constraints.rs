@@ -0,0 +1,212 @@
+/**
+ * Window Size Constraints
+ *
+ * Minimum and maximum inner sizes of a session window, so that e.g. a
+ * maximized window on a huge monitor doesn't stretch the layout into a single
+ * unusably wide column.
+ *
+ * Resizing and maximizing can also be locked altogether, e.g. for a
+ * fixed-size dashboard window. Both locks are restored with the window.
+ */
+use serde::Deserialize;
+use tauri::{AppHandle, LogicalSize, Manager};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+use super::{restore, tab_id_from_label};
+
+/// Stand-in for "no maximum" in the dimension that wasn't given.
+///
+/// The native APIs take a width and a height together, and some of them
+/// overflow on `f64::MAX`.
+pub(super) const UNBOUNDED_SIZE: f64 = 100_000.0;
+
+/// An inner size bound in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct SizeBound {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Checks that a minimum doesn't exceed the maximum of the same dimension
+pub(super) fn check_min_max(dimension: &str, min: f64, max: Option<f64>) -> Result<(), String> {
+    match max {
+        Some(max) if min > max => Err(format!(
+            "Invalid size constraints: min_{} {} is larger than max_{} {}",
+            dimension, min, dimension, max
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Checks a runtime constraint update
+fn validate_bounds(min: Option<SizeBound>, max: Option<SizeBound>) -> Result<(), String> {
+    for (name, bound) in [("min", min), ("max", max)] {
+        if let Some(bound) = bound {
+            for (axis, value) in [("width", bound.width), ("height", bound.height)] {
+                if !(value.is_finite() && value > 0.0) {
+                    return Err(format!(
+                        "Invalid {}_{}: must be a positive number, got {}",
+                        name, axis, value
+                    ));
+                }
+            }
+        }
+    }
+
+    if let (Some(min), Some(max)) = (min, max) {
+        check_min_max("width", min.width, Some(max.width))?;
+        check_min_max("height", min.height, Some(max.height))?;
+    }
+    Ok(())
+}
+
+/// Changes the minimum and maximum inner size of a window
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `min` - The new minimum inner size, or None to drop it
+/// * `max` - The new maximum inner size, or None to drop it
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_size_constraints(
+    app: AppHandle,
+    window_label: String,
+    min: Option<SizeBound>,
+    max: Option<SizeBound>,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_size_constraints", {
+        validate_bounds(min, max).map_err(AppError::InvalidParam)?;
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        window
+            .set_min_size(min.map(|min| LogicalSize::new(min.width, min.height)))
+            .map_err(|e| format!("Failed to set minimum size: {}", e))?;
+        window
+            .set_max_size(max.map(|max| LogicalSize::new(max.width, max.height)))
+            .map_err(|e| format!("Failed to set maximum size: {}", e))?;
+
+        window_log!(
+            info,
+            window_label,
+            "Size constraints set to {:?} - {:?}",
+            min,
+            max
+        );
+        Ok(())
+    })
+}
+
+/// Allows or locks resizing a window
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `resizable` - Whether the user can resize the window (default: true)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_resizable(
+    app: AppHandle,
+    window_label: String,
+    resizable: bool,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_resizable", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        window
+            .set_resizable(resizable)
+            .map_err(|e| format!("Failed to set resizable: {}", e))?;
+
+        with_registry(&app, |registry| {
+            registry.update(&window_label, |meta| meta.resizable = resizable)
+        });
+        if let Some(tab_id) = tab_id_from_label(&window_label) {
+            restore::set_open_window_resizable(&app, tab_id, resizable);
+        }
+
+        window_log!(
+            info,
+            window_label,
+            "Resizing {}",
+            if resizable { "allowed" } else { "locked" }
+        );
+        Ok(())
+    })
+}
+
+/// Allows or locks maximizing a window
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `maximizable` - Whether the user can maximize the window (default: true)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_maximizable(
+    app: AppHandle,
+    window_label: String,
+    maximizable: bool,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_maximizable", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        window
+            .set_maximizable(maximizable)
+            .map_err(|e| format!("Failed to set maximizable: {}", e))?;
+
+        with_registry(&app, |registry| {
+            registry.update(&window_label, |meta| meta.maximizable = maximizable)
+        });
+        if let Some(tab_id) = tab_id_from_label(&window_label) {
+            restore::set_open_window_maximizable(&app, tab_id, maximizable);
+        }
+
+        window_log!(
+            info,
+            window_label,
+            "Maximizing {}",
+            if maximizable { "allowed" } else { "locked" }
+        );
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bound(width: f64, height: f64) -> Option<SizeBound> {
+        Some(SizeBound { width, height })
+    }
+
+    #[test]
+    fn test_validate_bounds_rejects_min_above_max() {
+        assert_eq!(
+            validate_bounds(bound(600.0, 400.0), bound(1600.0, 1200.0)),
+            Ok(())
+        );
+        assert_eq!(validate_bounds(bound(600.0, 400.0), None), Ok(()));
+        assert_eq!(validate_bounds(None, None), Ok(()));
+        assert_eq!(
+            validate_bounds(bound(1800.0, 400.0), bound(1600.0, 1200.0)),
+            Err(
+                "Invalid size constraints: min_width 1800 is larger than max_width 1600"
+                    .to_string()
+            )
+        );
+        assert!(validate_bounds(bound(600.0, 400.0), bound(1600.0, 0.0)).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+/**
+ * Native Window Decorations
+ *
+ * Session windows are built without decorations and draw their own title
+ * bar. Users who prefer the native one (common on Linux) can switch per
+ * window at runtime; the frontend hides its title bar on
+ * `decorations-changed`. The choice is restored with the window.
+ */
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, EventTarget, Manager, WebviewWindow};
+
+use super::error::{window_not_found, AppError};
+use super::geometry;
+use super::registry::with_registry;
+use super::{restore, tab_id_from_label};
+
+/// Event sent to a window after its decorations were switched
+const DECORATIONS_CHANGED_EVENT: &str = "decorations-changed";
+
+/// Payload of the `decorations-changed` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct DecorationsChangedPayload {
+    window_label: String,
+    /// Whether the window now has the native title bar and border
+    decorated: bool,
+}
+
+/// Switches native decorations on or off and tells the window's frontend
+///
+/// Some window managers move the window when the frame changes; one that ends
+/// up off-screen is centered again. Shared by `set_window_decorations` and the
+/// startup restore.
+pub(super) fn apply_decorations(
+    app: &AppHandle,
+    window: &WebviewWindow,
+    decorated: bool,
+) -> Result<(), String> {
+    window
+        .set_decorations(decorated)
+        .map_err(|e| format!("Failed to set decorations: {}", e))?;
+
+    if let Ok(current) = geometry::capture_geometry(window) {
+        let screens = geometry::screen_bounds(app);
+        if !screens.is_empty() && !geometry::is_on_screen(&current, &screens) {
+            window_log!(
+                info,
+                window.label(),
+                "Moved off-screen by the decoration change, centering"
+            );
+            window
+                .center()
+                .map_err(|e| format!("Failed to center window: {}", e))?;
+        }
+    }
+
+    let payload = DecorationsChangedPayload {
+        window_label: window.label().to_string(),
+        decorated,
+    };
+    window
+        .emit_to(
+            EventTarget::webview_window(window.label()),
+            DECORATIONS_CHANGED_EVENT,
+            payload,
+        )
+        .map_err(|e| format!("Failed to emit event: {}", e))
+}
+
+/// Shows or hides the native title bar and border of a window
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `decorated` - Whether to use native decorations instead of the custom title bar
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_decorations(
+    app: AppHandle,
+    window_label: String,
+    decorated: bool,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_decorations", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        apply_decorations(&app, &window, decorated)?;
+
+        with_registry(&app, |registry| {
+            registry.update(&window_label, |meta| meta.decorated = decorated)
+        });
+        if let Some(tab_id) = tab_id_from_label(&window_label) {
+            restore::set_open_window_decorations(&app, tab_id, decorated);
+        }
+
+        window_log!(
+            info,
+            window_label,
+            "Native decorations {}",
+            if decorated { "on" } else { "off" }
+        );
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decorations_changed_payload() {
+        let payload = DecorationsChangedPayload {
+            window_label: "session-window-a".to_string(),
+            decorated: true,
+        };
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::json!({ "window_label": "session-window-a", "decorated": true })
+        );
+    }
+}
@@ -0,0 +1,88 @@
+/**
+ * Visible on All Desktops
+ *
+ * Pins a session window to every virtual desktop (macOS Spaces, Linux
+ * workspaces), so a window monitoring a long run stays in view when the
+ * user switches desktops. The choice is restored with the window.
+ */
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+use super::{restore, tab_id_from_label};
+
+/// Whether the platform can show a window on all desktops
+///
+/// Windows has no public API to pin a window to every virtual desktop.
+const SUPPORTS_ALL_WORKSPACES: bool = cfg!(any(target_os = "macos", target_os = "linux"));
+
+/// Pins a window to all desktops or unpins it
+///
+/// A no-op with a warning where the platform can't do it. Shared by
+/// `set_window_visible_on_all_workspaces` and the startup restore.
+pub(super) fn apply_visible_on_all_workspaces(
+    window: &WebviewWindow,
+    enabled: bool,
+) -> Result<(), String> {
+    if !SUPPORTS_ALL_WORKSPACES {
+        window_log!(
+            warn,
+            window.label(),
+            "Showing a window on all desktops is not supported on this platform"
+        );
+        return Ok(());
+    }
+    window
+        .set_visible_on_all_workspaces(enabled)
+        .map_err(|e| format!("Failed to set visible on all workspaces: {}", e))
+}
+
+/// Shows a window on all virtual desktops, or only on its own one
+///
+/// Platform support:
+/// * macOS - joins all Spaces (`NSWindowCollectionBehaviorCanJoinAllSpaces`)
+/// * Linux - sticky window; honored by X11 window managers, most Wayland
+///   compositors ignore it
+/// * Windows - not supported; the call only logs a warning
+///
+/// The choice is kept in the registry and reapplied when the window is
+/// restored, also on platforms that ignore it.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `enabled` - Whether the window should be visible on all desktops
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_visible_on_all_workspaces(
+    app: AppHandle,
+    window_label: String,
+    enabled: bool,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_visible_on_all_workspaces", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        apply_visible_on_all_workspaces(&window, enabled)?;
+
+        with_registry(&app, |registry| {
+            registry.update(&window_label, |meta| {
+                meta.visible_on_all_workspaces = enabled
+            })
+        });
+        if let Some(tab_id) = tab_id_from_label(&window_label) {
+            restore::set_open_window_all_workspaces(&app, tab_id, enabled);
+        }
+
+        window_log!(
+            info,
+            window_label,
+            "Visible on all desktops {}",
+            if enabled { "on" } else { "off" }
+        );
+        Ok(())
+    })
+}
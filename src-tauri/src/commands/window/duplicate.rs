@@ -0,0 +1,133 @@
+/**
+ * Session Window Duplication
+ *
+ * Opens a second window on the same project and engine as an existing session
+ * window, starting a fresh session so an alternate approach can be tried
+ * without touching the original.
+ */
+use tauri::{AppHandle, Manager};
+
+use super::error::{window_not_found, AppError};
+use super::geometry::{self, WindowGeometry};
+use super::registry::{with_registry, WindowMeta};
+use super::{build_session_window, CreateSessionWindowParams, WindowCreationResult};
+
+/// Parameters of the copy of a session window
+///
+/// Keeps the project, engine and size of the source but drops the session ID
+/// so the new window starts a clean session.
+fn duplicate_params(
+    source: &WindowMeta,
+    tab_id: String,
+    title: String,
+    geometry: Option<WindowGeometry>,
+    monitor_hint: Option<String>,
+) -> CreateSessionWindowParams {
+    CreateSessionWindowParams {
+        tab_id,
+        session_id: None,
+        project_path: source.project_path.clone(),
+        title,
+        engine: source.engine,
+        monitor_hint,
+        width: geometry.map(|geometry| geometry.width),
+        height: geometry.map(|geometry| geometry.height),
+        decorations: source.decorated.then_some(true),
+        ..Default::default()
+    }
+}
+
+/// Opens a copy of a session window with a fresh session
+///
+/// The copy is placed one cascade step off the source window.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `source_label` - The label of the session window to duplicate
+///
+/// # Returns
+/// * `Result<WindowCreationResult, AppError>` - The new window or an error message
+#[tauri::command]
+pub async fn duplicate_session_window(
+    app: AppHandle,
+    source_label: String,
+) -> Result<WindowCreationResult, AppError> {
+    reported!(app, "duplicate_session_window", {
+        let _scope = super::logging::LogScope::new("duplicate_session_window", &source_label);
+        let window = app
+            .get_webview_window(&source_label)
+            .ok_or_else(|| window_not_found(&source_label))?;
+        let source = with_registry(&app, |registry| registry.get(&source_label))
+            .flatten()
+            .ok_or_else(|| window_not_found(&source_label))?;
+
+        let geometry = geometry::capture_geometry(&window).ok();
+        let monitor_hint = window
+            .current_monitor()
+            .ok()
+            .flatten()
+            .and_then(|monitor| monitor.name().cloned());
+        let title = window.title().unwrap_or_else(|_| source.tab_id.clone());
+
+        let params = duplicate_params(
+            &source,
+            uuid::Uuid::new_v4().to_string(),
+            title,
+            geometry,
+            monitor_hint,
+        );
+
+        if let Some(geometry) = geometry {
+            geometry::cascade_from(geometry.x, geometry.y);
+        }
+
+        window_log!(
+            info,
+            source_label,
+            "Duplicating session window as tab {}",
+            params.tab_id
+        );
+        build_session_window(&app, &params).await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Engine;
+    use super::*;
+
+    #[test]
+    fn test_duplicate_params_start_a_fresh_session() {
+        let source = WindowMeta::new(
+            "session-window-a",
+            "a",
+            Some("session-1".to_string()),
+            Some("/tmp/project".to_string()),
+            Some(Engine::Codex),
+        );
+        let geometry = WindowGeometry {
+            x: 100.0,
+            y: 80.0,
+            width: 1200.0,
+            height: 800.0,
+        };
+
+        let params = duplicate_params(
+            &source,
+            "b".to_string(),
+            "Project".to_string(),
+            Some(geometry),
+            Some("DISPLAY1".to_string()),
+        );
+
+        assert_eq!(params.tab_id, "b");
+        assert_eq!(params.session_id, None);
+        assert_eq!(params.project_path.as_deref(), Some("/tmp/project"));
+        assert_eq!(params.engine, Some(Engine::Codex));
+        assert_eq!(params.title, "Project");
+        assert_eq!(params.width, Some(1200.0));
+        assert_eq!(params.height, Some(800.0));
+        assert_eq!(params.monitor_hint.as_deref(), Some("DISPLAY1"));
+        assert_eq!(params.initial_prompt, None);
+    }
+}
@@ -0,0 +1,141 @@
+/**
+ * Timed Command Results
+ *
+ * Variants of the heavier window commands that wrap their result in a
+ * `CommandEnvelope` with how long the call took, whether it took the fast
+ * path, and what didn't go as asked. Meant for profiling the IPC layer, e.g.
+ * slow window creation on low-end machines; the plain commands stay for
+ * callers that don't want the overhead.
+ */
+use serde::Serialize;
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+
+use super::error::AppError;
+use super::layout::{self, WorkspaceLayout};
+use super::{
+    build_session_window, url_project_path, window_label_for_workspace_tab,
+    CreateSessionWindowParams, WindowCreationResult,
+};
+
+/// A command result with timing metadata
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandEnvelope<T> {
+    /// What the plain command returns
+    pub data: T,
+    /// Wall-clock time the command took, in milliseconds
+    pub elapsed_ms: u64,
+    /// Whether the command could skip its expensive part (e.g. focusing an
+    /// open window instead of building one)
+    pub fast_path: bool,
+    /// Things that didn't go as asked but didn't fail the command
+    pub warnings: Vec<String>,
+}
+
+/// Measures a command from its start
+struct CommandTimer {
+    started: Instant,
+}
+
+impl CommandTimer {
+    fn start() -> Self {
+        Self {
+            started: Instant::now(),
+        }
+    }
+
+    /// Milliseconds since the start; never decreases
+    fn elapsed_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
+    fn envelope<T>(&self, data: T, fast_path: bool, warnings: Vec<String>) -> CommandEnvelope<T> {
+        CommandEnvelope {
+            data,
+            elapsed_ms: self.elapsed_ms(),
+            fast_path,
+            warnings,
+        }
+    }
+}
+
+/// Creates a session window and reports how long it took
+///
+/// The fast path is focusing a window that was already open for the tab.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `params` - Window creation parameters
+///
+/// # Returns
+/// * `Result<CommandEnvelope<WindowCreationResult>, AppError>` - The creation result with timing
+#[tauri::command]
+pub async fn create_session_window_timed(
+    app: AppHandle,
+    params: CreateSessionWindowParams,
+) -> Result<CommandEnvelope<WindowCreationResult>, AppError> {
+    reported!(app, "create_session_window_timed", {
+        let timer = CommandTimer::start();
+        let already_open =
+            window_label_for_workspace_tab(params.workspace.as_deref(), &params.tab_id)
+                .map(|label| app.get_webview_window(&label).is_some())
+                .unwrap_or(false);
+
+        let result = build_session_window(&app, &params).await?;
+
+        let mut warnings = Vec::new();
+        if !already_open && params.project_path.is_some() && url_project_path(&params).is_none() {
+            warnings.push("Project path too long for the URL, sent as an event".to_string());
+        }
+        Ok(timer.envelope(result, already_open, warnings))
+    })
+}
+
+/// Opens a workspace layout and reports how long it took
+///
+/// Windows that failed to open are listed in the warnings.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `layout` - The layout to open
+///
+/// # Returns
+/// * `Result<CommandEnvelope<Vec<WindowCreationResult>>, AppError>` - The opened windows with timing
+#[tauri::command]
+pub async fn open_workspace_layout_timed(
+    app: AppHandle,
+    layout: WorkspaceLayout,
+) -> Result<CommandEnvelope<Vec<WindowCreationResult>>, AppError> {
+    reported!(app, "open_workspace_layout_timed", {
+        let timer = CommandTimer::start();
+        let (results, warnings) = layout::open_layout(&app, &layout).await?;
+        Ok(timer.envelope(results, false, warnings))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_elapsed_ms_is_populated_and_monotonic() {
+        let timer = CommandTimer::start();
+        std::thread::sleep(Duration::from_millis(5));
+        let first = timer.envelope("a", false, Vec::new());
+        assert!(first.elapsed_ms >= 5);
+
+        let mut last = first.elapsed_ms;
+        for _ in 0..100 {
+            let now = timer.elapsed_ms();
+            assert!(now >= last);
+            last = now;
+        }
+
+        let value = serde_json::to_value(timer.envelope(1, true, vec!["w".to_string()])).unwrap();
+        assert_eq!(value["data"], 1);
+        assert_eq!(value["fast_path"], true);
+        assert_eq!(value["warnings"], serde_json::json!(["w"]));
+        assert!(value["elapsed_ms"].as_u64().unwrap() >= first.elapsed_ms);
+    }
+}
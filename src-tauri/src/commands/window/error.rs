@@ -0,0 +1,211 @@
+/**
+ * Window Command Errors
+ *
+ * Errors of the window commands, sorted into a few kinds the frontend can
+ * tell apart. They serialize as `{ "kind": ..., "message": ... }`; the
+ * message is the same human-readable string the commands used to return.
+ * A missing window also carries `"code": "window_not_found"`, which stays
+ * the same whatever the message says. Only the errors built by
+ * `window_not_found` carry it; other things that are missing don't.
+ *
+ * Every failure is also broadcast on `window-error`, so a toast system sees
+ * background failures and those of calls nobody awaits. The exception is a
+ * window that closed while an event was on its way: callers can usually
+ * ignore that, so it isn't broadcast.
+ */
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use tauri::{AppHandle, Emitter};
+
+/// Event broadcast for every window command or background failure
+const WINDOW_ERROR_EVENT: &str = "window-error";
+
+/// Code of the error for a window that doesn't exist
+pub const WINDOW_NOT_FOUND_CODE: &str = "window_not_found";
+
+/// Error of a window command
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    /// No window has the given label; carries `WINDOW_NOT_FOUND_CODE`
+    WindowNotFound(String),
+    /// Something else the command needed doesn't exist
+    NotFound(String),
+    /// A window couldn't be built
+    Build(String),
+    /// An event couldn't be delivered
+    Emit(String),
+    /// The target window closed while an event was being delivered to it
+    WindowGone(String),
+    /// An argument was out of range or malformed
+    InvalidParam(String),
+    /// Anything else: native window calls, state files, ...
+    Other(String),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::WindowNotFound(message)
+            | AppError::NotFound(message)
+            | AppError::Build(message)
+            | AppError::Emit(message)
+            | AppError::WindowGone(message)
+            | AppError::InvalidParam(message)
+            | AppError::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl AppError {
+    /// Name of the kind as serialized
+    fn kind(&self) -> &'static str {
+        match self {
+            AppError::WindowNotFound(_) | AppError::NotFound(_) => "not_found",
+            AppError::Build(_) => "build",
+            AppError::Emit(_) => "emit",
+            AppError::WindowGone(_) => "window_gone",
+            AppError::InvalidParam(_) => "invalid_param",
+            AppError::Other(_) => "other",
+        }
+    }
+
+    /// Stable code for matching on the error programmatically
+    pub fn code(&self) -> Option<&'static str> {
+        match self {
+            AppError::WindowNotFound(_) => Some(WINDOW_NOT_FOUND_CODE),
+            _ => None,
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let code = self.code();
+        let mut state =
+            serializer.serialize_struct("AppError", if code.is_some() { 3 } else { 2 })?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        if let Some(code) = code {
+            state.serialize_field("code", code)?;
+        }
+        state.end()
+    }
+}
+
+/// The error for a window label that no window has
+pub(super) fn window_not_found(window_label: &str) -> AppError {
+    AppError::WindowNotFound(format!("Window not found: {}", window_label))
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Other(message)
+    }
+}
+
+/// Payload of the `window-error` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct WindowErrorPayload<'a> {
+    /// Command (or background task) that failed
+    source: &'a str,
+    error: &'a AppError,
+}
+
+/// Whether a failure is worth a `window-error` broadcast
+fn is_reportable(error: &AppError) -> bool {
+    !matches!(error, AppError::WindowGone(_))
+}
+
+/// Broadcasts a failure on `window-error`
+pub(super) fn report_error(app: &AppHandle, source: &str, error: &AppError) {
+    if !is_reportable(error) {
+        return;
+    }
+    if let Err(e) = app.emit(WINDOW_ERROR_EVENT, WindowErrorPayload { source, error }) {
+        log::warn!("[Window] Failed to emit {}: {}", WINDOW_ERROR_EVENT, e);
+    }
+}
+
+/// Runs a command body and reports its error on `window-error`
+///
+/// `reported!(app, "snap_window", { ... })` evaluates to the body's
+/// `Result<_, AppError>`; `?` on `String` errors turns them into
+/// `AppError::Other`.
+macro_rules! reported {
+    ($app:expr, $command:expr, { $($body:tt)* }) => {{
+        let reporter: tauri::AppHandle = $app.clone();
+        let result: Result<_, $crate::commands::window::error::AppError> =
+            async { $($body)* }.await;
+        if let Err(ref error) = result {
+            $crate::commands::window::error::report_error(&reporter, $command, error);
+        }
+        result
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_keeps_the_human_message() {
+        let error = window_not_found("session-window-a");
+        assert_eq!(error.to_string(), "Window not found: session-window-a");
+        assert_eq!(
+            AppError::from("Failed to get scale factor: gone".to_string()).to_string(),
+            "Failed to get scale factor: gone"
+        );
+    }
+
+    #[test]
+    fn test_window_gone_is_not_broadcast() {
+        let error = AppError::WindowGone("Window closed: session-window-a".to_string());
+        assert!(!is_reportable(&error));
+        assert!(is_reportable(&AppError::Emit("boom".to_string())));
+        assert_eq!(
+            serde_json::to_value(error).unwrap(),
+            serde_json::json!({ "kind": "window_gone", "message": "Window closed: session-window-a" })
+        );
+    }
+
+    #[test]
+    fn test_serializes_kind_and_message() {
+        assert_eq!(
+            serde_json::to_value(AppError::InvalidParam("Invalid opacity 2".to_string())).unwrap(),
+            serde_json::json!({ "kind": "invalid_param", "message": "Invalid opacity 2" })
+        );
+        let error = AppError::Emit("Failed to emit event: closed".to_string());
+        assert_eq!(
+            serde_json::to_value(WindowErrorPayload {
+                source: "emit_to_window",
+                error: &error,
+            })
+            .unwrap(),
+            serde_json::json!({
+                "source": "emit_to_window",
+                "error": { "kind": "emit", "message": "Failed to emit event: closed" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_only_missing_windows_carry_the_code() {
+        assert_eq!(
+            serde_json::to_value(window_not_found("session-window-a")).unwrap(),
+            serde_json::json!({
+                "kind": "not_found",
+                "message": "Window not found: session-window-a",
+                "code": "window_not_found"
+            })
+        );
+
+        // Other missing things keep the kind but not the window code
+        assert_eq!(
+            serde_json::to_value(AppError::NotFound("Main window not found".to_string())).unwrap(),
+            serde_json::json!({ "kind": "not_found", "message": "Main window not found" })
+        );
+    }
+}
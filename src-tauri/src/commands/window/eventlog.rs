@@ -0,0 +1,286 @@
+/**
+ * Local Window Event Log
+ *
+ * Opt-in diagnostics of how session windows are used: when they are created,
+ * focused, reattached and closed, and with which engine. The events stay in a
+ * bounded in-memory buffer the user can inspect or export from the frontend;
+ * nothing is sent anywhere.
+ */
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+use super::error::AppError;
+use super::registry::{with_registry, WindowMeta, WindowRegistry};
+use super::Engine;
+
+/// Number of events kept; older ones are dropped first
+const WINDOW_EVENT_LOG_CAPACITY: usize = 500;
+
+/// What happened to a session window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowEventKind {
+    Created,
+    Closed,
+    Focused,
+    Reattached,
+}
+
+/// One entry of the event log
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WindowEventRecord {
+    /// Increasing sequence number, the cursor for `get_window_events`
+    pub seq: u64,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    pub kind: WindowEventKind,
+    pub window_label: String,
+    pub tab_id: String,
+    /// Execution engine of the session, if known
+    pub engine: Option<Engine>,
+}
+
+#[derive(Debug, Default)]
+struct EventBuffer {
+    events: VecDeque<WindowEventRecord>,
+    next_seq: u64,
+}
+
+/// Event log of the session windows, managed as app state
+#[derive(Debug, Default)]
+pub struct WindowEventLog {
+    /// Off until the user opts in
+    enabled: AtomicBool,
+    buffer: Mutex<EventBuffer>,
+}
+
+impl WindowEventLog {
+    /// Turns recording on or off; turning it off also drops what was collected
+    fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+        if !enabled {
+            if let Ok(mut buffer) = self.buffer.lock() {
+                buffer.events.clear();
+            }
+        }
+    }
+
+    /// Appends an event for a window, dropping the oldest one when full
+    fn record(&self, kind: WindowEventKind, meta: &WindowMeta, timestamp: u64) {
+        if !self.enabled.load(Ordering::SeqCst) {
+            return;
+        }
+        let Ok(mut buffer) = self.buffer.lock() else {
+            return;
+        };
+        buffer.next_seq += 1;
+        let record = WindowEventRecord {
+            seq: buffer.next_seq,
+            timestamp,
+            kind,
+            window_label: meta.window_label.clone(),
+            tab_id: meta.tab_id.clone(),
+            engine: meta.engine,
+        };
+        if buffer.events.len() == WINDOW_EVENT_LOG_CAPACITY {
+            buffer.events.pop_front();
+        }
+        buffer.events.push_back(record);
+    }
+
+    /// Events with a sequence number above `since`, oldest first
+    fn since(&self, since: Option<u64>) -> Vec<WindowEventRecord> {
+        let since = since.unwrap_or(0);
+        self.buffer
+            .lock()
+            .map(|buffer| {
+                buffer
+                    .events
+                    .iter()
+                    .filter(|event| event.seq > since)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Records an event for a registered session window
+///
+/// Must run while the window is still in the registry, i.e. before it is
+/// unregistered on close.
+pub(super) fn record_window_event(app: &AppHandle, kind: WindowEventKind, window_label: &str) {
+    let Some(log) = app.try_state::<WindowEventLog>() else {
+        return;
+    };
+    if let Some(meta) = with_registry(app, |registry| registry.get(window_label)).flatten() {
+        log.record(kind, &meta, now_millis());
+    }
+}
+
+/// Drops a window closed by a command from the registry, recording `Closed` first
+///
+/// The `Destroyed` handler only records windows that are still registered, and
+/// a closed window is unregistered before that arrives. A busy window only
+/// asks for confirmation, so it stays registered and is recorded once it
+/// actually closes.
+fn unregister_closed_window(
+    registry: &WindowRegistry,
+    log: Option<&WindowEventLog>,
+    window_label: &str,
+    timestamp: u64,
+) {
+    if registry.is_busy(window_label) {
+        return;
+    }
+    if let (Some(log), Some(meta)) = (log, registry.get(window_label)) {
+        log.record(WindowEventKind::Closed, &meta, timestamp);
+    }
+    registry.unregister(window_label);
+}
+
+/// Unregisters a window closed by `close_session_window` and its variants
+pub(super) fn unregister_closed(app: &AppHandle, window_label: &str) {
+    let log = app.try_state::<WindowEventLog>();
+    with_registry(app, |registry| {
+        unregister_closed_window(
+            registry,
+            log.as_ref().map(|log| log.inner()),
+            window_label,
+            now_millis(),
+        )
+    });
+}
+
+/// Turns the local window event log on or off
+///
+/// Turning it off discards the events collected so far.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `enabled` - Whether to record window events (default: false)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_event_log_enabled(app: AppHandle, enabled: bool) -> Result<(), AppError> {
+    reported!(app, "set_window_event_log_enabled", {
+        let log = app
+            .try_state::<WindowEventLog>()
+            .ok_or_else(|| AppError::Other("Window event log is not available".to_string()))?;
+        log.set_enabled(enabled);
+        log::info!(
+            "[Window] Window event log {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+        Ok(())
+    })
+}
+
+/// Gets the recorded window events
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `since` - Only return events with a higher `seq` (all events if None)
+///
+/// # Returns
+/// * `Result<Vec<WindowEventRecord>, AppError>` - The events, oldest first
+#[tauri::command]
+pub async fn get_window_events(
+    app: AppHandle,
+    since: Option<u64>,
+) -> Result<Vec<WindowEventRecord>, AppError> {
+    reported!(app, "get_window_events", {
+        Ok(app
+            .try_state::<WindowEventLog>()
+            .map(|log| log.since(since))
+            .unwrap_or_default())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta() -> WindowMeta {
+        WindowMeta::new("session-window-a", "a", None, None, Some(Engine::Codex))
+    }
+
+    #[test]
+    fn test_create_and_close_produce_event_records() {
+        let log = WindowEventLog::default();
+        // Nothing is kept before the user opts in
+        log.record(WindowEventKind::Created, &meta(), 1);
+        assert!(log.since(None).is_empty());
+
+        log.set_enabled(true);
+        log.record(WindowEventKind::Created, &meta(), 10);
+        log.record(WindowEventKind::Closed, &meta(), 20);
+
+        let events = log.since(None);
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            serde_json::to_value(&events[0]).unwrap(),
+            serde_json::json!({
+                "seq": 1,
+                "timestamp": 10,
+                "kind": "created",
+                "window_label": "session-window-a",
+                "tab_id": "a",
+                "engine": "codex"
+            })
+        );
+        assert_eq!(events[1].kind, WindowEventKind::Closed);
+        assert_eq!(log.since(Some(1)), vec![events[1].clone()]);
+    }
+
+    #[test]
+    fn test_programmatic_close_is_recorded_before_unregistering() {
+        let registry = WindowRegistry::default();
+        let log = WindowEventLog::default();
+        log.set_enabled(true);
+
+        registry.register(meta());
+        registry.update("session-window-a", |meta| meta.busy = true);
+        unregister_closed_window(&registry, Some(&log), "session-window-a", 10);
+        // Held back by the busy guard: still open, nothing recorded
+        assert!(registry.get("session-window-a").is_some());
+        assert!(log.since(None).is_empty());
+
+        registry.update("session-window-a", |meta| meta.busy = false);
+        unregister_closed_window(&registry, Some(&log), "session-window-a", 20);
+        assert_eq!(registry.get("session-window-a"), None);
+        let events = log.since(None);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, WindowEventKind::Closed);
+        assert_eq!(events[0].tab_id, "a");
+        assert_eq!(events[0].timestamp, 20);
+    }
+
+    #[test]
+    fn test_event_log_keeps_only_the_latest_events() {
+        let log = WindowEventLog::default();
+        log.set_enabled(true);
+        for i in 0..WINDOW_EVENT_LOG_CAPACITY + 5 {
+            log.record(WindowEventKind::Focused, &meta(), i as u64);
+        }
+
+        let events = log.since(None);
+        assert_eq!(events.len(), WINDOW_EVENT_LOG_CAPACITY);
+        assert_eq!(events[0].seq, 6);
+
+        log.set_enabled(false);
+        assert!(log.since(None).is_empty());
+    }
+}
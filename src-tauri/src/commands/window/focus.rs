@@ -0,0 +1,298 @@
+/**
+ * Window Focus
+ *
+ * Lets features like auto-dim-on-blur and the active-session indicator ask
+ * for the focus state directly instead of replaying focus events. Which
+ * session window has focus is tracked in the registry from those events.
+ *
+ * Also brings a window to the front: `set_focus` alone leaves a minimized
+ * window minimized on some platforms (Windows, several X11 window managers),
+ * so it is unminimized first.
+ *
+ * Or raises a window without focusing it, for attention and notification
+ * flows where the user is typing elsewhere and mustn't lose the keyboard.
+ */
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+
+/// What `bring_to_front` needs from a window
+trait FocusTarget {
+    fn is_minimized(&self) -> Result<bool, String>;
+    fn unminimize(&self) -> Result<(), String>;
+    fn set_focus(&self) -> Result<(), String>;
+}
+
+impl FocusTarget for WebviewWindow {
+    fn is_minimized(&self) -> Result<bool, String> {
+        WebviewWindow::is_minimized(self)
+            .map_err(|e| format!("Failed to get minimized state: {}", e))
+    }
+
+    fn unminimize(&self) -> Result<(), String> {
+        WebviewWindow::unminimize(self).map_err(|e| format!("Failed to unminimize window: {}", e))
+    }
+
+    fn set_focus(&self) -> Result<(), String> {
+        WebviewWindow::set_focus(self).map_err(|e| format!("Failed to focus window: {}", e))
+    }
+}
+
+/// Unminimizes a window if needed, then focuses it
+///
+/// Both calls are queued on the event loop in this order, so the focus
+/// request always reaches a window that is no longer minimized.
+fn bring_target_to_front(window: &impl FocusTarget) -> Result<(), String> {
+    if window.is_minimized().unwrap_or(false) {
+        window.unminimize()?;
+    }
+    window.set_focus()
+}
+
+/// Brings a window to the front and gives it focus, restoring it if minimized
+pub(super) fn bring_to_front(window: &WebviewWindow) -> Result<(), String> {
+    bring_target_to_front(window)
+}
+
+/// What the portable `raise_window` fallback needs from a window
+#[cfg_attr(
+    all(any(target_os = "windows", target_os = "macos"), not(test)),
+    allow(dead_code)
+)]
+trait RaiseTarget: FocusTarget {
+    fn is_always_on_top(&self) -> Result<bool, String>;
+    fn set_always_on_top(&self, always_on_top: bool) -> Result<(), String>;
+}
+
+impl RaiseTarget for WebviewWindow {
+    fn is_always_on_top(&self) -> Result<bool, String> {
+        WebviewWindow::is_always_on_top(self)
+            .map_err(|e| format!("Failed to get always-on-top state: {}", e))
+    }
+
+    fn set_always_on_top(&self, always_on_top: bool) -> Result<(), String> {
+        WebviewWindow::set_always_on_top(self, always_on_top)
+            .map_err(|e| format!("Failed to set always on top: {}", e))
+    }
+}
+
+/// Raises a window by pinning it on top for a moment
+///
+/// Window managers raise a window that becomes always-on-top without
+/// activating it. A window that already is stays pinned.
+#[cfg_attr(
+    all(any(target_os = "windows", target_os = "macos"), not(test)),
+    allow(dead_code)
+)]
+fn raise_target_without_focus(window: &impl RaiseTarget) -> Result<(), String> {
+    if window.is_minimized().unwrap_or(false) {
+        window.unminimize()?;
+    }
+    if window.is_always_on_top().unwrap_or(false) {
+        return Ok(());
+    }
+    window.set_always_on_top(true)?;
+    window.set_always_on_top(false)
+}
+
+/// Moves the window to the top of the z-order with `SWP_NOACTIVATE`
+#[cfg(target_os = "windows")]
+fn raise_native(window: &WebviewWindow) -> Result<(), String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        IsIconic, SetWindowPos, ShowWindow, HWND_TOP, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
+        SW_SHOWNOACTIVATE,
+    };
+
+    let hwnd = window
+        .hwnd()
+        .map_err(|e| format!("Failed to get window handle: {}", e))?;
+    let hwnd = HWND(hwnd.0);
+
+    unsafe {
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+        }
+        SetWindowPos(
+            hwnd,
+            HWND_TOP,
+            0,
+            0,
+            0,
+            0,
+            SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+        )
+    }
+    .map_err(|e| format!("Failed to raise window: {}", e))
+}
+
+/// Orders the NSWindow to the front without making it key
+#[cfg(target_os = "macos")]
+fn raise_native(window: &WebviewWindow) -> Result<(), String> {
+    use objc2_app_kit::NSWindow;
+
+    let ns_window = window
+        .ns_window()
+        .map_err(|e| format!("Failed to get NSWindow: {}", e))?;
+    let ns_window = ns_window as usize;
+
+    // AppKit objects may only be touched from the main thread
+    window
+        .run_on_main_thread(move || unsafe {
+            let ns_window = &*(ns_window as *const NSWindow);
+            if ns_window.isMiniaturized() {
+                ns_window.deminiaturize(None);
+            }
+            ns_window.orderFront(None);
+        })
+        .map_err(|e| format!("Failed to run on main thread: {}", e))
+}
+
+/// No native raise without activation; best effort through always-on-top
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn raise_native(window: &WebviewWindow) -> Result<(), String> {
+    raise_target_without_focus(window)
+}
+
+/// Raises a window above the others without giving it keyboard focus
+///
+/// Unlike `focus_session_window`, which brings the window to the front and
+/// activates it, this only changes the z-order: whatever the user is typing
+/// into keeps focus. A minimized window is restored. On Windows and macOS
+/// this is a native call; elsewhere it is best effort, as window managers may
+/// refuse to raise an inactive window.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn raise_window(app: AppHandle, window_label: String) -> Result<(), AppError> {
+    reported!(app, "raise_window", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+        raise_native(&window)?;
+        window_log!(debug, window_label, "Raised without focus");
+        Ok(())
+    })
+}
+
+/// Gets whether a window has keyboard focus
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+///
+/// # Returns
+/// * `Result<bool, AppError>` - Whether the window is focused or an error message
+#[tauri::command]
+pub async fn is_window_focused(app: AppHandle, window_label: String) -> Result<bool, AppError> {
+    reported!(app, "is_window_focused", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        Ok(window
+            .is_focused()
+            .map_err(|e| format!("Failed to get focus state: {}", e))?)
+    })
+}
+
+/// Gets the session window that currently has focus
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+///
+/// # Returns
+/// * `Result<Option<String>, AppError>` - The focused window's label, or None
+///   when the focus is elsewhere (e.g. on the main window)
+#[tauri::command]
+pub async fn get_focused_session_window(app: AppHandle) -> Result<Option<String>, AppError> {
+    reported!(app, "get_focused_session_window", {
+        Ok(with_registry(&app, |registry| registry.focused_label())
+            .flatten()
+            .filter(|label| app.get_webview_window(label).is_some()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct FakeWindow {
+        minimized: RefCell<bool>,
+        always_on_top: RefCell<bool>,
+        calls: RefCell<Vec<&'static str>>,
+    }
+
+    impl FocusTarget for FakeWindow {
+        fn is_minimized(&self) -> Result<bool, String> {
+            Ok(*self.minimized.borrow())
+        }
+
+        fn unminimize(&self) -> Result<(), String> {
+            *self.minimized.borrow_mut() = false;
+            self.calls.borrow_mut().push("unminimize");
+            Ok(())
+        }
+
+        fn set_focus(&self) -> Result<(), String> {
+            // Focusing a minimized window is what silently does nothing
+            assert!(!*self.minimized.borrow(), "focused while still minimized");
+            self.calls.borrow_mut().push("set_focus");
+            Ok(())
+        }
+    }
+
+    impl RaiseTarget for FakeWindow {
+        fn is_always_on_top(&self) -> Result<bool, String> {
+            Ok(*self.always_on_top.borrow())
+        }
+
+        fn set_always_on_top(&self, always_on_top: bool) -> Result<(), String> {
+            *self.always_on_top.borrow_mut() = always_on_top;
+            self.calls
+                .borrow_mut()
+                .push(if always_on_top { "pin" } else { "unpin" });
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_minimized_window_is_restored_before_focus() {
+        let window = FakeWindow::default();
+        *window.minimized.borrow_mut() = true;
+
+        bring_target_to_front(&window).unwrap();
+        assert!(!*window.minimized.borrow());
+        assert_eq!(*window.calls.borrow(), vec!["unminimize", "set_focus"]);
+
+        // A window that isn't minimized is only focused
+        window.calls.borrow_mut().clear();
+        bring_target_to_front(&window).unwrap();
+        assert_eq!(*window.calls.borrow(), vec!["set_focus"]);
+    }
+
+    #[test]
+    fn test_raise_fallback_never_focuses() {
+        let window = FakeWindow::default();
+        *window.minimized.borrow_mut() = true;
+
+        raise_target_without_focus(&window).unwrap();
+        assert_eq!(*window.calls.borrow(), vec!["unminimize", "pin", "unpin"]);
+        assert!(!*window.always_on_top.borrow());
+
+        // A pinned window is already on top and stays pinned
+        window.calls.borrow_mut().clear();
+        *window.always_on_top.borrow_mut() = true;
+        raise_target_without_focus(&window).unwrap();
+        assert!(window.calls.borrow().is_empty());
+        assert!(*window.always_on_top.borrow());
+    }
+}
@@ -0,0 +1,717 @@
+/**
+ * Session Window Geometry Persistence
+ *
+ * Remembers the position and size of detached session windows so that
+ * re-detaching a tab reopens its window where the user last left it.
+ * Geometry is stored per project and tab_id in a JSON file under the app
+ * data dir, so the same tab_id in two projects doesn't share a position.
+ */
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Monitor, WebviewWindow};
+
+use crate::utils::config_utils::{load_json_config, save_json_config};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+use super::window_state_file;
+
+/// File name of the geometry store inside the app data dir
+const GEOMETRY_FILE: &str = "session_window_geometry.json";
+
+/// Serializes read-modify-write cycles on the geometry file
+static GEOMETRY_LOCK: Mutex<()> = Mutex::new(());
+
+/// Offset between consecutively created windows, in logical pixels
+pub(super) const CASCADE_STEP: f64 = 30.0;
+
+/// Position of the last session window placed by cascading
+static LAST_CASCADE_POSITION: Mutex<Option<(f64, f64)>> = Mutex::new(None);
+
+/// Position and size of a window in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl WindowGeometry {
+    /// Returns true if this rectangle overlaps the other one by a non-zero area
+    pub(super) fn intersects(&self, other: &WindowGeometry) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+}
+
+/// Normal, maximized, minimized or fullscreen state of a window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowState {
+    #[default]
+    Normal,
+    Maximized,
+    Minimized,
+    Fullscreen,
+}
+
+impl WindowState {
+    /// Whether the window fills the screen, so its position doesn't matter
+    pub fn covers_screen(self) -> bool {
+        matches!(self, WindowState::Maximized | WindowState::Fullscreen)
+    }
+}
+
+/// On-disk layout of the geometry store
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GeometryStore {
+    /// Saved geometry keyed by `store_key`
+    #[serde(default)]
+    windows: HashMap<String, WindowGeometry>,
+    /// Saved state keyed by `store_key`; tabs without an entry were normal
+    #[serde(default)]
+    states: HashMap<String, WindowState>,
+}
+
+/// Key of a tab's entries in the geometry store.
+///
+/// `<project hash>/<tab_id>`, where the hash is taken from the canonical
+/// project path so different spellings of the same folder agree. Windows
+/// without a project use the bare tab_id, which is also how entries were
+/// keyed before projects were taken into account.
+fn store_key(project_path: Option<&str>, tab_id: &str) -> String {
+    let Some(project_path) = project_path else {
+        return tab_id.to_string();
+    };
+    let canonical = std::fs::canonicalize(project_path)
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| project_path.to_string());
+    let digest = Sha256::digest(canonical.as_bytes());
+    let hash: String = digest[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("{}/{}", hash, tab_id)
+}
+
+impl GeometryStore {
+    /// Moves a tab's entries saved before projects were part of the key to `key`.
+    ///
+    /// Returns whether anything moved, i.e. whether the store needs saving.
+    fn migrate_legacy(&mut self, key: &str, tab_id: &str) -> bool {
+        if key == tab_id || self.windows.contains_key(key) {
+            return false;
+        }
+        let Some(geometry) = self.windows.remove(tab_id) else {
+            return false;
+        };
+        self.windows.insert(key.to_string(), geometry);
+        if let Some(state) = self.states.remove(tab_id) {
+            self.states.insert(key.to_string(), state);
+        }
+        true
+    }
+
+    fn insert(&mut self, key: &str, geometry: WindowGeometry, state: WindowState) {
+        self.windows.insert(key.to_string(), geometry);
+        // Normal is the default, no need to keep it around
+        if state == WindowState::Normal {
+            self.states.remove(key);
+        } else {
+            self.states.insert(key.to_string(), state);
+        }
+    }
+}
+
+/// Extracts the tab_id from a `session-window-*` label
+fn tab_id_from_label(window_label: &str) -> Result<&str, String> {
+    super::tab_id_from_label(window_label)
+        .ok_or_else(|| format!("Not a session window: {}", window_label))
+}
+
+/// Returns true if the geometry is at least partially visible on one of the screens.
+///
+/// A saved position that lands entirely outside every screen (e.g. the monitor it
+/// was on has been unplugged) is treated as lost.
+pub fn is_on_screen(geometry: &WindowGeometry, screens: &[WindowGeometry]) -> bool {
+    screens.iter().any(|screen| geometry.intersects(screen))
+}
+
+/// Full bounds of a monitor in logical pixels
+pub(super) fn bounds_of(monitor: &Monitor) -> WindowGeometry {
+    let scale = monitor.scale_factor();
+    let position = monitor.position().to_logical::<f64>(scale);
+    let size = monitor.size().to_logical::<f64>(scale);
+    WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    }
+}
+
+/// Collects the bounds of all connected monitors in logical pixels
+pub(super) fn screen_bounds(app: &AppHandle) -> Vec<WindowGeometry> {
+    match app.available_monitors() {
+        Ok(monitors) => monitors.iter().map(bounds_of).collect(),
+        Err(e) => {
+            log::warn!("[Window] Failed to enumerate monitors: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// A connected monitor's name and work area in logical pixels
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct NamedWorkArea {
+    pub(super) name: Option<String>,
+    pub(super) area: WindowGeometry,
+}
+
+pub(super) fn work_area_of(monitor: &Monitor) -> NamedWorkArea {
+    let scale = monitor.scale_factor();
+    let work_area = monitor.work_area();
+    let position = work_area.position.to_logical::<f64>(scale);
+    let size = work_area.size.to_logical::<f64>(scale);
+    NamedWorkArea {
+        name: monitor.name().cloned(),
+        area: WindowGeometry {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        },
+    }
+}
+
+/// Picks the work area a new window should open on.
+///
+/// A `monitor_hint` wins if that monitor is still connected; if it isn't, the
+/// primary monitor is used. Without a hint, the monitor the main window is on
+/// is preferred, then the primary one, then whatever is connected.
+fn choose_work_area(
+    screens: &[NamedWorkArea],
+    monitor_hint: Option<&str>,
+    main_monitor: Option<&str>,
+    primary_monitor: Option<&str>,
+) -> Option<WindowGeometry> {
+    let find = |name: Option<&str>| {
+        name.and_then(|name| {
+            screens
+                .iter()
+                .find(|screen| screen.name.as_deref() == Some(name))
+        })
+    };
+
+    let chosen = match monitor_hint {
+        Some(hint) => find(Some(hint)).or_else(|| {
+            log::info!(
+                "[Window] Monitor {} is no longer connected, using the primary monitor",
+                hint
+            );
+            find(primary_monitor)
+        }),
+        None => find(main_monitor).or_else(|| find(primary_monitor)),
+    };
+
+    chosen.or_else(|| screens.first()).map(|screen| screen.area)
+}
+
+/// Returns the position that centers a window of the given size in the area
+pub(super) fn center_in(area: &WindowGeometry, width: f64, height: f64) -> (f64, f64) {
+    (
+        area.x + ((area.width - width) / 2.0).max(0.0),
+        area.y + ((area.height - height) / 2.0).max(0.0),
+    )
+}
+
+/// Returns the position of the next cascaded window.
+///
+/// Each window is offset by `CASCADE_STEP` down and right from the previous
+/// one; the first window, a window on another monitor and a window that would
+/// run off the work area all go back to the center.
+fn next_cascade_position(
+    last: Option<(f64, f64)>,
+    area: &WindowGeometry,
+    width: f64,
+    height: f64,
+) -> (f64, f64) {
+    let center = center_in(area, width, height);
+    let Some((last_x, last_y)) = last else {
+        return center;
+    };
+
+    let x = last_x + CASCADE_STEP;
+    let y = last_y + CASCADE_STEP;
+    let in_area = last_x >= area.x
+        && last_y >= area.y
+        && x + width <= area.x + area.width
+        && y + height <= area.y + area.height;
+
+    if in_area {
+        (x, y)
+    } else {
+        center
+    }
+}
+
+/// Computes where to place a new window of the given size (logical pixels).
+///
+/// Windows are cascaded from the center of the chosen monitor so several
+/// detached tabs don't stack on top of each other. Returns None when no
+/// monitor information is available, in which case the caller falls back to
+/// the builder's own centering.
+pub fn cascaded_position(
+    app: &AppHandle,
+    monitor_hint: Option<&str>,
+    width: f64,
+    height: f64,
+) -> Option<(f64, f64)> {
+    let screens: Vec<NamedWorkArea> = match app.available_monitors() {
+        Ok(monitors) => monitors.iter().map(work_area_of).collect(),
+        Err(e) => {
+            log::warn!("[Window] Failed to enumerate monitors: {}", e);
+            return None;
+        }
+    };
+
+    let main_monitor = app
+        .get_webview_window("main")
+        .and_then(|main| main.current_monitor().ok().flatten())
+        .and_then(|monitor| monitor.name().cloned());
+    let primary_monitor = app
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .and_then(|monitor| monitor.name().cloned());
+
+    let area = choose_work_area(
+        &screens,
+        monitor_hint,
+        main_monitor.as_deref(),
+        primary_monitor.as_deref(),
+    )?;
+
+    let mut last = LAST_CASCADE_POSITION.lock().ok()?;
+    let position = next_cascade_position(*last, &area, width, height);
+    *last = Some(position);
+    Some(position)
+}
+
+/// Makes the next cascaded window land one step off the given position
+pub(super) fn cascade_from(x: f64, y: f64) {
+    if let Ok(mut last) = LAST_CASCADE_POSITION.lock() {
+        *last = Some((x, y));
+    }
+}
+
+/// Reads the current geometry of a live window in logical pixels
+pub(super) fn capture_geometry(window: &WebviewWindow) -> Result<WindowGeometry, String> {
+    let scale = window
+        .scale_factor()
+        .map_err(|e| format!("Failed to get scale factor: {}", e))?;
+    let position = window
+        .outer_position()
+        .map_err(|e| format!("Failed to get window position: {}", e))?
+        .to_logical::<f64>(scale);
+    let size = window
+        .inner_size()
+        .map_err(|e| format!("Failed to get window size: {}", e))?
+        .to_logical::<f64>(scale);
+
+    Ok(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Reads the current state of a live window
+pub(super) fn capture_state(window: &WebviewWindow) -> WindowState {
+    if window.is_fullscreen().unwrap_or(false) {
+        WindowState::Fullscreen
+    } else if window.is_maximized().unwrap_or(false) {
+        WindowState::Maximized
+    } else if window.is_minimized().unwrap_or(false) {
+        WindowState::Minimized
+    } else {
+        WindowState::Normal
+    }
+}
+
+/// Persists the geometry and state of a tab's window, replacing any previous entry
+pub(super) fn store_geometry(
+    app: &AppHandle,
+    project_path: Option<&str>,
+    tab_id: &str,
+    geometry: WindowGeometry,
+    state: WindowState,
+) -> Result<(), String> {
+    let path = window_state_file(app, GEOMETRY_FILE)?;
+    let _guard = GEOMETRY_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let mut store: GeometryStore = load_json_config(&path)?;
+    let key = store_key(project_path, tab_id);
+    store.migrate_legacy(&key, tab_id);
+    store.insert(&key, geometry, state);
+    save_json_config(&store, &path)
+}
+
+/// Loads the saved geometry and state of a tab's window, if any
+///
+/// Entries from before projects were part of the key are migrated on the way.
+fn load_entry(
+    app: &AppHandle,
+    project_path: Option<&str>,
+    tab_id: &str,
+) -> Option<(WindowGeometry, Option<WindowState>)> {
+    let path = window_state_file(app, GEOMETRY_FILE).ok()?;
+    let _guard = GEOMETRY_LOCK.lock().ok()?;
+
+    let mut store = match load_json_config::<GeometryStore>(&path) {
+        Ok(store) => store,
+        Err(e) => {
+            log::warn!("[Window] Failed to load window geometry: {}", e);
+            return None;
+        }
+    };
+    let key = store_key(project_path, tab_id);
+    if store.migrate_legacy(&key, tab_id) {
+        if let Err(e) = save_json_config(&store, &path) {
+            log::warn!(
+                "[Window] Failed to migrate geometry of tab {}: {}",
+                tab_id,
+                e
+            );
+        }
+    }
+
+    let geometry = store.windows.get(&key).copied()?;
+    Some((geometry, store.states.get(&key).copied()))
+}
+
+/// Returns the state a tab's window was last closed in, if it wasn't normal
+pub(super) fn saved_state_for_tab(
+    app: &AppHandle,
+    project_path: Option<&str>,
+    tab_id: &str,
+) -> Option<WindowState> {
+    load_entry(app, project_path, tab_id).and_then(|(_, state)| state)
+}
+
+/// Returns the saved geometry for a tab only if it is still visible on a connected screen
+pub fn saved_geometry_for_tab(
+    app: &AppHandle,
+    project_path: Option<&str>,
+    tab_id: &str,
+) -> Option<WindowGeometry> {
+    let (geometry, _) = load_entry(app, project_path, tab_id)?;
+
+    if is_on_screen(&geometry, &screen_bounds(app)) {
+        Some(geometry)
+    } else {
+        log::info!(
+            "[Window] Saved geometry for tab {} is off-screen, falling back to center",
+            tab_id
+        );
+        None
+    }
+}
+
+/// Project of a session window, as recorded in the registry
+fn project_path_of(app: &AppHandle, window_label: &str) -> Option<String> {
+    with_registry(app, |registry| registry.get(window_label))
+        .flatten()
+        .and_then(|meta| meta.project_path)
+}
+
+/// Captures and persists the geometry of a session window.
+///
+/// Called from the window's close handler so the next detach of the same tab
+/// can reopen at the same place.
+pub fn persist_window_geometry(
+    app: &AppHandle,
+    window: &WebviewWindow,
+) -> Result<WindowGeometry, String> {
+    let tab_id = tab_id_from_label(window.label())?;
+    let geometry = capture_geometry(window)?;
+    let project_path = project_path_of(app, window.label());
+    store_geometry(
+        app,
+        project_path.as_deref(),
+        tab_id,
+        geometry,
+        capture_state(window),
+    )?;
+
+    window_log!(debug, window.label(), "Saved geometry: {:?}", geometry);
+    Ok(geometry)
+}
+
+/// Saves the current position and size of a session window
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window to save
+///
+/// # Returns
+/// * `Result<WindowGeometry, AppError>` - The saved geometry or an error message
+#[tauri::command]
+pub async fn save_window_geometry(
+    app: AppHandle,
+    window_label: String,
+) -> Result<WindowGeometry, AppError> {
+    reported!(app, "save_window_geometry", {
+        if let Some(window) = app.get_webview_window(&window_label) {
+            Ok(persist_window_geometry(&app, &window)?)
+        } else {
+            Err(window_not_found(&window_label))
+        }
+    })
+}
+
+/// Moves a session window back to its saved position and size
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window to restore
+///
+/// # Returns
+/// * `Result<bool, AppError>` - Whether a saved on-screen geometry was applied
+#[tauri::command]
+pub async fn restore_window_geometry(
+    app: AppHandle,
+    window_label: String,
+) -> Result<bool, AppError> {
+    reported!(app, "restore_window_geometry", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+        let tab_id = tab_id_from_label(&window_label)?;
+
+        let project_path = project_path_of(&app, &window_label);
+        let Some(geometry) = saved_geometry_for_tab(&app, project_path.as_deref(), tab_id) else {
+            return Ok(false);
+        };
+
+        window
+            .set_size(tauri::LogicalSize::new(geometry.width, geometry.height))
+            .map_err(|e| format!("Failed to resize window: {}", e))?;
+        window
+            .set_position(tauri::LogicalPosition::new(geometry.x, geometry.y))
+            .map_err(|e| format!("Failed to move window: {}", e))?;
+
+        Ok(true)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> WindowGeometry {
+        WindowGeometry {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_is_on_screen_inside_monitor() {
+        let screens = vec![rect(0.0, 0.0, 1920.0, 1080.0)];
+        assert!(is_on_screen(&rect(100.0, 100.0, 1000.0, 700.0), &screens));
+    }
+
+    #[test]
+    fn test_is_on_screen_partially_visible() {
+        let screens = vec![rect(0.0, 0.0, 1920.0, 1080.0)];
+        assert!(is_on_screen(&rect(1800.0, 900.0, 1000.0, 700.0), &screens));
+    }
+
+    #[test]
+    fn test_is_on_screen_unplugged_monitor() {
+        // Window was on a second monitor to the right that is no longer connected
+        let screens = vec![rect(0.0, 0.0, 1920.0, 1080.0)];
+        assert!(!is_on_screen(&rect(2200.0, 100.0, 1000.0, 700.0), &screens));
+        assert!(!is_on_screen(&rect(1920.0, 0.0, 1000.0, 700.0), &screens));
+    }
+
+    #[test]
+    fn test_is_on_screen_secondary_monitor() {
+        let screens = vec![
+            rect(0.0, 0.0, 1920.0, 1080.0),
+            rect(-2560.0, 0.0, 2560.0, 1440.0),
+        ];
+        assert!(is_on_screen(&rect(-1500.0, 200.0, 1000.0, 700.0), &screens));
+    }
+
+    #[test]
+    fn test_is_on_screen_no_monitors() {
+        assert!(!is_on_screen(&rect(0.0, 0.0, 1000.0, 700.0), &[]));
+    }
+
+    fn screen(name: &str, x: f64) -> NamedWorkArea {
+        NamedWorkArea {
+            name: Some(name.to_string()),
+            area: rect(x, 0.0, 1920.0, 1040.0),
+        }
+    }
+
+    #[test]
+    fn test_choose_work_area_prefers_hint() {
+        let screens = [screen("primary", 0.0), screen("secondary", 1920.0)];
+        let area = choose_work_area(
+            &screens,
+            Some("secondary"),
+            Some("primary"),
+            Some("primary"),
+        );
+        assert_eq!(area, Some(screens[1].area));
+    }
+
+    #[test]
+    fn test_choose_work_area_disconnected_hint_uses_primary() {
+        let screens = [screen("primary", 0.0), screen("secondary", 1920.0)];
+        let area = choose_work_area(&screens, Some("gone"), Some("secondary"), Some("primary"));
+        assert_eq!(area, Some(screens[0].area));
+    }
+
+    #[test]
+    fn test_choose_work_area_defaults_to_main_window_monitor() {
+        let screens = [screen("primary", 0.0), screen("secondary", 1920.0)];
+        assert_eq!(
+            choose_work_area(&screens, None, Some("secondary"), Some("primary")),
+            Some(screens[1].area)
+        );
+        assert_eq!(
+            choose_work_area(&screens, None, None, Some("primary")),
+            Some(screens[0].area)
+        );
+        assert_eq!(choose_work_area(&[], None, None, None), None);
+    }
+
+    #[test]
+    fn test_center_in_work_area() {
+        let area = rect(1920.0, 40.0, 1920.0, 1000.0);
+        assert_eq!(center_in(&area, 1000.0, 700.0), (2380.0, 190.0));
+        // Larger than the area: pin to the top-left corner
+        assert_eq!(center_in(&area, 2500.0, 1200.0), (1920.0, 40.0));
+    }
+
+    #[test]
+    fn test_cascade_produces_distinct_positions() {
+        let area = rect(0.0, 0.0, 1920.0, 1040.0);
+        let first = next_cascade_position(None, &area, 1000.0, 700.0);
+        let second = next_cascade_position(Some(first), &area, 1000.0, 700.0);
+        let third = next_cascade_position(Some(second), &area, 1000.0, 700.0);
+
+        assert_eq!(first, (460.0, 170.0));
+        assert_eq!(second, (490.0, 200.0));
+        assert_eq!(third, (520.0, 230.0));
+    }
+
+    #[test]
+    fn test_cascade_resets_at_screen_edge() {
+        let area = rect(0.0, 0.0, 1920.0, 1040.0);
+        let mut position = next_cascade_position(None, &area, 1000.0, 700.0);
+        let mut steps = 0;
+        loop {
+            let next = next_cascade_position(Some(position), &area, 1000.0, 700.0);
+            if next == center_in(&area, 1000.0, 700.0) {
+                break;
+            }
+            assert!(next.1 + 700.0 <= 1040.0);
+            position = next;
+            steps += 1;
+        }
+        // (1040 - 700 - 170) / 30 steps fit below the center
+        assert_eq!(steps, 5);
+    }
+
+    #[test]
+    fn test_cascade_from_other_monitor_starts_at_center() {
+        let area = rect(1920.0, 0.0, 1920.0, 1040.0);
+        assert_eq!(
+            next_cascade_position(Some((100.0, 100.0)), &area, 1000.0, 700.0),
+            center_in(&area, 1000.0, 700.0)
+        );
+    }
+
+    #[test]
+    fn test_window_state_covers_screen() {
+        assert!(WindowState::Maximized.covers_screen());
+        assert!(WindowState::Fullscreen.covers_screen());
+        assert!(!WindowState::Normal.covers_screen());
+        assert!(!WindowState::Minimized.covers_screen());
+    }
+
+    #[test]
+    fn test_same_tab_in_two_projects_keeps_separate_geometry() {
+        let mut store = GeometryStore::default();
+        let key_a = store_key(Some("/projects/a"), "tab-1");
+        let key_b = store_key(Some("/projects/b"), "tab-1");
+        assert_ne!(key_a, key_b);
+
+        store.insert(&key_a, rect(0.0, 0.0, 800.0, 600.0), WindowState::Normal);
+        store.insert(
+            &key_b,
+            rect(900.0, 0.0, 1000.0, 700.0),
+            WindowState::Maximized,
+        );
+
+        assert_eq!(
+            store.windows.get(&key_a),
+            Some(&rect(0.0, 0.0, 800.0, 600.0))
+        );
+        assert_eq!(
+            store.windows.get(&key_b),
+            Some(&rect(900.0, 0.0, 1000.0, 700.0))
+        );
+        assert_eq!(store.states.get(&key_a), None);
+        assert_eq!(store.states.get(&key_b), Some(&WindowState::Maximized));
+        // Windows without a project keep the bare tab_id
+        assert_eq!(store_key(None, "tab-1"), "tab-1");
+    }
+
+    #[test]
+    fn test_legacy_tab_entries_migrate_to_project_key() {
+        let mut store: GeometryStore = serde_json::from_value(serde_json::json!({
+            "windows": { "tab-1": { "x": 1.0, "y": 2.0, "width": 800.0, "height": 600.0 } },
+            "states": { "tab-1": "maximized" }
+        }))
+        .unwrap();
+        let key = store_key(Some("/projects/a"), "tab-1");
+
+        assert!(store.migrate_legacy(&key, "tab-1"));
+        assert_eq!(store.windows.get(&key), Some(&rect(1.0, 2.0, 800.0, 600.0)));
+        assert_eq!(store.states.get(&key), Some(&WindowState::Maximized));
+        assert!(!store.windows.contains_key("tab-1"));
+
+        // Nothing left to migrate
+        assert!(!store.migrate_legacy(&key, "tab-1"));
+    }
+
+    #[test]
+    fn test_geometry_store_without_states_still_loads() {
+        let store: GeometryStore = serde_json::from_value(serde_json::json!({
+            "windows": { "tab-1": { "x": 1.0, "y": 2.0, "width": 800.0, "height": 600.0 } }
+        }))
+        .unwrap();
+        assert_eq!(store.windows.len(), 1);
+        assert!(store.states.is_empty());
+
+        let store: GeometryStore = serde_json::from_value(serde_json::json!({
+            "states": { "tab-1": "maximized" }
+        }))
+        .unwrap();
+        assert_eq!(store.states.get("tab-1"), Some(&WindowState::Maximized));
+    }
+}
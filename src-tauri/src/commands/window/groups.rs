@@ -0,0 +1,214 @@
+/**
+ * Window Groups
+ *
+ * Tags session windows with a group id so an action can cascade to just
+ * those windows: closing or focusing the group, or arranging it with
+ * `arrange_windows`. Groups live in the registry; a window belongs to at
+ * most one and leaves it when it closes.
+ */
+use tauri::{AppHandle, Manager};
+
+use super::error::{window_not_found, AppError};
+use super::focus;
+use super::lifecycle::{self, CloseAllResult};
+use super::registry::{with_registry, WindowRegistry};
+
+/// Checks a group id and trims it
+fn normalize_group_id(group_id: &str) -> Result<String, String> {
+    let group_id = group_id.trim();
+    if group_id.is_empty() {
+        return Err("Invalid group_id: must not be empty".to_string());
+    }
+    Ok(group_id.to_string())
+}
+
+/// Puts a window in a group, or takes it out with None; false if it isn't registered
+fn assign_group(registry: &WindowRegistry, window_label: &str, group_id: Option<String>) -> bool {
+    registry.update(window_label, |meta| meta.group_id = group_id)
+}
+
+/// Labels of a group's members, sorted; empty without a registry
+pub(super) fn group_members(app: &AppHandle, group_id: &str) -> Vec<String> {
+    with_registry(app, |registry| registry.labels_in_group(group_id)).unwrap_or_default()
+}
+
+/// Puts a session window in a group or takes it out of its group
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the session window
+/// * `group_id` - The group to join, or None to leave the current one
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_group(
+    app: AppHandle,
+    window_label: String,
+    group_id: Option<String>,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_group", {
+        let group_id = group_id
+            .as_deref()
+            .map(normalize_group_id)
+            .transpose()
+            .map_err(AppError::InvalidParam)?;
+        let assigned = app.get_webview_window(&window_label).is_some()
+            && with_registry(&app, |registry| {
+                assign_group(registry, &window_label, group_id.clone())
+            })
+            .unwrap_or(false);
+        if !assigned {
+            return Err(window_not_found(&window_label));
+        }
+
+        match group_id {
+            Some(group_id) => window_log!(info, window_label, "Joined group {}", group_id),
+            None => window_log!(info, window_label, "Left its group"),
+        }
+        Ok(())
+    })
+}
+
+/// Closes every window of a group
+///
+/// Busy members are left open and get `close-blocked`, like with
+/// `close_all_session_windows`.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `group_id` - The group to close
+///
+/// # Returns
+/// * `Result<CloseAllResult, AppError>` - Closed, busy and failed windows
+#[tauri::command]
+pub async fn close_group(app: AppHandle, group_id: String) -> Result<CloseAllResult, AppError> {
+    reported!(app, "close_group", {
+        let group_id = normalize_group_id(&group_id).map_err(AppError::InvalidParam)?;
+        let result = lifecycle::close_windows(
+            group_members(&app, &group_id),
+            |label| lifecycle::is_busy(&app, label),
+            |label| match app.get_webview_window(label) {
+                Some(window) => lifecycle::close_programmatically(&window),
+                None => Err(window_not_found(label).to_string()),
+            },
+        );
+
+        log::info!(
+            "[Window] Closed {} window(s) of group {}, {} busy, {} failed",
+            result.closed.len(),
+            group_id,
+            result.skipped_busy.len(),
+            result.failed.len()
+        );
+        Ok(result)
+    })
+}
+
+/// Brings every window of a group to the front
+///
+/// Minimized members are restored; the last one by label ends up with focus.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `group_id` - The group to focus
+///
+/// # Returns
+/// * `Result<usize, AppError>` - The number of windows brought to the front or an error message
+#[tauri::command]
+pub async fn focus_group(app: AppHandle, group_id: String) -> Result<usize, AppError> {
+    reported!(app, "focus_group", {
+        let group_id = normalize_group_id(&group_id).map_err(AppError::InvalidParam)?;
+        let mut focused = 0;
+        for label in group_members(&app, &group_id) {
+            let Some(window) = app.get_webview_window(&label) else {
+                continue;
+            };
+            focus::bring_to_front(&window)?;
+            focused += 1;
+        }
+
+        log::info!(
+            "[Window] Focused {} window(s) of group {}",
+            focused,
+            group_id
+        );
+        Ok(focused)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::registry::WindowMeta;
+    use super::*;
+
+    fn registry_with(labels: &[&str]) -> WindowRegistry {
+        let registry = WindowRegistry::default();
+        for label in labels {
+            let tab_id = label.trim_start_matches("session-window-");
+            registry.register(WindowMeta::new(label, tab_id, None, None, None));
+        }
+        registry
+    }
+
+    #[test]
+    fn test_group_actions_only_touch_members() {
+        let registry = registry_with(&[
+            "session-window-a",
+            "session-window-b",
+            "session-window-c",
+            "session-window-d",
+        ]);
+        assert!(assign_group(
+            &registry,
+            "session-window-c",
+            Some("review".to_string())
+        ));
+        assert!(assign_group(
+            &registry,
+            "session-window-a",
+            Some("review".to_string())
+        ));
+        assert!(assign_group(
+            &registry,
+            "session-window-b",
+            Some("other".to_string())
+        ));
+        assert!(!assign_group(
+            &registry,
+            "session-window-gone",
+            Some("review".to_string())
+        ));
+
+        let members = registry.labels_in_group("review");
+        assert_eq!(members, vec!["session-window-a", "session-window-c"]);
+
+        let mut touched = Vec::new();
+        let result = lifecycle::close_windows(
+            members,
+            |label| label == "session-window-c",
+            |label| {
+                touched.push(label.to_string());
+                Ok(())
+            },
+        );
+        assert_eq!(touched, vec!["session-window-a", "session-window-c"]);
+        assert_eq!(result.closed, vec!["session-window-a"]);
+        assert_eq!(result.skipped_busy, vec!["session-window-c"]);
+        assert!(registry.labels_in_group("missing").is_empty());
+    }
+
+    #[test]
+    fn test_ungrouped_window_leaves_the_group() {
+        let registry = registry_with(&["session-window-a", "session-window-b"]);
+        assign_group(&registry, "session-window-a", Some("review".to_string()));
+        assign_group(&registry, "session-window-b", Some("review".to_string()));
+
+        assert!(assign_group(&registry, "session-window-a", None));
+        assert_eq!(registry.labels_in_group("review"), vec!["session-window-b"]);
+        assert_eq!(registry.get("session-window-a").unwrap().group_id, None);
+
+        assert_eq!(normalize_group_id("  review "), Ok("review".to_string()));
+        assert!(normalize_group_id("   ").is_err());
+    }
+}
@@ -0,0 +1,289 @@
+/**
+ * Focus Follows Mouse
+ *
+ * Optional mode for side-by-side session windows: the window under the
+ * pointer gets focus once the pointer has rested on it for a moment, so
+ * passing over a window on the way to another doesn't steal focus.
+ *
+ * The pointer is polled, since Tauri has no hover events for the native
+ * window. Nothing is focused while another app is in front: the mode only
+ * switches between this app's windows.
+ */
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use super::error::AppError;
+use super::focus;
+use super::geometry::WindowGeometry;
+use super::is_session_window_label;
+
+/// How often the pointer position is read
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long the pointer has to rest on a window before it is focused
+const HOVER_DWELL: Duration = Duration::from_millis(300);
+
+/// Bumped on every enable and disable; a poll loop runs while it holds the
+/// current generation
+static POLL_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+impl WindowGeometry {
+    /// Whether a point lies inside the rectangle
+    fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
+
+/// Decides when the hovered window has been hovered long enough to focus it
+#[derive(Debug, Default)]
+struct DwellTracker {
+    hovered: Option<String>,
+    since: Option<Instant>,
+    /// Whether the current hover has been dealt with
+    settled: bool,
+}
+
+impl DwellTracker {
+    /// Feeds the window under the pointer at `now`
+    ///
+    /// Returns the window to focus once the pointer has stayed on it for
+    /// `dwell`, once per hover. Moving to another window starts over; the
+    /// window that already has focus is never returned.
+    fn observe(
+        &mut self,
+        hovered: Option<&str>,
+        focused: Option<&str>,
+        now: Instant,
+        dwell: Duration,
+    ) -> Option<String> {
+        if self.hovered.as_deref() != hovered {
+            self.hovered = hovered.map(str::to_string);
+            self.since = Some(now);
+            self.settled = false;
+            return None;
+        }
+        let hovered = hovered?;
+        if self.settled {
+            return None;
+        }
+        if Some(hovered) == focused {
+            self.settled = true;
+            return None;
+        }
+        let since = self.since?;
+        if now.duration_since(since) < dwell {
+            return None;
+        }
+        self.settled = true;
+        Some(hovered.to_string())
+    }
+}
+
+/// The window under a point
+///
+/// Where windows overlap the stacking order isn't known; the focused window
+/// is usually on top, so it wins, otherwise the first label does.
+fn window_under(
+    x: f64,
+    y: f64,
+    windows: &[(String, WindowGeometry)],
+    focused: Option<&str>,
+) -> Option<String> {
+    let mut under: Vec<&str> = windows
+        .iter()
+        .filter(|(_, bounds)| bounds.contains(x, y))
+        .map(|(label, _)| label.as_str())
+        .collect();
+    if let Some(focused) = focused.filter(|focused| under.contains(focused)) {
+        return Some(focused.to_string());
+    }
+    under.sort_unstable();
+    under.first().map(|label| label.to_string())
+}
+
+/// Outer bounds in physical pixels of the windows focus can move to
+fn hover_targets(app: &AppHandle, include_main: bool) -> Vec<(String, WindowGeometry)> {
+    app.webview_windows()
+        .into_iter()
+        .filter(|(label, _)| is_session_window_label(label) || (include_main && label == "main"))
+        .filter(|(_, window)| {
+            window.is_visible().unwrap_or(false) && !window.is_minimized().unwrap_or(true)
+        })
+        .filter_map(|(label, window)| {
+            let position = window.outer_position().ok()?;
+            let size = window.outer_size().ok()?;
+            Some((
+                label,
+                WindowGeometry {
+                    x: position.x as f64,
+                    y: position.y as f64,
+                    width: size.width as f64,
+                    height: size.height as f64,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// The app window that has focus, if any
+fn focused_app_window(app: &AppHandle) -> Option<String> {
+    app.webview_windows()
+        .into_iter()
+        .find(|(_, window)| window.is_focused().unwrap_or(false))
+        .map(|(label, _)| label)
+}
+
+/// Polls the pointer until `generation` is outdated
+fn spawn_poll(app: AppHandle, generation: u64, include_main: bool) {
+    tauri::async_runtime::spawn(async move {
+        let mut tracker = DwellTracker::default();
+        while POLL_GENERATION.load(Ordering::SeqCst) == generation {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            // Another app is in front; leave it alone
+            let Some(focused) = focused_app_window(&app) else {
+                tracker = DwellTracker::default();
+                continue;
+            };
+            let Ok(cursor) = app.cursor_position() else {
+                continue;
+            };
+            let targets = hover_targets(&app, include_main);
+            let hovered = window_under(cursor.x, cursor.y, &targets, Some(&focused));
+            let Some(label) = tracker.observe(
+                hovered.as_deref(),
+                Some(&focused),
+                Instant::now(),
+                HOVER_DWELL,
+            ) else {
+                continue;
+            };
+            if let Some(window) = app.get_webview_window(&label) {
+                if let Err(e) = focus::bring_to_front(&window) {
+                    window_log!(debug, label, "Focus follows mouse failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// Turns focus follows mouse on or off
+///
+/// While on, the session window under the pointer gets focus after the
+/// pointer has rested on it briefly (300 ms). Off by default.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `enabled` - Whether focus follows the mouse
+/// * `include_main` - Whether the main window takes part too (default: false)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_focus_follows_mouse(
+    app: AppHandle,
+    enabled: bool,
+    include_main: Option<bool>,
+) -> Result<(), AppError> {
+    reported!(app, "set_focus_follows_mouse", {
+        // Stops a running poll, also when it is about to be replaced
+        let generation = POLL_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+        if enabled {
+            spawn_poll(app.clone(), generation, include_main.unwrap_or(false));
+        }
+        log::info!(
+            "[Window] Focus follows mouse {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dwell_focuses_only_after_the_pointer_rests() {
+        let dwell = Duration::from_millis(300);
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut tracker = DwellTracker::default();
+        let focused = Some("session-window-a");
+
+        assert_eq!(
+            tracker.observe(Some("session-window-b"), focused, at(0), dwell),
+            None
+        );
+        assert_eq!(
+            tracker.observe(Some("session-window-b"), focused, at(250), dwell),
+            None
+        );
+        // Passing over another window restarts the dwell
+        assert_eq!(
+            tracker.observe(Some("session-window-c"), focused, at(300), dwell),
+            None
+        );
+        assert_eq!(
+            tracker.observe(Some("session-window-b"), focused, at(350), dwell),
+            None
+        );
+        assert_eq!(
+            tracker.observe(Some("session-window-b"), focused, at(600), dwell),
+            None
+        );
+        assert_eq!(
+            tracker.observe(Some("session-window-b"), focused, at(650), dwell),
+            Some("session-window-b".to_string())
+        );
+        // Once per hover
+        assert_eq!(
+            tracker.observe(
+                Some("session-window-b"),
+                Some("session-window-b"),
+                at(700),
+                dwell
+            ),
+            None
+        );
+
+        // The focused window and empty space never trigger
+        let mut tracker = DwellTracker::default();
+        tracker.observe(Some("session-window-a"), focused, at(0), dwell);
+        assert_eq!(
+            tracker.observe(Some("session-window-a"), focused, at(900), dwell),
+            None
+        );
+        tracker.observe(None, focused, at(1000), dwell);
+        assert_eq!(tracker.observe(None, focused, at(2000), dwell), None);
+    }
+
+    #[test]
+    fn test_window_under_prefers_focused_where_windows_overlap() {
+        let rect = |x: f64| WindowGeometry {
+            x,
+            y: 0.0,
+            width: 1000.0,
+            height: 800.0,
+        };
+        let windows = vec![
+            ("session-window-b".to_string(), rect(800.0)),
+            ("session-window-a".to_string(), rect(0.0)),
+        ];
+
+        assert_eq!(
+            window_under(100.0, 100.0, &windows, None).as_deref(),
+            Some("session-window-a")
+        );
+        assert_eq!(
+            window_under(900.0, 100.0, &windows, Some("session-window-b")).as_deref(),
+            Some("session-window-b")
+        );
+        assert_eq!(
+            window_under(900.0, 100.0, &windows, None).as_deref(),
+            Some("session-window-a")
+        );
+        assert_eq!(window_under(2000.0, 100.0, &windows, None), None);
+    }
+}
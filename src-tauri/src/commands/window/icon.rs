@@ -0,0 +1,122 @@
+/**
+ * Per-Engine Window Icons
+ *
+ * Gives session windows an icon badged with their engine's color, so Claude,
+ * Codex and Gemini windows can be told apart in the taskbar. The icons ship
+ * as bundled resources (`icons/engines/<engine>.png`); if one can't be
+ * loaded the default app icon is used instead.
+ *
+ * macOS has one dock icon per app, not per window, so there the command only
+ * records the choice.
+ */
+use tauri::image::Image;
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+use super::{restore, tab_id_from_label, Engine};
+
+/// Resource path of an engine's icon
+fn engine_icon_resource(engine: Engine) -> String {
+    format!("icons/engines/{}.png", engine)
+}
+
+/// Loads the bundled icon of an engine, falling back to the default app icon
+fn load_engine_icon(app: &AppHandle, engine: Engine) -> Option<Image<'static>> {
+    let loaded = app
+        .path()
+        .resolve(engine_icon_resource(engine), BaseDirectory::Resource)
+        .map_err(|e| e.to_string())
+        .and_then(|path| Image::from_path(path).map_err(|e| e.to_string()));
+
+    match loaded {
+        Ok(icon) => Some(icon),
+        Err(e) => {
+            log::warn!(
+                "[Window] Failed to load {} icon, using the app icon: {}",
+                engine,
+                e
+            );
+            app.default_window_icon().cloned().map(Image::to_owned)
+        }
+    }
+}
+
+/// Sets a window's taskbar icon to the one of an engine
+///
+/// Shared by `set_window_icon` and the startup restore.
+pub(super) fn apply_engine_icon(
+    app: &AppHandle,
+    window: &WebviewWindow,
+    engine: Engine,
+) -> Result<(), String> {
+    if cfg!(target_os = "macos") {
+        window_log!(
+            warn,
+            window.label(),
+            "Per-window icons are not supported on macOS, keeping the dock icon"
+        );
+        return Ok(());
+    }
+
+    let icon = load_engine_icon(app, engine).ok_or_else(|| "No icon available".to_string())?;
+    window
+        .set_icon(icon)
+        .map_err(|e| format!("Failed to set window icon: {}", e))
+}
+
+/// Sets a window's taskbar/dock icon to the one of an engine
+///
+/// The choice is kept in the window metadata and the restore snapshot, so a
+/// restored window gets the same icon.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `engine` - 'claude' | 'codex' | 'gemini'
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_icon(
+    app: AppHandle,
+    window_label: String,
+    engine: String,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_icon", {
+        let engine: Engine = engine
+            .parse()
+            .map_err(|e| AppError::InvalidParam(format!("Invalid engine: {}", e)))?;
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        apply_engine_icon(&app, &window, engine)?;
+
+        with_registry(&app, |registry| {
+            registry.update(&window_label, |meta| meta.icon = Some(engine))
+        });
+        if let Some(tab_id) = tab_id_from_label(&window_label) {
+            restore::set_open_window_icon(&app, tab_id, engine);
+        }
+
+        window_log!(info, window_label, "Icon set to {}", engine);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_icons_are_bundled() {
+        for engine in [Engine::Claude, Engine::Codex, Engine::Gemini] {
+            let resource = engine_icon_resource(engine);
+            let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(&resource);
+            assert!(path.is_file(), "{} is missing", resource);
+            assert!(Image::from_path(&path).is_ok(), "{} is not a PNG", resource);
+        }
+    }
+}
@@ -0,0 +1,865 @@
+/**
+ * Cross-Window Communication
+ *
+ * Commands for sending events to a single session window, to all of them or
+ * to the main window, and for handing text from one window's selection to
+ * another's input box.
+ *
+ * Broadcast payloads are capped in size (see `set_max_broadcast_payload_size`)
+ * since every receiving window gets its own copy. Larger syncs go through
+ * `broadcast_chunked`, which the windows reassemble.
+ */
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::{AppHandle, Emitter, EventTarget, Manager};
+
+use super::error::{window_not_found, AppError};
+use super::limits;
+use super::registry::with_registry;
+use super::{is_session_window_label, Engine};
+
+/// Label the main window is created with
+const MAIN_WINDOW_LABEL: &str = "main";
+
+/// Picks the main window among the open windows.
+///
+/// That is `main` when it exists, otherwise the first non-session window in
+/// label order, so the choice doesn't depend on map iteration.
+fn main_window_label<I>(labels: I) -> Option<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut others: Vec<String> = labels
+        .into_iter()
+        .filter(|label| !is_session_window_label(label))
+        .collect();
+    if others.iter().any(|label| label == MAIN_WINDOW_LABEL) {
+        return Some(MAIN_WINDOW_LABEL.to_string());
+    }
+    others.sort();
+    others.into_iter().next()
+}
+
+/// Emits an event to the main window only
+///
+/// Session windows never see the event. Fails with `NotFound` when no main
+/// window is open.
+pub(super) fn emit_to_main_window<S: Serialize + Clone>(
+    app: &AppHandle,
+    event_name: &str,
+    payload: S,
+) -> Result<(), AppError> {
+    let label = main_window_label(app.webview_windows().into_keys())
+        .ok_or_else(|| AppError::NotFound("Main window not found".to_string()))?;
+    app.emit_to(EventTarget::webview_window(&label), event_name, payload)
+        .map_err(|e| AppError::Emit(format!("Failed to emit event: {}", e)))
+}
+
+/// Tells a failed emit to a window that just closed from a real failure
+///
+/// An emit can race with the window being destroyed; if the window is gone
+/// by the time the error comes back, that's `WindowGone`, which callers can
+/// ignore, rather than `Emit`.
+fn classify_emit_error(window_label: &str, error: &str, still_open: bool) -> AppError {
+    if still_open {
+        AppError::Emit(format!("Failed to emit event: {}", error))
+    } else {
+        AppError::WindowGone(format!(
+            "Window closed before the event was delivered: {}",
+            window_label
+        ))
+    }
+}
+
+fn emit_error(app: &AppHandle, window_label: &str, error: tauri::Error) -> AppError {
+    classify_emit_error(
+        window_label,
+        &error.to_string(),
+        app.get_webview_window(window_label).is_some(),
+    )
+}
+
+/// Emits an event to a specific window
+///
+/// Deprecated: the payload is forwarded as a string, so receivers get a quoted
+/// JSON string they have to parse again. Use `emit_json_to_window` instead.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The target window label
+/// * `event_name` - The event name
+/// * `payload` - The event payload (JSON string)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn emit_to_window(
+    app: AppHandle,
+    window_label: String,
+    event_name: String,
+    payload: String,
+) -> Result<(), AppError> {
+    reported!(app, "emit_to_window", {
+        if let Some(window) = app.get_webview_window(&window_label) {
+            window
+                .emit(&event_name, payload)
+                .map_err(|e| emit_error(&app, &window_label, e))?;
+            Ok(())
+        } else {
+            Err(window_not_found(&window_label))
+        }
+    })
+}
+
+/// Target and payload of an `emit_json_to_window` event
+///
+/// Only JSON objects are accepted, so receivers can always read fields off
+/// the payload. The object is passed on as is for Tauri to serialize.
+fn json_window_event(
+    window_label: &str,
+    payload: serde_json::Value,
+) -> Result<(EventTarget, serde_json::Value), AppError> {
+    if !payload.is_object() {
+        return Err(AppError::InvalidParam(format!(
+            "Event payload must be a JSON object, got: {}",
+            payload
+        )));
+    }
+    Ok((EventTarget::webview_window(window_label), payload))
+}
+
+/// Emits an event with a JSON payload to a specific window
+///
+/// The payload is serialized by Tauri, so the receiver gets a real object
+/// instead of a string it has to parse again. Only that window gets it.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The target window label
+/// * `event_name` - The event name
+/// * `payload` - The event payload, a JSON object
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn emit_json_to_window(
+    app: AppHandle,
+    window_label: String,
+    event_name: String,
+    payload: serde_json::Value,
+) -> Result<(), AppError> {
+    reported!(app, "emit_json_to_window", {
+        let (target, payload) = json_window_event(&window_label, payload)?;
+        if let Some(window) = app.get_webview_window(&window_label) {
+            window
+                .emit_to(target, &event_name, payload)
+                .map_err(|e| emit_error(&app, &window_label, e))?;
+            Ok(())
+        } else {
+            Err(window_not_found(&window_label))
+        }
+    })
+}
+
+/// Outcome of a broadcast, per window
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct BroadcastResult {
+    /// Labels of the windows that received the event
+    pub delivered: Vec<String>,
+    /// Labels of the windows the event could not be delivered to, with the error
+    pub failed: Vec<(String, String)>,
+    /// Labels of the windows that closed while the event was on its way
+    pub skipped: Vec<String>,
+}
+
+/// Sorts per-window emit outcomes into a `BroadcastResult`
+fn collect_broadcast<I>(outcomes: I) -> BroadcastResult
+where
+    I: IntoIterator<Item = (String, Result<(), AppError>)>,
+{
+    let mut result = BroadcastResult::default();
+
+    for (label, outcome) in outcomes {
+        match outcome {
+            Ok(()) => result.delivered.push(label),
+            Err(AppError::WindowGone(_)) => result.skipped.push(label),
+            Err(e) => result.failed.push((label, e.to_string())),
+        }
+    }
+
+    // Window iteration order is arbitrary; keep the result stable for callers
+    result.delivered.sort();
+    result.failed.sort();
+    result.skipped.sort();
+    result
+}
+
+/// Picks the session windows a broadcast goes to, skipping the excluded label
+fn broadcast_targets<I>(labels: I, exclude_label: Option<&str>) -> Vec<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    labels
+        .into_iter()
+        .filter(|label| is_session_window_label(label))
+        .filter(|label| Some(label.as_str()) != exclude_label)
+        .collect()
+}
+
+/// Checks that a broadcast payload is within the size limit
+fn check_broadcast_payload(payload: &str, max_bytes: usize) -> Result<(), String> {
+    if payload.len() > max_bytes {
+        Err(format!(
+            "Payload too large: {} bytes (at most {} bytes); use broadcast_chunked",
+            payload.len(),
+            max_bytes
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Emits an event to every session window, recording the outcome per window.
+///
+/// Each window is targeted individually (`emit` would reach every window on
+/// every iteration), so listeners registered on a window only hear the event
+/// once and an excluded window doesn't hear it at all.
+fn broadcast(
+    app: &AppHandle,
+    event_name: &str,
+    payload: &str,
+    exclude_label: Option<&str>,
+) -> BroadcastResult {
+    let targets = broadcast_targets(app.webview_windows().into_keys(), exclude_label);
+    emit_to_labels(app, targets, event_name, payload)
+}
+
+/// Emits an event to each of the given windows, recording the outcome per window
+fn emit_to_labels<S: Serialize + Clone>(
+    app: &AppHandle,
+    targets: Vec<String>,
+    event_name: &str,
+    payload: S,
+) -> BroadcastResult {
+    collect_broadcast(targets.into_iter().map(|label| {
+        let outcome = app
+            .emit_to(
+                EventTarget::webview_window(&label),
+                event_name,
+                payload.clone(),
+            )
+            .map_err(|e| emit_error(app, &label, e));
+        (label, outcome)
+    }))
+}
+
+/// Broadcasts an event to all session windows
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `event_name` - The event name
+/// * `payload` - The event payload (JSON string)
+/// * `exclude_label` - Optional window to skip (usually the sender)
+///
+/// # Returns
+/// * `Result<u32, AppError>` - Number of windows that received the event
+#[tauri::command]
+pub async fn broadcast_to_session_windows(
+    app: AppHandle,
+    event_name: String,
+    payload: String,
+    exclude_label: Option<String>,
+) -> Result<u32, AppError> {
+    reported!(app, "broadcast_to_session_windows", {
+        check_broadcast_payload(&payload, limits::max_broadcast_payload_bytes(&app))
+            .map_err(AppError::InvalidParam)?;
+        let result = broadcast(&app, &event_name, &payload, exclude_label.as_deref());
+        Ok(result.delivered.len() as u32)
+    })
+}
+
+/// Broadcasts an event to all session windows and reports which ones failed
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `event_name` - The event name
+/// * `payload` - The event payload (JSON string)
+/// * `exclude_label` - Optional window to skip (usually the sender)
+///
+/// # Returns
+/// * `Result<BroadcastResult, AppError>` - Delivered and failed window labels
+#[tauri::command]
+pub async fn broadcast_to_session_windows_detailed(
+    app: AppHandle,
+    event_name: String,
+    payload: String,
+    exclude_label: Option<String>,
+) -> Result<BroadcastResult, AppError> {
+    reported!(app, "broadcast_to_session_windows_detailed", {
+        check_broadcast_payload(&payload, limits::max_broadcast_payload_bytes(&app))
+            .map_err(AppError::InvalidParam)?;
+        let result = broadcast(&app, &event_name, &payload, exclude_label.as_deref());
+
+        for (label, error) in &result.failed {
+            window_log!(warn, label, "Broadcast failed: {}", error);
+        }
+
+        Ok(result)
+    })
+}
+
+/// Event carrying one piece of a chunked broadcast
+const BROADCAST_CHUNK_EVENT: &str = "broadcast-chunk";
+
+/// Chunk size of `broadcast_chunked` unless asked otherwise, in bytes
+const DEFAULT_BROADCAST_CHUNK_BYTES: usize = 256 * 1024;
+
+/// Payload of the `broadcast-chunk` event
+///
+/// A window collects the chunks of a `transfer_id` and, once it has all
+/// `total` of them, joins their `data` in `seq` order and handles the result
+/// as `event_name`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct BroadcastChunk {
+    transfer_id: String,
+    event_name: String,
+    /// Position of the chunk, from 0
+    seq: usize,
+    total: usize,
+    data: String,
+}
+
+/// Splits a payload into pieces of at most `chunk_bytes`, never inside a character
+///
+/// A chunk only grows past `chunk_bytes` if a single character is larger. An
+/// empty payload is one empty chunk, so the receivers still get the event.
+fn split_payload(payload: &str, chunk_bytes: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < payload.len() {
+        let mut end = (start + chunk_bytes).min(payload.len());
+        while !payload.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == start {
+            end = start + 1;
+            while !payload.is_char_boundary(end) {
+                end += 1;
+            }
+        }
+        chunks.push(&payload[start..end]);
+        start = end;
+    }
+    if chunks.is_empty() {
+        chunks.push("");
+    }
+    chunks
+}
+
+/// The `broadcast-chunk` events a chunked broadcast sends, in order
+fn broadcast_chunks(
+    transfer_id: &str,
+    event_name: &str,
+    payload: &str,
+    chunk_bytes: usize,
+) -> Vec<BroadcastChunk> {
+    let pieces = split_payload(payload, chunk_bytes);
+    let total = pieces.len();
+    pieces
+        .into_iter()
+        .enumerate()
+        .map(|(seq, data)| BroadcastChunk {
+            transfer_id: transfer_id.to_string(),
+            event_name: event_name.to_string(),
+            seq,
+            total,
+            data: data.to_string(),
+        })
+        .collect()
+}
+
+/// Broadcasts a large payload to all session windows in chunks
+///
+/// The payload is sent as sequence-numbered `broadcast-chunk` events that the
+/// windows reassemble into `event_name`. A window that misses a chunk gets no
+/// further ones and is reported as failed.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `event_name` - The event name the windows reassemble the payload into
+/// * `payload` - The event payload (JSON string)
+/// * `exclude_label` - Optional window to skip (usually the sender)
+/// * `chunk_size` - Largest chunk in bytes (default 256 KB, at most the payload size limit)
+///
+/// # Returns
+/// * `Result<BroadcastResult, AppError>` - Delivered and failed window labels
+#[tauri::command]
+pub async fn broadcast_chunked(
+    app: AppHandle,
+    event_name: String,
+    payload: String,
+    exclude_label: Option<String>,
+    chunk_size: Option<usize>,
+) -> Result<BroadcastResult, AppError> {
+    reported!(app, "broadcast_chunked", {
+        let max_bytes = limits::max_broadcast_payload_bytes(&app);
+        let chunk_bytes = chunk_size.unwrap_or(DEFAULT_BROADCAST_CHUNK_BYTES.min(max_bytes));
+        if chunk_bytes == 0 || chunk_bytes > max_bytes {
+            return Err(AppError::InvalidParam(format!(
+                "Invalid chunk_size: must be between 1 and {} bytes, got {}",
+                max_bytes, chunk_bytes
+            )));
+        }
+
+        let transfer_id = uuid::Uuid::new_v4().to_string();
+        let chunks = broadcast_chunks(&transfer_id, &event_name, &payload, chunk_bytes);
+        let mut targets =
+            broadcast_targets(app.webview_windows().into_keys(), exclude_label.as_deref());
+        let mut failed = Vec::new();
+        let mut skipped = Vec::new();
+        for chunk in &chunks {
+            let result = emit_to_labels(&app, targets, BROADCAST_CHUNK_EVENT, chunk);
+            targets = result.delivered;
+            failed.extend(result.failed);
+            skipped.extend(result.skipped);
+        }
+        failed.sort();
+        skipped.sort();
+        let result = BroadcastResult {
+            delivered: targets,
+            failed,
+            skipped,
+        };
+        for (label, error) in &result.failed {
+            window_log!(warn, label, "Chunked broadcast failed: {}", error);
+        }
+        log::debug!(
+            "[Window] Broadcast {} ({} bytes) in {} chunk(s) to {} window(s)",
+            event_name,
+            payload.len(),
+            chunks.len(),
+            result.delivered.len()
+        );
+        Ok(result)
+    })
+}
+
+/// Splits requested labels into open windows and unknown ones, dropping duplicates
+fn batch_targets(
+    labels: Vec<String>,
+    is_open: impl Fn(&str) -> bool,
+) -> (Vec<String>, Vec<(String, String)>) {
+    let mut seen = HashSet::new();
+    let (open, unknown): (Vec<String>, Vec<String>) = labels
+        .into_iter()
+        .filter(|label| seen.insert(label.clone()))
+        .partition(|label| is_open(label));
+
+    let unknown = unknown
+        .into_iter()
+        .map(|label| {
+            let error = window_not_found(&label).to_string();
+            (label, error)
+        })
+        .collect();
+    (open, unknown)
+}
+
+/// Emits an event with a JSON payload to several specific windows
+///
+/// Duplicate labels only get the event once; unknown labels are reported as
+/// failed instead of aborting the batch.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `labels` - The target window labels
+/// * `event_name` - The event name
+/// * `payload` - The event payload
+///
+/// # Returns
+/// * `Result<BroadcastResult, AppError>` - Delivered and failed window labels
+#[tauri::command]
+pub async fn emit_to_windows(
+    app: AppHandle,
+    labels: Vec<String>,
+    event_name: String,
+    payload: serde_json::Value,
+) -> Result<BroadcastResult, AppError> {
+    reported!(app, "emit_to_windows", {
+        let open = app.webview_windows();
+        let (targets, unknown) = batch_targets(labels, |label| open.contains_key(label));
+
+        let mut result = emit_to_labels(&app, targets, &event_name, payload);
+        result.failed.extend(unknown);
+        result.failed.sort();
+
+        for (label, error) in &result.failed {
+            window_log!(warn, label, "Emit failed: {}", error);
+        }
+
+        Ok(result)
+    })
+}
+
+/// Event carrying text handed to a window's input box
+const TEXT_RECEIVED_EVENT: &str = "text-received";
+
+/// Largest text `send_text_to_window` hands over, in bytes
+const MAX_HANDOFF_TEXT_LEN: usize = 1024 * 1024;
+
+/// How handed-over text goes into the target's input box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InsertMode {
+    /// Replace whatever is in the input box
+    Replace,
+    /// Add to the end of the input box
+    Append,
+}
+
+/// Payload of the `text-received` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct TextReceivedPayload {
+    text: String,
+    mode: InsertMode,
+}
+
+/// Checks that handed-over text is within the size limit
+fn validate_handoff_text(text: &str) -> Result<(), String> {
+    if text.len() > MAX_HANDOFF_TEXT_LEN {
+        Err(format!(
+            "Text too long: {} bytes (at most {} bytes)",
+            text.len(),
+            MAX_HANDOFF_TEXT_LEN
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Hands text to another window's input box
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `target_label` - The window that receives the text
+/// * `text` - The text (at most 1 MB)
+/// * `mode` - Whether the text replaces the input or is appended to it
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn send_text_to_window(
+    app: AppHandle,
+    target_label: String,
+    text: String,
+    mode: InsertMode,
+) -> Result<(), AppError> {
+    reported!(app, "send_text_to_window", {
+        validate_handoff_text(&text).map_err(AppError::InvalidParam)?;
+        let window = app
+            .get_webview_window(&target_label)
+            .ok_or_else(|| window_not_found(&target_label))?;
+
+        window
+            .emit_to(
+                EventTarget::webview_window(&target_label),
+                TEXT_RECEIVED_EVENT,
+                TextReceivedPayload { text, mode },
+            )
+            .map_err(|e| emit_error(&app, &target_label, e))
+    })
+}
+
+/// Broadcasts an event to the session windows running one engine
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `engine` - Only windows of this engine receive the event
+/// * `event_name` - The event name
+/// * `payload` - The event payload (JSON string)
+///
+/// # Returns
+/// * `Result<u32, AppError>` - Number of windows that received the event
+#[tauri::command]
+pub async fn broadcast_to_engine_windows(
+    app: AppHandle,
+    engine: Engine,
+    event_name: String,
+    payload: String,
+) -> Result<u32, AppError> {
+    reported!(app, "broadcast_to_engine_windows", {
+        check_broadcast_payload(&payload, limits::max_broadcast_payload_bytes(&app))
+            .map_err(AppError::InvalidParam)?;
+        let open = app.webview_windows();
+        let targets: Vec<String> =
+            with_registry(&app, |registry| registry.labels_for_engine(engine))
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|label| open.contains_key(label))
+                .collect();
+
+        let result = emit_to_labels(&app, targets, &event_name, &payload);
+        for (label, error) in &result.failed {
+            window_log!(warn, label, "Broadcast failed: {}", error);
+        }
+
+        Ok(result.delivered.len() as u32)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn open_labels() -> Vec<String> {
+        vec![
+            "main".to_string(),
+            "session-window-a".to_string(),
+            "session-window-b".to_string(),
+            "session-window-c".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_main_window_receives_main_events_not_session_windows() {
+        assert_eq!(main_window_label(open_labels()).as_deref(), Some("main"));
+
+        // Without `main`, another non-session window stands in, never a session window
+        let labels = vec![
+            "session-window-a".to_string(),
+            "settings".to_string(),
+            "launcher".to_string(),
+        ];
+        assert_eq!(main_window_label(labels).as_deref(), Some("launcher"));
+        assert_eq!(
+            main_window_label(vec!["session-window-a".to_string()]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_broadcast_targets_only_session_windows() {
+        let targets = broadcast_targets(open_labels(), None);
+        assert_eq!(targets.len(), 3);
+        assert!(!targets.contains(&"main".to_string()));
+    }
+
+    #[test]
+    fn test_broadcast_targets_excludes_sender() {
+        let all = broadcast_targets(open_labels(), None);
+        let excluded = broadcast_targets(open_labels(), Some("session-window-b"));
+
+        assert_eq!(excluded.len(), all.len() - 1);
+        assert!(!excluded.contains(&"session-window-b".to_string()));
+    }
+
+    #[test]
+    fn test_broadcast_targets_unknown_exclude_is_noop() {
+        let excluded = broadcast_targets(open_labels(), Some("session-window-gone"));
+        assert_eq!(excluded.len(), 3);
+    }
+
+    #[test]
+    fn test_collect_broadcast_splits_delivered_and_failed() {
+        let result = collect_broadcast(vec![
+            ("session-window-c".to_string(), Ok(())),
+            (
+                "session-window-b".to_string(),
+                Err(AppError::Emit(
+                    "Failed to emit event: window closed".to_string(),
+                )),
+            ),
+            ("session-window-a".to_string(), Ok(())),
+        ]);
+
+        assert_eq!(
+            result.delivered,
+            vec![
+                "session-window-a".to_string(),
+                "session-window-c".to_string()
+            ]
+        );
+        assert_eq!(
+            result.failed,
+            vec![(
+                "session-window-b".to_string(),
+                "Failed to emit event: window closed".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_broadcast_result_serializes_failures_as_pairs() {
+        let result = BroadcastResult {
+            delivered: vec!["session-window-a".to_string()],
+            failed: vec![("session-window-b".to_string(), "boom".to_string())],
+            skipped: vec!["session-window-c".to_string()],
+        };
+
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            json!({
+                "delivered": ["session-window-a"],
+                "failed": [["session-window-b", "boom"]],
+                "skipped": ["session-window-c"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_emit_to_closed_window_is_not_a_failure() {
+        // The webview is torn down mid-emit: the error comes back and the window is gone
+        let gone = classify_emit_error("session-window-b", "webview destroyed", false);
+        assert!(matches!(gone, AppError::WindowGone(_)));
+
+        let result = collect_broadcast(vec![
+            ("session-window-a".to_string(), Ok(())),
+            ("session-window-b".to_string(), Err(gone)),
+        ]);
+        assert_eq!(result.delivered, vec!["session-window-a".to_string()]);
+        assert!(result.failed.is_empty());
+        assert_eq!(result.skipped, vec!["session-window-b".to_string()]);
+
+        // A window that is still open failed for real
+        assert_eq!(
+            classify_emit_error("session-window-b", "serialization failed", true),
+            AppError::Emit("Failed to emit event: serialization failed".to_string())
+        );
+    }
+
+    // Tauri serializes emit payloads with serde_json; these tests pin down what the
+    // receiving window gets for each command's payload type.
+
+    #[test]
+    fn test_json_payload_arrives_as_object() {
+        let payload = json!({
+            "tab_id": "tab-1",
+            "count": 3,
+            "ratio": 0.5,
+            "done": false,
+            "nested": { "items": [1, "two", null], "empty": {} }
+        });
+
+        let (target, emitted) = json_window_event("session-window-a", payload.clone()).unwrap();
+
+        // Sent to that window only, with the object itself rather than its JSON text
+        assert_eq!(target, EventTarget::webview_window("session-window-a"));
+        assert_eq!(emitted, payload);
+        assert!(emitted["count"].is_u64());
+        assert!(emitted["nested"]["empty"].is_object());
+    }
+
+    #[test]
+    fn test_json_payload_rejects_non_objects() {
+        // A string here would reach the window double-encoded
+        let stringified = json!({ "nested": { "count": 3 } }).to_string();
+
+        for payload in [json!(stringified), json!([1, 2]), json!(3), json!(null)] {
+            assert!(matches!(
+                json_window_event("session-window-a", payload),
+                Err(AppError::InvalidParam(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_broadcast_payload_size_limit() {
+        assert_eq!(check_broadcast_payload(&"a".repeat(1024), 1024), Ok(()));
+        assert_eq!(
+            check_broadcast_payload(&"a".repeat(1025), 1024),
+            Err(
+                "Payload too large: 1025 bytes (at most 1024 bytes); use broadcast_chunked"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_broadcast_chunks_reassemble_in_order() {
+        // Multi-byte characters straddle the chunk boundaries
+        let payload = format!("{{\"text\":\"{}\"}}", "aé€😀".repeat(50));
+        let chunks = broadcast_chunks("t-1", "sync", &payload, 16);
+
+        assert!(chunks.len() > 1);
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert_eq!(chunk.seq, i);
+            assert_eq!(chunk.total, chunks.len());
+            assert_eq!(chunk.transfer_id, "t-1");
+            assert_eq!(chunk.event_name, "sync");
+            assert!(chunk.data.len() <= 16);
+        }
+        let joined: String = chunks.iter().map(|chunk| chunk.data.as_str()).collect();
+        assert_eq!(joined, payload);
+
+        assert_eq!(
+            serde_json::to_value(&chunks[0]).unwrap(),
+            json!({
+                "transfer_id": "t-1",
+                "event_name": "sync",
+                "seq": 0,
+                "total": chunks.len(),
+                "data": chunks[0].data
+            })
+        );
+
+        // An empty payload still arrives, as a single empty chunk
+        let empty = broadcast_chunks("t-2", "sync", "", 16);
+        assert_eq!(
+            (empty.len(), empty[0].total, empty[0].data.as_str()),
+            (1, 1, "")
+        );
+        // A chunk smaller than a character still makes progress
+        assert_eq!(split_payload("😀😀", 1), vec!["😀", "😀"]);
+    }
+
+    #[test]
+    fn test_handoff_text_length_cap() {
+        assert_eq!(validate_handoff_text(""), Ok(()));
+        assert_eq!(
+            validate_handoff_text(&"a".repeat(MAX_HANDOFF_TEXT_LEN)),
+            Ok(())
+        );
+        assert_eq!(
+            validate_handoff_text(&"a".repeat(MAX_HANDOFF_TEXT_LEN + 1)),
+            Err("Text too long: 1048577 bytes (at most 1048576 bytes)".to_string())
+        );
+        // The cap counts bytes, not characters
+        assert!(validate_handoff_text(&"é".repeat(MAX_HANDOFF_TEXT_LEN / 2 + 1)).is_err());
+    }
+
+    #[test]
+    fn test_text_received_payload() {
+        let payload = TextReceivedPayload {
+            text: "fn main() {}".to_string(),
+            mode: InsertMode::Append,
+        };
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            json!({ "text": "fn main() {}", "mode": "append" })
+        );
+    }
+
+    #[test]
+    fn test_batch_targets_dedupes_and_reports_unknown() {
+        let open = open_labels();
+        let (targets, unknown) = batch_targets(
+            vec![
+                "session-window-a".to_string(),
+                "session-window-gone".to_string(),
+                "main".to_string(),
+                "session-window-a".to_string(),
+            ],
+            |label| open.iter().any(|open| open == label),
+        );
+
+        assert_eq!(targets, vec!["session-window-a", "main"]);
+        assert_eq!(
+            unknown,
+            vec![(
+                "session-window-gone".to_string(),
+                "Window not found: session-window-gone".to_string()
+            )]
+        );
+    }
+}
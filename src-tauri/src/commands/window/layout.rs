@@ -0,0 +1,221 @@
+/**
+ * Workspace Layouts
+ *
+ * Opens a saved, named set of session windows in one call. Each window goes
+ * through the regular `create_session_window` build path and is then moved
+ * to the position stored with the layout, using the same placement as
+ * snapping and arranging.
+ */
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::{AppHandle, Manager};
+
+use super::error::AppError;
+use super::geometry::{self, WindowGeometry};
+use super::snap::place_window;
+use super::{build_session_window, CreateSessionWindowParams, WindowCreationResult};
+
+/// One window of a workspace layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceWindowSpec {
+    /// Tab, engine, project etc. as for `create_session_window`
+    #[serde(flatten)]
+    pub params: CreateSessionWindowParams,
+    /// Where to put the window; without one it opens like a detached tab
+    #[serde(default)]
+    pub geometry: Option<WindowGeometry>,
+}
+
+/// A named set of session windows to open together
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceLayout {
+    pub name: String,
+    pub windows: Vec<WorkspaceWindowSpec>,
+}
+
+/// Checks that a layout opens at least one window and no tab twice
+fn validate_layout(layout: &WorkspaceLayout) -> Result<(), String> {
+    if layout.windows.is_empty() {
+        return Err(format!("Layout {:?} has no windows", layout.name));
+    }
+    let mut seen = HashSet::new();
+    for spec in &layout.windows {
+        if !seen.insert(spec.params.tab_id.as_str()) {
+            return Err(format!(
+                "Layout {:?} lists tab {} more than once",
+                layout.name, spec.params.tab_id
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Creation parameters of a spec; the window is built at its layout size
+fn spec_params(spec: &WorkspaceWindowSpec) -> CreateSessionWindowParams {
+    let mut params = spec.params.clone();
+    if let Some(geometry) = spec.geometry {
+        params.width = Some(geometry.width);
+        params.height = Some(geometry.height);
+    }
+    params
+}
+
+/// Result of a window that was moved to its layout position
+fn placed_result(result: WindowCreationResult, geometry: &WindowGeometry) -> WindowCreationResult {
+    WindowCreationResult {
+        x: geometry.x,
+        y: geometry.y,
+        width: geometry.width,
+        height: geometry.height,
+        ..result
+    }
+}
+
+/// Opens the windows of a layout, skipping the ones that fail
+///
+/// Returns the opened windows and a message per skipped one. Shared by
+/// `open_workspace_layout` and its timed variant.
+pub(super) async fn open_layout(
+    app: &AppHandle,
+    layout: &WorkspaceLayout,
+) -> Result<(Vec<WindowCreationResult>, Vec<String>), AppError> {
+    validate_layout(layout).map_err(AppError::InvalidParam)?;
+
+    let screens = geometry::screen_bounds(app);
+    let mut results = Vec::with_capacity(layout.windows.len());
+    let mut skipped = Vec::new();
+    for spec in &layout.windows {
+        let result = match build_session_window(app, &spec_params(spec)).await {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!(
+                    "[Window] Failed to open tab {} of layout {:?}: {}",
+                    spec.params.tab_id,
+                    layout.name,
+                    e
+                );
+                skipped.push(format!("Failed to open tab {}: {}", spec.params.tab_id, e));
+                continue;
+            }
+        };
+
+        let target = spec
+            .geometry
+            .filter(|target| screens.is_empty() || geometry::is_on_screen(target, &screens));
+        let placed = target.and_then(|target| {
+            let window = app.get_webview_window(&result.window_label)?;
+            match place_window(&window, &target) {
+                Ok(()) => Some(target),
+                Err(e) => {
+                    window_log!(warn, result.window_label, "Failed to apply layout: {}", e);
+                    None
+                }
+            }
+        });
+        results.push(match placed {
+            Some(target) => placed_result(result, &target),
+            None => result,
+        });
+    }
+
+    log::info!(
+        "[Window] Opened {} of {} window(s) of layout {:?}",
+        results.len(),
+        layout.windows.len(),
+        layout.name
+    );
+    Ok((results, skipped))
+}
+
+/// Opens all windows of a saved workspace layout
+///
+/// Windows whose saved position is no longer on a connected screen keep the
+/// position they were built at. A window that fails to open is skipped.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `layout` - The layout to open
+///
+/// # Returns
+/// * `Result<Vec<WindowCreationResult>, AppError>` - The opened windows or an error message
+#[tauri::command]
+pub async fn open_workspace_layout(
+    app: AppHandle,
+    layout: WorkspaceLayout,
+) -> Result<Vec<WindowCreationResult>, AppError> {
+    reported!(app, "open_workspace_layout", {
+        let (results, _skipped) = open_layout(&app, &layout).await?;
+        Ok(results)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_window_layout() -> WorkspaceLayout {
+        serde_json::from_value(serde_json::json!({
+            "name": "review",
+            "windows": [
+                {
+                    "tab_id": "left",
+                    "title": "Left",
+                    "engine": "claude",
+                    "project_path": "/projects/a",
+                    "geometry": { "x": 0.0, "y": 0.0, "width": 960.0, "height": 1080.0 }
+                },
+                {
+                    "tab_id": "right",
+                    "title": "Right",
+                    "engine": "codex",
+                    "geometry": { "x": 960.0, "y": 0.0, "width": 960.0, "height": 1080.0 }
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_two_window_layout_opens_at_specified_geometry() {
+        let layout = two_window_layout();
+        assert!(validate_layout(&layout).is_ok());
+
+        let opened: Vec<WindowCreationResult> = layout
+            .windows
+            .iter()
+            .map(|spec| {
+                let params = spec_params(spec);
+                let geometry = spec.geometry.unwrap();
+                assert_eq!(params.width, Some(geometry.width));
+                assert_eq!(params.height, Some(geometry.height));
+                // As built by `build_session_window`, before placement
+                let built = WindowCreationResult::from_measured(
+                    format!("session-window-{}", params.tab_id),
+                    None,
+                    geometry.width,
+                    geometry.height,
+                );
+                placed_result(built, &geometry)
+            })
+            .collect();
+
+        assert_eq!(opened.len(), 2);
+        assert_eq!(opened[0].window_label, "session-window-left");
+        assert_eq!((opened[0].x, opened[0].y), (0.0, 0.0));
+        assert_eq!(opened[1].window_label, "session-window-right");
+        assert_eq!(
+            (opened[1].x, opened[1].y, opened[1].width, opened[1].height),
+            (960.0, 0.0, 960.0, 1080.0)
+        );
+    }
+
+    #[test]
+    fn test_layout_rejects_empty_and_duplicate_tabs() {
+        let mut layout = two_window_layout();
+        layout.windows[1].params.tab_id = "left".to_string();
+        assert!(validate_layout(&layout).is_err());
+
+        layout.windows.clear();
+        assert!(validate_layout(&layout).is_err());
+    }
+}
@@ -0,0 +1,597 @@
+/**
+ * Session Window Lifecycle
+ *
+ * Handles the native events of a session window: persists its state when it
+ * closes and tells the main window when it gains or loses focus, moves,
+ * resizes or goes away (including through the OS close button).
+ *
+ * Windows flagged busy (a Claude/Codex run in progress) don't close right
+ * away: the frontend gets `close-blocked` and closes them by answering with
+ * `confirm-close`.
+ *
+ * When the last session window closes, the main window gets the focus back
+ * (unless turned off), instead of leaving it to whatever the OS picks.
+ *
+ * `session-window-closed` carries an `origin`: `programmatic` when the
+ * backend closed the window on the frontend's behalf (close, close-all,
+ * reattach), `user` for the OS close button and the like, so the frontend
+ * doesn't clean up a tab twice.
+ */
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, EventTarget, Listener, Manager, WebviewWindow, WindowEvent};
+
+use super::error::{report_error, window_not_found, AppError};
+use super::eventlog::{record_window_event, WindowEventKind};
+use super::geometry::{self, WindowGeometry};
+use super::ipc::emit_to_main_window;
+use super::registry::with_registry;
+use super::{is_session_window_label, restore, thumbnail};
+
+/// Event sent to a busy window whose close was held back
+const CLOSE_BLOCKED_EVENT: &str = "close-blocked";
+
+/// Event the window sends back once the user confirmed closing it
+const CONFIRM_CLOSE_EVENT: &str = "confirm-close";
+
+fn set_busy(app: &AppHandle, window_label: &str, busy: bool) -> bool {
+    with_registry(app, |registry| {
+        registry.update(window_label, |meta| meta.busy = busy)
+    })
+    .unwrap_or(false)
+}
+
+pub(super) fn is_busy(app: &AppHandle, window_label: &str) -> bool {
+    with_registry(app, |registry| registry.is_busy(window_label)).unwrap_or(false)
+}
+
+/// Who closed a session window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloseOrigin {
+    /// The OS close button, a keyboard shortcut, a confirmed close prompt
+    User,
+    /// A command closed it, so the frontend already knows
+    Programmatic,
+}
+
+/// Session windows closed by a command whose `Destroyed` hasn't arrived yet
+static PROGRAMMATIC_CLOSES: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Takes the pending programmatic close of a window, telling who closed it
+fn take_close_origin(pending: &mut HashSet<String>, window_label: &str) -> CloseOrigin {
+    if pending.remove(window_label) {
+        CloseOrigin::Programmatic
+    } else {
+        CloseOrigin::User
+    }
+}
+
+fn forget_programmatic_close(window_label: &str) {
+    if let Ok(mut pending) = PROGRAMMATIC_CLOSES.lock() {
+        pending.remove(window_label);
+    }
+}
+
+/// Closes a session window on the frontend's behalf
+///
+/// The `session-window-closed` it leads to is tagged `programmatic`.
+pub(super) fn close_programmatically(window: &WebviewWindow) -> Result<(), String> {
+    if let Ok(mut pending) = PROGRAMMATIC_CLOSES.lock() {
+        pending.insert(window.label().to_string());
+    }
+    window.close().map_err(|e| {
+        forget_programmatic_close(window.label());
+        format!("Failed to close window: {}", e)
+    })
+}
+
+/// Whether the main window is focused once the last session window closes
+static FOCUS_MAIN_ON_LAST_CLOSE: AtomicBool = AtomicBool::new(true);
+
+/// Whether closing a session window should hand the focus back to the main window
+///
+/// Only the last session window does, and not while the app is quitting.
+fn should_focus_main(enabled: bool, remaining_session_windows: usize, shutting_down: bool) -> bool {
+    enabled && remaining_session_windows == 0 && !shutting_down
+}
+
+/// Focuses the main window if `closed_label` was the last session window
+fn focus_main_if_last(app: &AppHandle, closed_label: &str) {
+    let remaining = app
+        .webview_windows()
+        .into_keys()
+        .filter(|label| is_session_window_label(label) && label != closed_label)
+        .count();
+    if !should_focus_main(
+        FOCUS_MAIN_ON_LAST_CLOSE.load(Ordering::SeqCst),
+        remaining,
+        restore::is_app_shutting_down(),
+    ) {
+        return;
+    }
+
+    if let Some(main) = app.get_webview_window("main") {
+        if let Err(e) = main.set_focus() {
+            log::warn!("[Window] Failed to focus main window: {}", e);
+        }
+    }
+}
+
+/// Whether a close request should be held back for confirmation.
+///
+/// Never blocks while the app is shutting down, so quitting from the main
+/// window can't hang on a busy session window.
+fn should_block_close(busy: bool, shutting_down: bool) -> bool {
+    busy && !shutting_down
+}
+
+/// Whether a payload the frontend sent (e.g. `confirm-close`) is meant for this window.
+///
+/// Global emits reach every window's listener, so a payload naming another
+/// window is ignored; an empty payload is taken as addressed to this one.
+pub(super) fn event_addressed_to(raw_payload: &str, window_label: &str) -> bool {
+    let mut value: serde_json::Value =
+        serde_json::from_str(raw_payload).unwrap_or(serde_json::Value::Null);
+    if let serde_json::Value::String(inner) = &value {
+        value = serde_json::from_str(inner).unwrap_or(serde_json::Value::Null);
+    }
+
+    match value
+        .get("window_label")
+        .or_else(|| value.get("windowLabel"))
+        .and_then(|v| v.as_str())
+    {
+        Some(label) => label == window_label,
+        None => true,
+    }
+}
+
+/// Lifecycle notifications sent to the main window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LifecycleEvent {
+    Focused,
+    Blurred,
+    Moved,
+    Resized,
+    Closed,
+}
+
+impl LifecycleEvent {
+    /// Name of the event emitted to the main window
+    fn event_name(&self) -> &'static str {
+        match self {
+            LifecycleEvent::Focused => "session-window-focused",
+            LifecycleEvent::Blurred => "session-window-blurred",
+            LifecycleEvent::Moved => "session-window-moved",
+            LifecycleEvent::Resized => "session-window-resized",
+            LifecycleEvent::Closed => "session-window-closed",
+        }
+    }
+}
+
+/// Maps a native window event to the notification it triggers, if any
+fn lifecycle_event(event: &WindowEvent) -> Option<LifecycleEvent> {
+    match event {
+        WindowEvent::Focused(true) => Some(LifecycleEvent::Focused),
+        WindowEvent::Focused(false) => Some(LifecycleEvent::Blurred),
+        WindowEvent::Moved(_) => Some(LifecycleEvent::Moved),
+        WindowEvent::Resized(_) => Some(LifecycleEvent::Resized),
+        WindowEvent::Destroyed => Some(LifecycleEvent::Closed),
+        _ => None,
+    }
+}
+
+/// Payload of the lifecycle events
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionWindowLifecyclePayload {
+    /// Label of the session window
+    pub window_label: String,
+    /// Tab identifier of the detached session
+    pub tab_id: String,
+    /// Current geometry, for move and resize events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geometry: Option<WindowGeometry>,
+    /// Who closed the window, for close events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<CloseOrigin>,
+}
+
+/// Sends a lifecycle notification to the main window
+fn notify_main_window(
+    app: &AppHandle,
+    kind: LifecycleEvent,
+    payload: &SessionWindowLifecyclePayload,
+) {
+    if let Err(e) = emit_to_main_window(app, kind.event_name(), payload) {
+        window_log!(
+            warn,
+            payload.window_label,
+            "Failed to emit {}: {}",
+            kind.event_name(),
+            e
+        );
+        report_error(app, "lifecycle", &e);
+    }
+}
+
+/// Registers the native event handlers of a freshly built session window
+pub(super) fn attach_lifecycle_handlers(app: &AppHandle, window: &WebviewWindow, tab_id: &str) {
+    let app = app.clone();
+    let window_for_events = window.clone();
+    let tab_id = tab_id.to_string();
+
+    // The user confirmed closing a busy window: drop the flag and close for real
+    let app_for_confirm = app.clone();
+    let window_for_confirm = window.clone();
+    let confirm_listener = window.listen(CONFIRM_CLOSE_EVENT, move |event| {
+        let label = window_for_confirm.label();
+        if !event_addressed_to(event.payload(), label) {
+            return;
+        }
+        set_busy(&app_for_confirm, label, false);
+        if let Err(e) = window_for_confirm.close() {
+            window_log!(error, label, "Failed to close session window: {}", e);
+        }
+    });
+
+    window.on_window_event(move |event| {
+        if let WindowEvent::CloseRequested { api, .. } = event {
+            let label = window_for_events.label();
+            if should_block_close(is_busy(&app, label), restore::is_app_shutting_down()) {
+                api.prevent_close();
+                // Closing after the confirmation is the user's doing
+                forget_programmatic_close(label);
+                window_log!(info, label, "Close of busy session window held back");
+                if let Err(e) = window_for_events.emit_to(
+                    EventTarget::webview_window(label),
+                    CLOSE_BLOCKED_EVENT,
+                    label,
+                ) {
+                    window_log!(warn, label, "Failed to emit {}: {}", CLOSE_BLOCKED_EVENT, e);
+                }
+                return;
+            }
+
+            // The window still exists here, so this is the last chance to read its geometry
+            if let Err(e) = geometry::persist_window_geometry(&app, &window_for_events) {
+                window_log!(
+                    warn,
+                    window_for_events.label(),
+                    "Failed to save geometry: {}",
+                    e
+                );
+                report_error(&app, "lifecycle", &AppError::Other(e));
+            }
+        }
+
+        let Some(kind) = lifecycle_event(event) else {
+            return;
+        };
+
+        if kind == LifecycleEvent::Focused || kind == LifecycleEvent::Blurred {
+            let focused = kind == LifecycleEvent::Focused;
+            with_registry(&app, |registry| {
+                registry.set_focused(window_for_events.label(), focused)
+            });
+            if !focused {
+                thumbnail::refresh_thumbnail_on_blur(&app, window_for_events.label());
+            }
+        }
+        if kind == LifecycleEvent::Focused {
+            restore::touch_open_window(&app, &tab_id);
+            record_window_event(&app, WindowEventKind::Focused, window_for_events.label());
+        }
+        if kind == LifecycleEvent::Closed {
+            record_window_event(&app, WindowEventKind::Closed, window_for_events.label());
+            // Covers the OS close button as well as close_session_window
+            restore::forget_open_window(&app, &tab_id);
+            with_registry(&app, |registry| {
+                registry.unregister(window_for_events.label())
+            });
+            window_for_events.unlisten(confirm_listener);
+            focus_main_if_last(&app, window_for_events.label());
+        }
+
+        let geometry = match kind {
+            LifecycleEvent::Moved | LifecycleEvent::Resized => {
+                geometry::capture_geometry(&window_for_events).ok()
+            }
+            _ => None,
+        };
+
+        let origin = (kind == LifecycleEvent::Closed).then(|| {
+            PROGRAMMATIC_CLOSES
+                .lock()
+                .map(|mut pending| take_close_origin(&mut pending, window_for_events.label()))
+                .unwrap_or(CloseOrigin::User)
+        });
+
+        let payload = SessionWindowLifecyclePayload {
+            window_label: window_for_events.label().to_string(),
+            tab_id: tab_id.clone(),
+            geometry,
+            origin,
+        };
+        notify_main_window(&app, kind, &payload);
+    });
+}
+
+/// Flags whether a session window has a run in progress
+///
+/// While flagged, closing the window is held back and `close-blocked` is sent
+/// to it instead; the window closes once it emits `confirm-close`.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the session window
+/// * `busy` - Whether a run is in progress
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_busy(
+    app: AppHandle,
+    window_label: String,
+    busy: bool,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_busy", {
+        if app.get_webview_window(&window_label).is_none() || !set_busy(&app, &window_label, busy) {
+            return Err(window_not_found(&window_label));
+        }
+        Ok(())
+    })
+}
+
+/// A session window that failed to close
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CloseFailure {
+    pub window_label: String,
+    pub error: String,
+}
+
+/// Outcome of `close_all_session_windows`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CloseAllResult {
+    /// Windows that were closed
+    pub closed: Vec<String>,
+    /// Busy windows that got `close-blocked` and stay open
+    pub skipped_busy: Vec<String>,
+    /// Windows whose close call failed
+    pub failed: Vec<CloseFailure>,
+}
+
+/// Closes every window in `labels`, sorting them into the result
+///
+/// Busy windows are still asked to close so their close guard sends
+/// `close-blocked`, but are reported as skipped since they stay open.
+pub(super) fn close_windows(
+    labels: Vec<String>,
+    is_busy: impl Fn(&str) -> bool,
+    mut close: impl FnMut(&str) -> Result<(), String>,
+) -> CloseAllResult {
+    let mut result = CloseAllResult::default();
+    for label in labels {
+        let busy = is_busy(&label);
+        match close(&label) {
+            Ok(()) if busy => result.skipped_busy.push(label),
+            Ok(()) => result.closed.push(label),
+            Err(error) => result.failed.push(CloseFailure {
+                window_label: label,
+                error,
+            }),
+        }
+    }
+    result
+}
+
+/// Closes all session windows
+///
+/// Busy windows are left open and get `close-blocked`, like when they are
+/// closed one by one, so running tasks aren't killed.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+///
+/// # Returns
+/// * `Result<CloseAllResult, AppError>` - Closed, busy and failed windows
+#[tauri::command]
+pub async fn close_all_session_windows(app: AppHandle) -> Result<CloseAllResult, AppError> {
+    reported!(app, "close_all_session_windows", {
+        let mut labels: Vec<String> = app
+            .webview_windows()
+            .into_keys()
+            .filter(|label| is_session_window_label(label))
+            .collect();
+        labels.sort();
+
+        let result = close_windows(
+            labels,
+            |label| is_busy(&app, label),
+            |label| match app.get_webview_window(label) {
+                Some(window) => close_programmatically(&window),
+                None => Err(window_not_found(label).to_string()),
+            },
+        );
+
+        log::info!(
+            "[Window] Closed {} session window(s), {} busy, {} failed",
+            result.closed.len(),
+            result.skipped_busy.len(),
+            result.failed.len()
+        );
+        Ok(result)
+    })
+}
+
+/// Sets whether the main window is focused once the last session window closes
+///
+/// # Arguments
+/// * `enabled` - Whether to focus the main window (default: true)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_focus_main_on_last_close(enabled: bool) -> Result<(), AppError> {
+    FOCUS_MAIN_ON_LAST_CLOSE.store(enabled, Ordering::SeqCst);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tauri::{PhysicalPosition, PhysicalSize};
+
+    #[test]
+    fn test_lifecycle_event_mapping() {
+        assert_eq!(
+            lifecycle_event(&WindowEvent::Focused(true)),
+            Some(LifecycleEvent::Focused)
+        );
+        assert_eq!(
+            lifecycle_event(&WindowEvent::Focused(false)),
+            Some(LifecycleEvent::Blurred)
+        );
+        assert_eq!(
+            lifecycle_event(&WindowEvent::Moved(PhysicalPosition::new(10, 20))),
+            Some(LifecycleEvent::Moved)
+        );
+        assert_eq!(
+            lifecycle_event(&WindowEvent::Resized(PhysicalSize::new(800, 600))),
+            Some(LifecycleEvent::Resized)
+        );
+        assert_eq!(
+            lifecycle_event(&WindowEvent::Destroyed),
+            Some(LifecycleEvent::Closed)
+        );
+        assert_eq!(
+            lifecycle_event(&WindowEvent::ThemeChanged(tauri::Theme::Dark)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_main_focused_only_after_last_session_window() {
+        // The last session window closed
+        assert!(should_focus_main(true, 0, false));
+        // Other session windows are still open
+        assert!(!should_focus_main(true, 2, false));
+        // Turned off by the user
+        assert!(!should_focus_main(false, 0, false));
+        // The app is quitting anyway
+        assert!(!should_focus_main(true, 0, true));
+    }
+
+    #[test]
+    fn test_close_blocked_only_when_busy() {
+        assert!(should_block_close(true, false));
+        assert!(!should_block_close(false, false));
+        // Quitting the app always goes through
+        assert!(!should_block_close(true, true));
+    }
+
+    #[test]
+    fn test_confirm_close_applies_to_addressed_window() {
+        let label = "session-window-tab-1";
+        assert!(event_addressed_to("null", label));
+        assert!(event_addressed_to("", label));
+        assert!(event_addressed_to(
+            r#"{"window_label":"session-window-tab-1"}"#,
+            label
+        ));
+        assert!(event_addressed_to(
+            r#""{\"windowLabel\":\"session-window-tab-1\"}""#,
+            label
+        ));
+        assert!(!event_addressed_to(
+            r#"{"window_label":"session-window-tab-2"}"#,
+            label
+        ));
+    }
+
+    #[test]
+    fn test_lifecycle_payload_omits_missing_geometry() {
+        let payload = SessionWindowLifecyclePayload {
+            window_label: "session-window-tab-1".to_string(),
+            tab_id: "tab-1".to_string(),
+            geometry: None,
+            origin: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&payload).unwrap(),
+            serde_json::json!({
+                "window_label": "session-window-tab-1",
+                "tab_id": "tab-1",
+            })
+        );
+    }
+
+    #[test]
+    fn test_close_origin_differs_between_close_paths() {
+        let mut pending = HashSet::new();
+
+        // close_session_window and friends mark the window before closing it
+        pending.insert("session-window-a".to_string());
+        assert_eq!(
+            take_close_origin(&mut pending, "session-window-a"),
+            CloseOrigin::Programmatic
+        );
+        // The OS close button doesn't, and a mark is only used once
+        assert_eq!(
+            take_close_origin(&mut pending, "session-window-a"),
+            CloseOrigin::User
+        );
+
+        let closed = |origin| SessionWindowLifecyclePayload {
+            window_label: "session-window-a".to_string(),
+            tab_id: "a".to_string(),
+            geometry: None,
+            origin: Some(origin),
+        };
+        assert_eq!(
+            serde_json::to_value(closed(CloseOrigin::Programmatic)).unwrap()["origin"],
+            "programmatic"
+        );
+        assert_eq!(
+            serde_json::to_value(closed(CloseOrigin::User)).unwrap()["origin"],
+            "user"
+        );
+    }
+
+    #[test]
+    fn test_close_windows_sorts_outcomes() {
+        let mut attempted = Vec::new();
+        let result = close_windows(
+            vec![
+                "session-window-a".to_string(),
+                "session-window-b".to_string(),
+                "session-window-c".to_string(),
+            ],
+            |label| label == "session-window-b",
+            |label| {
+                attempted.push(label.to_string());
+                if label == "session-window-c" {
+                    Err("Failed to close window: gone".to_string())
+                } else {
+                    Ok(())
+                }
+            },
+        );
+
+        // The busy window is asked too, so its guard can send close-blocked
+        assert_eq!(attempted.len(), 3);
+        assert_eq!(result.closed, vec!["session-window-a"]);
+        assert_eq!(result.skipped_busy, vec!["session-window-b"]);
+        assert_eq!(
+            result.failed,
+            vec![CloseFailure {
+                window_label: "session-window-c".to_string(),
+                error: "Failed to close window: gone".to_string(),
+            }]
+        );
+    }
+}
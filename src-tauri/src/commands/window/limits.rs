@@ -0,0 +1,245 @@
+/**
+ * Session Window Limit
+ *
+ * Caps how many session windows can be open at once, since every webview
+ * costs memory and GPU resources. Focusing an already open window is never
+ * limited; only building a new one is.
+ *
+ * Also holds the size cap of broadcast payloads, which are copied once per
+ * receiving window.
+ */
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::error::AppError;
+use super::is_session_window_label;
+
+/// Session windows allowed at once unless configured otherwise
+pub const DEFAULT_MAX_SESSION_WINDOWS: usize = 20;
+
+/// Largest broadcast payload allowed unless configured otherwise, in bytes
+pub const DEFAULT_MAX_BROADCAST_PAYLOAD_BYTES: usize = 1024 * 1024;
+
+/// Event broadcast when a session window was refused because of the limit
+const WINDOW_LIMIT_REACHED_EVENT: &str = "window-limit-reached";
+
+/// Window limit settings, managed as app state
+#[derive(Debug)]
+pub struct WindowLimits {
+    max_session_windows: AtomicUsize,
+    max_broadcast_payload_bytes: AtomicUsize,
+}
+
+impl Default for WindowLimits {
+    fn default() -> Self {
+        Self {
+            max_session_windows: AtomicUsize::new(DEFAULT_MAX_SESSION_WINDOWS),
+            max_broadcast_payload_bytes: AtomicUsize::new(DEFAULT_MAX_BROADCAST_PAYLOAD_BYTES),
+        }
+    }
+}
+
+/// Payload of the `window-limit-reached` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct WindowLimitPayload {
+    /// Tab whose window was refused
+    tab_id: String,
+    /// The configured limit
+    max_session_windows: usize,
+}
+
+pub(super) fn max_session_windows(app: &AppHandle) -> usize {
+    app.try_state::<WindowLimits>()
+        .map(|limits| limits.max_session_windows.load(Ordering::SeqCst))
+        .unwrap_or(DEFAULT_MAX_SESSION_WINDOWS)
+}
+
+/// The configured size cap of broadcast payloads, in bytes
+pub(super) fn max_broadcast_payload_bytes(app: &AppHandle) -> usize {
+    app.try_state::<WindowLimits>()
+        .map(|limits| limits.max_broadcast_payload_bytes.load(Ordering::SeqCst))
+        .unwrap_or(DEFAULT_MAX_BROADCAST_PAYLOAD_BYTES)
+}
+
+/// Whether one more session window fits next to `open` ones
+pub(super) fn admit_window(open: usize, max: usize) -> Result<(), String> {
+    if open >= max {
+        Err(format!(
+            "window limit reached: {} session windows are already open",
+            max
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Number of session windows a new one has to fit next to
+///
+/// Windows still being built count as well, so concurrent creations can't
+/// all pass the check; the asking window itself is left out.
+fn counted_windows<I>(open: I, building: &HashSet<String>, window_label: &str) -> usize
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut labels: HashSet<String> = open
+        .into_iter()
+        .filter(|label| is_session_window_label(label))
+        .collect();
+    labels.extend(building.iter().cloned());
+    labels.remove(window_label);
+    labels.len()
+}
+
+/// Refuses a new session window once the limit is reached
+///
+/// `building` are the labels of the windows being built, the asking one
+/// included. Emits `window-limit-reached` so the frontend can tell the user
+/// why the window didn't open.
+pub(super) fn check_window_limit(
+    app: &AppHandle,
+    tab_id: &str,
+    window_label: &str,
+    building: &HashSet<String>,
+) -> Result<(), String> {
+    let open = counted_windows(app.webview_windows().into_keys(), building, window_label);
+    let max = max_session_windows(app);
+
+    admit_window(open, max).inspect_err(|_| {
+        let payload = WindowLimitPayload {
+            tab_id: tab_id.to_string(),
+            max_session_windows: max,
+        };
+        if let Err(e) = app.emit(WINDOW_LIMIT_REACHED_EVENT, payload) {
+            log::warn!(
+                "[Window] Failed to emit {}: {}",
+                WINDOW_LIMIT_REACHED_EVENT,
+                e
+            );
+        }
+    })
+}
+
+/// Sets how many session windows can be open at once
+///
+/// Windows that are already open stay open if there are more than the new limit.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `max_session_windows` - The new limit (at least 1)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_max_session_windows(
+    app: AppHandle,
+    max_session_windows: usize,
+) -> Result<(), AppError> {
+    reported!(app, "set_max_session_windows", {
+        if max_session_windows == 0 {
+            return Err(AppError::InvalidParam(
+                "Invalid max_session_windows: must be at least 1".to_string(),
+            ));
+        }
+
+        let limits = app
+            .try_state::<WindowLimits>()
+            .ok_or_else(|| AppError::Other("Window limits are not available".to_string()))?;
+        limits
+            .max_session_windows
+            .store(max_session_windows, Ordering::SeqCst);
+        Ok(())
+    })
+}
+
+/// Sets the largest payload a broadcast may carry (default 1 MB)
+///
+/// Larger payloads are rejected by the broadcast commands and have to go
+/// through `broadcast_chunked`, which also sends one chunk at most this big.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `max_bytes` - The new limit in bytes (at least 1)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_max_broadcast_payload_size(
+    app: AppHandle,
+    max_bytes: usize,
+) -> Result<(), AppError> {
+    reported!(app, "set_max_broadcast_payload_size", {
+        if max_bytes == 0 {
+            return Err(AppError::InvalidParam(
+                "Invalid max_bytes: must be at least 1".to_string(),
+            ));
+        }
+
+        let limits = app
+            .try_state::<WindowLimits>()
+            .ok_or_else(|| AppError::Other("Window limits are not available".to_string()))?;
+        limits
+            .max_broadcast_payload_bytes
+            .store(max_bytes, Ordering::SeqCst);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_beyond_limit_is_rejected() {
+        let max = 3;
+        for open in 0..max {
+            assert_eq!(admit_window(open, max), Ok(()));
+        }
+        // The N+1th window
+        assert_eq!(
+            admit_window(max, max),
+            Err("window limit reached: 3 session windows are already open".to_string())
+        );
+    }
+
+    #[test]
+    fn test_windows_being_built_count_against_the_limit() {
+        let open = ["main", "session-window-a"].map(String::from);
+        let building: HashSet<String> = ["session-window-b", "session-window-c"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        // b and c are both in flight: each one sees the other
+        assert_eq!(
+            counted_windows(open.clone(), &building, "session-window-b"),
+            2
+        );
+        assert_eq!(
+            counted_windows(open.clone(), &building, "session-window-c"),
+            2
+        );
+        assert!(admit_window(
+            counted_windows(open.clone(), &building, "session-window-c"),
+            2
+        )
+        .is_err());
+
+        // A window that was built but hasn't released its claim counts once
+        let built = ["session-window-a", "session-window-b"].map(String::from);
+        assert_eq!(counted_windows(built, &building, "session-window-c"), 2);
+    }
+
+    #[test]
+    fn test_window_limit_payload() {
+        let payload = WindowLimitPayload {
+            tab_id: "tab-1".to_string(),
+            max_session_windows: DEFAULT_MAX_SESSION_WINDOWS,
+        };
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::json!({ "tab_id": "tab-1", "max_session_windows": 20 })
+        );
+    }
+}
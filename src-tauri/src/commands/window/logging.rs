@@ -0,0 +1,51 @@
+/**
+ * Per-Window Log Context
+ *
+ * Tags log lines with the label of the window they are about, e.g.
+ * `[Window session-window-abc123] Session window created`, so one session's
+ * lifecycle can be traced through the interleaved logs of a multi-window run.
+ */
+use std::time::Instant;
+
+/// Logs a line tagged with a window label
+///
+/// `window_log!(info, window_label, "Snapped to {:?}", position)` logs
+/// `[Window <label>] Snapped to ...` at the given level.
+macro_rules! window_log {
+    ($level:ident, $window_label:expr, $($arg:tt)+) => {
+        log::$level!("[Window {}] {}", $window_label, format_args!($($arg)+))
+    };
+}
+
+/// Brackets a window command in the logs for as long as it is alive
+///
+/// Logs at debug level when the command starts and, with the elapsed time,
+/// when the scope is dropped, whichever way the command returns.
+pub(super) struct LogScope {
+    window_label: String,
+    command: &'static str,
+    started: Instant,
+}
+
+impl LogScope {
+    pub(super) fn new(command: &'static str, window_label: &str) -> Self {
+        window_log!(debug, window_label, "{} started", command);
+        Self {
+            window_label: window_label.to_string(),
+            command,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Drop for LogScope {
+    fn drop(&mut self) {
+        window_log!(
+            debug,
+            self.window_label,
+            "{} finished in {:?}",
+            self.command,
+            self.started.elapsed()
+        );
+    }
+}
@@ -0,0 +1,2153 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+/**
+ * Window Management Commands
+ *
+ * Provides commands for creating and managing independent session windows.
+ * Supports detaching tabs into separate windows and cross-window communication.
+ */
+use tauri::webview::PageLoadEvent;
+use tauri::{
+    AppHandle, Emitter, EventTarget, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+};
+
+use self::error::window_not_found;
+
+#[macro_use]
+mod logging;
+#[macro_use]
+mod error;
+
+mod activity;
+mod arrange;
+mod attention;
+mod background;
+mod constraints;
+mod decorations;
+mod desktops;
+mod duplicate;
+mod envelope;
+mod eventlog;
+mod focus;
+mod geometry;
+mod groups;
+mod hover;
+mod icon;
+mod ipc;
+mod layout;
+mod lifecycle;
+mod limits;
+mod monitor;
+mod opacity;
+mod preview;
+mod reattach;
+mod registry;
+mod reload;
+mod request;
+mod restore;
+mod reveal;
+mod scale;
+mod shutdown;
+mod snap;
+mod snapshot;
+mod softclose;
+mod switch;
+mod thumbnail;
+mod titlebar;
+mod transfer;
+mod validate;
+mod visibility;
+mod zoom;
+
+pub use self::activity::report_window_activity;
+pub use self::arrange::arrange_windows;
+pub use self::attention::notify_window_attention;
+pub use self::background::set_window_background_color;
+pub use self::constraints::{
+    set_window_maximizable, set_window_resizable, set_window_size_constraints,
+};
+pub use self::decorations::set_window_decorations;
+pub use self::desktops::set_window_visible_on_all_workspaces;
+pub use self::duplicate::duplicate_session_window;
+pub use self::envelope::{create_session_window_timed, open_workspace_layout_timed};
+pub use self::error::AppError;
+pub use self::eventlog::{get_window_events, set_window_event_log_enabled, WindowEventLog};
+pub use self::focus::{get_focused_session_window, is_window_focused, raise_window};
+pub use self::geometry::{restore_window_geometry, save_window_geometry};
+pub use self::groups::{close_group, focus_group, set_window_group};
+pub use self::hover::set_focus_follows_mouse;
+pub use self::icon::set_window_icon;
+pub use self::ipc::{
+    broadcast_chunked, broadcast_to_engine_windows, broadcast_to_session_windows,
+    broadcast_to_session_windows_detailed, emit_json_to_window, emit_to_window, emit_to_windows,
+    send_text_to_window,
+};
+pub use self::layout::open_workspace_layout;
+pub use self::lifecycle::{
+    close_all_session_windows, set_focus_main_on_last_close, set_window_busy,
+};
+pub use self::limits::{set_max_broadcast_payload_size, set_max_session_windows, WindowLimits};
+pub use self::monitor::{
+    get_window_relative_position, list_monitors, move_window_to_monitor,
+    set_window_relative_position,
+};
+pub use self::opacity::set_window_opacity;
+pub use self::preview::create_preview_window;
+pub use self::reattach::{mirror_session_in_main, reattach_session_window};
+pub use self::registry::{
+    get_window_meta, reconcile_windows, update_window_engine, WindowMeta, WindowRegistry,
+};
+pub use self::reload::reload_session_window;
+pub use self::request::request_from_window;
+pub use self::restore::{
+    get_session_window_restore_enabled, restore_session_windows,
+    restore_session_windows_on_startup, set_session_window_restore_enabled,
+};
+pub use self::scale::{get_window_scale_factor, handle_scale_factor_change};
+pub use self::shutdown::persist_windows_on_exit;
+pub use self::snap::snap_window;
+pub use self::snapshot::take_session_snapshot;
+pub use self::softclose::close_session_window_saving;
+pub use self::switch::switch_window_engine;
+pub use self::thumbnail::capture_window_thumbnail;
+pub use self::titlebar::{
+    get_titlebar_theme, handle_system_theme_change, set_titlebar_color, set_titlebar_theme,
+};
+pub use self::transfer::transfer_tab_between_windows;
+pub use self::validate::validate_session_window_params;
+pub use self::visibility::{
+    minimize_all_session_windows, restore_all_session_windows, set_window_skip_taskbar,
+    set_window_visible,
+};
+pub use self::zoom::set_window_zoom;
+
+/// Resolves a window-state file inside the app data dir
+fn window_state_file(app: &AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    Ok(app_data_dir.join(file_name))
+}
+
+/// Execution engine of a session window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", try_from = "String")]
+pub enum Engine {
+    Claude,
+    Codex,
+    Gemini,
+}
+
+impl Engine {
+    /// The value used in window URLs and on the frontend
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Engine::Claude => "claude",
+            Engine::Codex => "codex",
+            Engine::Gemini => "gemini",
+        }
+    }
+}
+
+impl std::fmt::Display for Engine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for Engine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "claude" => Ok(Engine::Claude),
+            "codex" => Ok(Engine::Codex),
+            "gemini" => Ok(Engine::Gemini),
+            other => Err(format!("unsupported engine: {}", other)),
+        }
+    }
+}
+
+impl TryFrom<String> for Engine {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Parameters for creating a new session window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateSessionWindowParams {
+    /// Unique identifier for the tab being detached
+    pub tab_id: String,
+    /// Optional session ID (for existing sessions)
+    pub session_id: Option<String>,
+    /// Project path for the session
+    pub project_path: Option<String>,
+    /// Window title
+    pub title: String,
+    /// Execution engine: 'claude' | 'codex' | 'gemini'
+    pub engine: Option<Engine>,
+    /// Name of the monitor to open on (defaults to the main window's monitor)
+    #[serde(default)]
+    pub monitor_hint: Option<String>,
+    /// Initial inner width in logical pixels (default 1000)
+    #[serde(default)]
+    pub width: Option<f64>,
+    /// Initial inner height in logical pixels (default 700)
+    #[serde(default)]
+    pub height: Option<f64>,
+    /// Minimum inner width in logical pixels (default 600)
+    #[serde(default)]
+    pub min_width: Option<f64>,
+    /// Minimum inner height in logical pixels (default 400)
+    #[serde(default)]
+    pub min_height: Option<f64>,
+    /// Maximum inner width in logical pixels (default: none)
+    #[serde(default)]
+    pub max_width: Option<f64>,
+    /// Maximum inner height in logical pixels (default: none)
+    #[serde(default)]
+    pub max_height: Option<f64>,
+    /// Prompt to submit once the session is ready
+    #[serde(default)]
+    pub initial_prompt: Option<String>,
+    /// Skip the existence check of `project_path` (remote or virtual paths)
+    #[serde(default)]
+    pub skip_path_check: bool,
+    /// State to open in; defaults to the state the tab's window was closed in
+    #[serde(default)]
+    pub initial_state: Option<geometry::WindowState>,
+    /// Workspace to namespace the window label with
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Use the native title bar and border instead of the custom title bar
+    /// (default: frameless)
+    #[serde(default)]
+    pub decorations: Option<bool>,
+    /// Additional query parameters for the frontend (theme, layout, readonly, ...)
+    #[serde(default)]
+    pub extra_params: Option<HashMap<String, String>>,
+    /// Opaque UI state (JSON) to hand to the window once it is ready
+    ///
+    /// Not persisted for restore: it only describes the moment of detaching.
+    #[serde(default, skip_serializing)]
+    pub session_snapshot: Option<String>,
+}
+
+/// Default inner size of a session window
+const DEFAULT_WINDOW_SIZE: (f64, f64) = (1000.0, 700.0);
+
+/// Default minimum inner size of a session window
+const DEFAULT_MIN_WINDOW_SIZE: (f64, f64) = (600.0, 400.0);
+
+/// Resolved inner, minimum and maximum size of a session window
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SessionWindowSize {
+    width: f64,
+    height: f64,
+    min_width: f64,
+    min_height: f64,
+    max_width: Option<f64>,
+    max_height: Option<f64>,
+}
+
+/// Fills in the size defaults and keeps the size within the minimum and maximum
+fn resolve_window_size(params: &CreateSessionWindowParams) -> Result<SessionWindowSize, String> {
+    let positive = |name: &str, value: Option<f64>, default: f64| match value {
+        Some(v) if !(v.is_finite() && v > 0.0) => Err(format!(
+            "Invalid {}: must be a positive number, got {}",
+            name, v
+        )),
+        Some(v) => Ok(v),
+        None => Ok(default),
+    };
+
+    let min_width = positive("min_width", params.min_width, DEFAULT_MIN_WINDOW_SIZE.0)?;
+    let min_height = positive("min_height", params.min_height, DEFAULT_MIN_WINDOW_SIZE.1)?;
+    let width = positive("width", params.width, DEFAULT_WINDOW_SIZE.0)?;
+    let height = positive("height", params.height, DEFAULT_WINDOW_SIZE.1)?;
+    let max_width = params
+        .max_width
+        .map(|v| positive("max_width", Some(v), v))
+        .transpose()?;
+    let max_height = params
+        .max_height
+        .map(|v| positive("max_height", Some(v), v))
+        .transpose()?;
+    constraints::check_min_max("width", min_width, max_width)?;
+    constraints::check_min_max("height", min_height, max_height)?;
+
+    Ok(SessionWindowSize {
+        width: width.max(min_width).min(max_width.unwrap_or(f64::INFINITY)),
+        height: height
+            .max(min_height)
+            .min(max_height.unwrap_or(f64::INFINITY)),
+        min_width,
+        min_height,
+        max_width,
+        max_height,
+    })
+}
+
+/// Checks that a project path exists and is a directory
+fn validate_project_path(project_path: &str) -> Result<(), String> {
+    let path = std::fs::canonicalize(project_path)
+        .map_err(|_| format!("project path does not exist: {}", project_path))?;
+    if !path.is_dir() {
+        return Err(format!("project path is not a directory: {}", project_path));
+    }
+    Ok(())
+}
+
+/// Canonical form of a project path, or the path as given if it can't be resolved
+///
+/// Drops the `\\?\` prefix Windows adds, which the frontend doesn't expect.
+fn canonical_project_path(project_path: &str) -> String {
+    match std::fs::canonicalize(project_path) {
+        Ok(path) => {
+            let path = path.to_string_lossy();
+            path.strip_prefix(r"\\?\").unwrap_or(&path).to_string()
+        }
+        Err(_) => project_path.to_string(),
+    }
+}
+
+/// The creation parameters with what the backend filled in or normalized:
+/// the canonical project path, the size defaults and limits, and the
+/// decorations.
+fn resolved_params(
+    params: &CreateSessionWindowParams,
+) -> Result<CreateSessionWindowParams, String> {
+    let size = resolve_window_size(params)?;
+    Ok(CreateSessionWindowParams {
+        project_path: params.project_path.as_deref().map(canonical_project_path),
+        width: Some(size.width),
+        height: Some(size.height),
+        min_width: Some(size.min_width),
+        min_height: Some(size.min_height),
+        max_width: size.max_width,
+        max_height: size.max_height,
+        decorations: Some(native_decorations(params)),
+        session_snapshot: None,
+        ..params.clone()
+    })
+}
+
+/// Result of window creation
+#[derive(Debug, Serialize)]
+pub struct WindowCreationResult {
+    /// The label/identifier of the created window
+    pub window_label: String,
+    /// Whether the window was successfully created
+    pub success: bool,
+    /// Outer position of the window in logical pixels
+    pub x: f64,
+    pub y: f64,
+    /// Inner size of the window in logical pixels
+    pub width: f64,
+    pub height: f64,
+    /// The parameters as resolved by the backend, so a caller doesn't have
+    /// to re-derive the canonical project path or the defaults
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<CreateSessionWindowParams>,
+}
+
+impl WindowCreationResult {
+    /// Builds the result from the geometry measured on the live window.
+    ///
+    /// Some platforms report a zero size until the window has been shown, so
+    /// the requested size is used if the measurement is missing or empty.
+    fn from_measured(
+        window_label: String,
+        measured: Option<geometry::WindowGeometry>,
+        requested_width: f64,
+        requested_height: f64,
+    ) -> Self {
+        let (x, y) = measured.map(|g| (g.x, g.y)).unwrap_or((0.0, 0.0));
+        let (width, height) = match measured {
+            Some(g) if g.width > 0.0 && g.height > 0.0 => (g.width, g.height),
+            _ => (requested_width, requested_height),
+        };
+
+        Self {
+            window_label,
+            success: true,
+            x,
+            y,
+            width,
+            height,
+            params: None,
+        }
+    }
+
+    /// Adds the resolved creation parameters to the result
+    fn with_params(self, params: CreateSessionWindowParams) -> Self {
+        Self {
+            params: Some(params),
+            ..self
+        }
+    }
+}
+
+/// Creates a new independent window for a session
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `params` - Window creation parameters
+///
+/// # Returns
+/// * `Result<WindowCreationResult, AppError>` - The window label or an error message
+#[tauri::command]
+pub async fn create_session_window(
+    app: AppHandle,
+    params: CreateSessionWindowParams,
+) -> Result<WindowCreationResult, AppError> {
+    reported!(app, "create_session_window", {
+        build_session_window(&app, &params).await
+    })
+}
+
+/// Prefix of every session window label
+pub const WINDOW_LABEL_PREFIX: &str = "session-window-";
+
+/// Separates the optional workspace from the tab_id in a label.
+///
+/// Tab IDs may contain '-', so the workspace is split off at a character
+/// neither of them can contain.
+const WORKSPACE_SEPARATOR: char = ':';
+
+/// Checks one segment (tab_id or workspace) of a window label
+pub(super) fn validate_label_segment(name: &str, value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        return Err(format!("Invalid {}: must not be empty", name));
+    }
+
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "Invalid {} {:?}: only letters, digits, '_' and '-' are allowed",
+            name, value
+        ));
+    }
+
+    Ok(())
+}
+
+/// Builds the window label for a tab, namespaced by an optional workspace
+///
+/// Labels look like `session-window-{tab_id}` or
+/// `session-window-{workspace}:{tab_id}`. Both segments must be non-empty and
+/// only contain `[A-Za-z0-9_-]`, so the label is always accepted by Tauri and
+/// can't collide with another tab's label.
+///
+/// # Arguments
+/// * `workspace` - The workspace the window belongs to, if any
+/// * `tab_id` - The tab identifier
+///
+/// # Returns
+/// * `Result<String, String>` - The window label or an error message
+pub fn window_label_for_workspace_tab(
+    workspace: Option<&str>,
+    tab_id: &str,
+) -> Result<String, String> {
+    validate_label_segment("tab_id", tab_id)?;
+
+    match workspace {
+        Some(workspace) => {
+            validate_label_segment("workspace", workspace)?;
+            Ok(format!(
+                "{}{}{}{}",
+                WINDOW_LABEL_PREFIX, workspace, WORKSPACE_SEPARATOR, tab_id
+            ))
+        }
+        None => Ok(format!("{}{}", WINDOW_LABEL_PREFIX, tab_id)),
+    }
+}
+
+/// Whether a label belongs to a session window (as opposed to e.g. `main`)
+pub fn is_session_window_label(window_label: &str) -> bool {
+    window_label.starts_with(WINDOW_LABEL_PREFIX)
+}
+
+/// Parts of a session window label
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionWindowLabel<'a> {
+    pub workspace: Option<&'a str>,
+    pub tab_id: &'a str,
+}
+
+/// Splits a session window label into its workspace and tab_id
+///
+/// Returns None for labels that aren't session window labels.
+pub fn parse_session_window_label(window_label: &str) -> Option<SessionWindowLabel<'_>> {
+    let rest = window_label.strip_prefix(WINDOW_LABEL_PREFIX)?;
+    let (workspace, tab_id) = match rest.split_once(WORKSPACE_SEPARATOR) {
+        Some((workspace, tab_id)) => (Some(workspace), tab_id),
+        None => (None, rest),
+    };
+    if tab_id.is_empty() || workspace.is_some_and(str::is_empty) {
+        return None;
+    }
+
+    Some(SessionWindowLabel { workspace, tab_id })
+}
+
+/// Extracts the tab_id from a session window label
+pub fn tab_id_from_label(window_label: &str) -> Option<&str> {
+    parse_session_window_label(window_label).map(|label| label.tab_id)
+}
+
+/// Longest encoded initial prompt that is still put into the window URL.
+///
+/// Kept well below the ~2000 character URL length that older webviews and
+/// proxies reliably handle; longer prompts are sent as an event after load.
+const MAX_URL_PROMPT_LEN: usize = 1500;
+
+/// Event carrying an initial prompt too long for the URL
+const INITIAL_PROMPT_EVENT: &str = "initial-prompt";
+
+/// Returns the initial prompt if it is short enough to go into the URL
+fn url_prompt(params: &CreateSessionWindowParams) -> Option<&str> {
+    let prompt = params.initial_prompt.as_deref()?;
+    (urlencoding::encode(prompt).len() <= MAX_URL_PROMPT_LEN).then_some(prompt)
+}
+
+/// Longest encoded project path that is still put into the window URL.
+///
+/// Deeply nested paths grow quickly once percent-encoded; some webview
+/// backends silently truncate long URLs, so longer paths follow as an event.
+const MAX_URL_PROJECT_PATH_LEN: usize = 400;
+
+/// Event carrying a project path too long for the URL
+const PROJECT_PATH_EVENT: &str = "project-path";
+
+/// Returns the project path if it is short enough to go into the URL
+fn url_project_path(params: &CreateSessionWindowParams) -> Option<&str> {
+    let project_path = params.project_path.as_deref()?;
+    (urlencoding::encode(project_path).len() <= MAX_URL_PROJECT_PATH_LEN).then_some(project_path)
+}
+
+/// Builds a webview URL from query parameters.
+///
+/// Every key and value is percent-encoded, so values containing `&`, `=`, `#`
+/// or spaces can't corrupt the query string the frontend parses.
+pub(super) fn build_url<'a, I>(pairs: I) -> String
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    let query: Vec<String> = pairs
+        .into_iter()
+        .map(|(key, value)| {
+            format!(
+                "{}={}",
+                urlencoding::encode(key),
+                urlencoding::encode(value)
+            )
+        })
+        .collect();
+
+    format!("/?{}", query.join("&"))
+}
+
+/// Query keys the backend sets itself; `extra_params` can't override them
+const RESERVED_QUERY_KEYS: [&str; 9] = [
+    "window",
+    "tab_id",
+    "session_id",
+    "project_path",
+    "project_path_event",
+    "engine",
+    "decorated",
+    "initial_prompt",
+    "initial_prompt_event",
+];
+
+/// Checks that no extra query parameter clobbers one the backend sets
+fn validate_extra_params(extra_params: &HashMap<String, String>) -> Result<(), String> {
+    let mut reserved: Vec<&str> = extra_params
+        .keys()
+        .map(String::as_str)
+        .filter(|key| RESERVED_QUERY_KEYS.contains(key))
+        .collect();
+    if reserved.is_empty() {
+        return Ok(());
+    }
+
+    reserved.sort_unstable();
+    Err(format!(
+        "Invalid extra_params: reserved keys {}",
+        reserved.join(", ")
+    ))
+}
+
+/// Whether a session window is built with native decorations
+fn native_decorations(params: &CreateSessionWindowParams) -> bool {
+    params.decorations.unwrap_or(false)
+}
+
+/// Builds the webview URL for a session window.
+fn session_window_url(params: &CreateSessionWindowParams) -> String {
+    let engine = params.engine.map(|engine| engine.to_string());
+    let mut pairs = vec![("window", "session"), ("tab_id", params.tab_id.as_str())];
+
+    if let Some(ref session_id) = params.session_id {
+        pairs.push(("session_id", session_id));
+    }
+
+    // Long paths follow as a `project-path` event, flagged like long prompts
+    if params.project_path.is_some() {
+        match url_project_path(params) {
+            Some(project_path) => pairs.push(("project_path", project_path)),
+            None => pairs.push(("project_path_event", "1")),
+        }
+    }
+
+    if let Some(ref engine) = engine {
+        pairs.push(("engine", engine));
+    }
+
+    // Tells the frontend to leave the title bar to the OS
+    if native_decorations(params) {
+        pairs.push(("decorated", "1"));
+    }
+
+    // Long prompts follow as an `initial-prompt` event; the flag tells the
+    // frontend to wait for it
+    if params.initial_prompt.is_some() {
+        match url_prompt(params) {
+            Some(prompt) => pairs.push(("initial_prompt", prompt)),
+            None => pairs.push(("initial_prompt_event", "1")),
+        }
+    }
+
+    // Sorted so the same params always produce the same URL
+    if let Some(ref extra_params) = params.extra_params {
+        let mut extra: Vec<(&str, &str)> = extra_params
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        extra.sort_unstable();
+        pairs.extend(extra);
+    }
+
+    build_url(pairs)
+}
+
+/// Session details recovered from a session window's URL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionWindowInfo {
+    /// Label of the session window
+    pub window_label: String,
+    /// Tab identifier of the detached session
+    pub tab_id: String,
+    /// Session ID, if the session has started
+    pub session_id: Option<String>,
+    /// Project path for the session
+    pub project_path: Option<String>,
+    /// Execution engine
+    pub engine: Option<Engine>,
+}
+
+/// Recovers the session details from a session window's URL query
+///
+/// This is the inverse of `session_window_url`.
+fn session_info_from_url(
+    window_label: &str,
+    url: &tauri::Url,
+) -> Result<SessionWindowInfo, String> {
+    let mut info = SessionWindowInfo {
+        window_label: window_label.to_string(),
+        tab_id: String::new(),
+        session_id: None,
+        project_path: None,
+        engine: None,
+    };
+
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "tab_id" => info.tab_id = value.into_owned(),
+            "session_id" => info.session_id = Some(value.into_owned()),
+            "project_path" => info.project_path = Some(value.into_owned()),
+            "engine" => info.engine = Some(value.parse()?),
+            _ => {}
+        }
+    }
+
+    if info.tab_id.is_empty() {
+        return Err(format!("Window {} has no tab_id in its URL", window_label));
+    }
+
+    Ok(info)
+}
+
+/// Registry entry of a session window about to be built
+///
+/// Always holds the full project path, also when it was too long for the URL.
+fn session_window_meta(window_label: &str, params: &CreateSessionWindowParams) -> WindowMeta {
+    WindowMeta {
+        decorated: native_decorations(params),
+        session_snapshot: params.session_snapshot.clone(),
+        ..WindowMeta::new(
+            window_label,
+            &params.tab_id,
+            params.session_id.clone(),
+            params.project_path.clone(),
+            params.engine,
+        )
+    }
+}
+
+/// Labels of the session windows currently being built
+static IN_FLIGHT_LABELS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Marks a label as being built for as long as it is alive.
+///
+/// Two detaches of the same tab can both find no window under the label before
+/// either one is registered; only the one holding the guard builds it. The
+/// holder looks for the window again once it has the guard, since the other
+/// detach may have finished building it in the meantime.
+struct InFlightGuard {
+    window_label: String,
+}
+
+impl InFlightGuard {
+    /// Claims the label, or returns None if another creation already holds it
+    fn acquire(window_label: &str) -> Option<Self> {
+        let mut in_flight = IN_FLIGHT_LABELS.lock().ok()?;
+        if !in_flight.insert(window_label.to_string()) {
+            return None;
+        }
+        Some(Self {
+            window_label: window_label.to_string(),
+        })
+    }
+
+    /// Labels currently held by a creation
+    fn held_labels() -> HashSet<String> {
+        IN_FLIGHT_LABELS
+            .lock()
+            .map(|in_flight| in_flight.clone())
+            .unwrap_or_default()
+    }
+
+    /// Whether a creation currently holds the label
+    fn is_held(window_label: &str) -> bool {
+        IN_FLIGHT_LABELS
+            .lock()
+            .map(|in_flight| in_flight.contains(window_label))
+            .unwrap_or(false)
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = IN_FLIGHT_LABELS.lock() {
+            in_flight.remove(&self.window_label);
+        }
+    }
+}
+
+/// Registry entry of a window that is still being built.
+///
+/// The entry is registered up front so the window can look itself up while it
+/// loads, and rolled back on drop unless the build went through. A rollback
+/// puts back the entry it replaced, so a build that loses the label to a live
+/// window leaves that window's entry in place.
+struct PendingRegistration<'a> {
+    registry: Option<&'a WindowRegistry>,
+    window_label: String,
+    replaced: Option<WindowMeta>,
+    committed: bool,
+}
+
+impl<'a> PendingRegistration<'a> {
+    fn register(registry: Option<&'a WindowRegistry>, meta: WindowMeta) -> Self {
+        let window_label = meta.window_label.clone();
+        let replaced = registry.and_then(|registry| registry.register(meta));
+        Self {
+            registry,
+            window_label,
+            replaced,
+            committed: false,
+        }
+    }
+
+    /// Keeps the entry once the window exists
+    fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for PendingRegistration<'_> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        if let Some(registry) = self.registry {
+            match self.replaced.take() {
+                Some(replaced) => {
+                    registry.register(replaced);
+                }
+                None => registry.unregister(&self.window_label),
+            }
+        }
+    }
+}
+
+/// Why building a session window failed
+#[derive(Debug, PartialEq)]
+enum BuildError {
+    /// Another window already took the label
+    LabelTaken,
+    /// A hiccup of the webview backend that usually clears on retry
+    Transient(String),
+    /// Any other failure, as a message for the caller
+    Other(String),
+}
+
+/// Fragments (lowercase) of build errors caused by GPU or webview process
+/// churn rather than by the request itself
+const TRANSIENT_BUILD_ERRORS: &[&str] = &[
+    "gpu process",
+    "failed to create webview",
+    "0x8007139f", // WebView2: the group or resource is not in the correct state
+    "0x80004004", // WebView2: operation aborted
+];
+
+impl From<tauri::Error> for BuildError {
+    fn from(e: tauri::Error) -> Self {
+        match e {
+            tauri::Error::WindowLabelAlreadyExists(_)
+            | tauri::Error::WebviewLabelAlreadyExists(_) => BuildError::LabelTaken,
+            e => BuildError::from_message(format!("Failed to create window: {}", e)),
+        }
+    }
+}
+
+impl BuildError {
+    /// Sorts a build failure into transient or permanent by its message
+    fn from_message(message: String) -> Self {
+        let lowercase = message.to_lowercase();
+        if TRANSIENT_BUILD_ERRORS
+            .iter()
+            .any(|fragment| lowercase.contains(fragment))
+        {
+            BuildError::Transient(message)
+        } else {
+            BuildError::Other(message)
+        }
+    }
+}
+
+/// Waits before each retry of a transient build failure
+const BUILD_RETRY_BACKOFF: [Duration; 3] = [
+    Duration::from_millis(50),
+    Duration::from_millis(150),
+    Duration::from_millis(400),
+];
+
+/// Runs `build`, retrying transient failures after each `BUILD_RETRY_BACKOFF` step.
+///
+/// Permanent failures (and the label being taken) are returned right away.
+/// The waits are awaited, so a retry doesn't hold up a runtime worker.
+async fn retry_transient_build<T, W>(
+    window_label: &str,
+    mut build: impl FnMut() -> Result<T, BuildError>,
+    mut wait: impl FnMut(Duration) -> W,
+) -> Result<T, BuildError>
+where
+    W: std::future::Future<Output = ()>,
+{
+    let mut backoff = BUILD_RETRY_BACKOFF.iter();
+    loop {
+        match build() {
+            Err(BuildError::Transient(e)) => match backoff.next() {
+                Some(&delay) => {
+                    window_log!(
+                        warn,
+                        window_label,
+                        "Transient build failure, retrying in {:?}: {}",
+                        delay,
+                        e
+                    );
+                    wait(delay).await;
+                }
+                None => return Err(BuildError::Transient(e)),
+            },
+            result => return result,
+        }
+    }
+}
+
+/// Focuses the window that already has the label instead of building another one
+///
+/// Takes the window handle the caller's existence check returned rather than
+/// looking the label up again, so the window can't close in between.
+fn focus_existing_window(
+    window: &WebviewWindow,
+    size: &SessionWindowSize,
+) -> Result<WindowCreationResult, String> {
+    // Re-detaching the tab of a minimized window should visibly bring it back
+    focus::bring_to_front(window)?;
+    Ok(WindowCreationResult::from_measured(
+        window.label().to_string(),
+        geometry::capture_geometry(window).ok(),
+        size.width,
+        size.height,
+    ))
+}
+
+/// Builds (or focuses) the window for a session.
+///
+/// Shared by `create_session_window` and the startup restore so both go
+/// through the same URL, geometry and event wiring. The result echoes the
+/// resolved parameters.
+async fn build_session_window(
+    app: &AppHandle,
+    params: &CreateSessionWindowParams,
+) -> Result<WindowCreationResult, AppError> {
+    let result = build_window(app, params).await?;
+    Ok(result.with_params(resolved_params(params).map_err(AppError::InvalidParam)?))
+}
+
+async fn build_window(
+    app: &AppHandle,
+    params: &CreateSessionWindowParams,
+) -> Result<WindowCreationResult, AppError> {
+    // Generate unique window label
+    let window_label = window_label_for_workspace_tab(params.workspace.as_deref(), &params.tab_id)
+        .map_err(AppError::InvalidParam)?;
+    let _scope = logging::LogScope::new("build_session_window", &window_label);
+    let size = resolve_window_size(params).map_err(AppError::InvalidParam)?;
+
+    // Focus existing window instead of creating a new one
+    if let Some(window) = app.get_webview_window(&window_label) {
+        return Ok(focus_existing_window(&window, &size)?);
+    }
+
+    // Fail right away instead of opening a window whose session can't start
+    if let Some(project_path) = params.project_path.as_deref() {
+        if !params.skip_path_check {
+            validate_project_path(project_path).map_err(AppError::InvalidParam)?;
+        }
+    }
+    if let Some(ref extra_params) = params.extra_params {
+        validate_extra_params(extra_params).map_err(AppError::InvalidParam)?;
+    }
+
+    // Released on every return path, whether the build succeeds or not
+    let Some(_in_flight) = InFlightGuard::acquire(&window_label) else {
+        window_log!(
+            info,
+            window_label,
+            "Session window is already being created"
+        );
+        return Ok(WindowCreationResult::from_measured(
+            window_label,
+            None,
+            size.width,
+            size.height,
+        ));
+    };
+
+    // A creation that finished just before the guard was taken already built it
+    if let Some(window) = app.get_webview_window(&window_label) {
+        return Ok(focus_existing_window(&window, &size)?);
+    }
+
+    // Checked with the label claimed, so concurrent creations see each other
+    limits::check_window_limit(
+        app,
+        &params.tab_id,
+        &window_label,
+        &InFlightGuard::held_labels(),
+    )?;
+
+    let url = session_window_url(params);
+
+    window_log!(
+        info,
+        window_label,
+        "Creating session window with URL: {}",
+        url
+    );
+
+    let deferred_prompt = params
+        .initial_prompt
+        .clone()
+        .filter(|_| url_prompt(params).is_none());
+    let deferred_project_path = params
+        .project_path
+        .clone()
+        .filter(|_| url_project_path(params).is_none());
+    if let Some(ref project_path) = deferred_project_path {
+        window_log!(
+            info,
+            window_label,
+            "Project path is too long for the URL ({} chars), sending it as {}",
+            project_path.len(),
+            PROJECT_PATH_EVENT
+        );
+    }
+
+    // Reopen where the tab's window was last closed, unless that spot is off-screen;
+    // otherwise cascade from the center of the hinted monitor or the main window's
+    let project_path = params.project_path.as_deref();
+    let saved = geometry::saved_geometry_for_tab(app, project_path, &params.tab_id);
+    let (requested_width, requested_height) = saved
+        .map(|saved| (saved.width, saved.height))
+        .unwrap_or((size.width, size.height));
+    let state = params
+        .initial_state
+        .or_else(|| geometry::saved_state_for_tab(app, project_path, &params.tab_id))
+        .unwrap_or_default();
+    // Worked out once, so a retried build doesn't cascade a step further
+    let cascade = if saved.is_none() && !state.covers_screen() {
+        geometry::cascaded_position(app, params.monitor_hint.as_deref(), size.width, size.height)
+    } else {
+        None
+    };
+
+    // Painted in the theme's color while the webview catches up on resizes
+    let background = background::new_window_background(app);
+
+    // A builder is used up by `build`, so each attempt gets a fresh one
+    let new_builder = || {
+        // Frameless with the frontend's title bar, unless native decorations were asked for
+        let mut builder =
+            WebviewWindowBuilder::new(app, &window_label, WebviewUrl::App(url.clone().into()))
+                .title(&params.title)
+                .min_inner_size(size.min_width, size.min_height)
+                .resizable(true)
+                .maximizable(true)
+                .minimizable(true)
+                .visible(false)
+                .decorations(native_decorations(params));
+
+        if let Some(background) = background {
+            builder = builder.background_color(background);
+        }
+
+        if size.max_width.is_some() || size.max_height.is_some() {
+            builder = builder.max_inner_size(
+                size.max_width.unwrap_or(constraints::UNBOUNDED_SIZE),
+                size.max_height.unwrap_or(constraints::UNBOUNDED_SIZE),
+            );
+        }
+
+        // Hand over what didn't fit into the URL once the page has loaded;
+        // the project path goes first since the prompt runs in that project
+        let deferred: Vec<(&'static str, String)> = [
+            (PROJECT_PATH_EVENT, deferred_project_path.clone()),
+            (INITIAL_PROMPT_EVENT, deferred_prompt.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(event, value)| value.map(|value| (event, value)))
+        .collect();
+        if !deferred.is_empty() {
+            let pending = Mutex::new(deferred);
+            builder = builder.on_page_load(move |window, payload| {
+                if payload.event() != PageLoadEvent::Finished {
+                    return;
+                }
+                let Some(events) = pending.lock().ok().map(|mut p| std::mem::take(&mut *p)) else {
+                    return;
+                };
+                for (event, value) in events {
+                    if let Err(e) =
+                        window.emit_to(EventTarget::webview_window(window.label()), event, value)
+                    {
+                        window_log!(warn, window.label(), "Failed to send {}: {}", event, e);
+                    }
+                }
+            });
+        }
+
+        match saved {
+            Some(saved) => builder
+                .position(saved.x, saved.y)
+                .inner_size(saved.width, saved.height),
+            // Maximized and fullscreen windows fill the monitor anyway
+            None if state.covers_screen() => builder.inner_size(size.width, size.height),
+            None => {
+                let builder = builder.inner_size(size.width, size.height);
+                match cascade {
+                    Some((x, y)) => builder.position(x, y),
+                    None => builder.center(),
+                }
+            }
+        }
+    };
+
+    // State saved by a soft close of the tab's last window, unless the caller brings its own
+    let saved_state = params
+        .session_snapshot
+        .is_none()
+        .then(|| softclose::load_saved_state(app, &params.tab_id))
+        .flatten();
+    let registration = PendingRegistration::register(
+        app.try_state::<WindowRegistry>().map(|state| state.inner()),
+        WindowMeta {
+            session_snapshot: params.session_snapshot.clone().or(saved_state.clone()),
+            ..session_window_meta(&window_label, params)
+        },
+    );
+    let window = match retry_transient_build(
+        &window_label,
+        || new_builder().build().map_err(BuildError::from),
+        tokio::time::sleep,
+    )
+    .await
+    {
+        Ok(window) => window,
+        // Created behind our back (e.g. outside `create_session_window`)
+        Err(BuildError::LabelTaken) => {
+            window_log!(
+                info,
+                window_label,
+                "Session window already exists, focusing it"
+            );
+            drop(registration);
+            return match app.get_webview_window(&window_label) {
+                Some(window) => Ok(focus_existing_window(&window, &size)?),
+                // Closed again before we got to it
+                None => Ok(WindowCreationResult::from_measured(
+                    window_label,
+                    None,
+                    size.width,
+                    size.height,
+                )),
+            };
+        }
+        Err(BuildError::Transient(e) | BuildError::Other(e)) => return Err(AppError::Build(e)),
+    };
+    registration.commit();
+    if saved_state.is_some() {
+        softclose::forget_saved_state(app, &params.tab_id);
+    }
+    eventlog::record_window_event(app, eventlog::WindowEventKind::Created, &window_label);
+
+    // Keep the startup-restore snapshot current so a crash still leaves it usable
+    restore::remember_open_window(app, params);
+    // The OS owns a native title bar's colors
+    if !native_decorations(params) {
+        titlebar::apply_current_theme(app, &window);
+    }
+
+    zoom::apply_saved_zoom(app, &window);
+
+    // Persist state on close and keep the main window informed
+    lifecycle::attach_lifecycle_handlers(app, &window, &params.tab_id);
+
+    // Built hidden; shown (in `state`) once the frontend has painted
+    reveal::reveal_when_ready(&window, state);
+
+    // Some platforms report 0,0 while the window is hidden; the requested size covers that
+    let measured = geometry::capture_geometry(&window).ok();
+
+    window_log!(info, window_label, "Session window created successfully");
+
+    Ok(WindowCreationResult::from_measured(
+        window_label,
+        measured,
+        requested_width,
+        requested_height,
+    ))
+}
+
+/// Closes a session window and drops it from the registry
+///
+/// Shared by `close_session_window` and `close_session_window_saving`.
+fn close_and_unregister(app: &AppHandle, window: &WebviewWindow) -> Result<(), String> {
+    let window_label = window.label();
+    lifecycle::close_programmatically(window)?;
+    // A busy window only asks for confirmation, so it stays registered
+    eventlog::unregister_closed(app, window_label);
+    window_log!(info, window_label, "Session window closed");
+    Ok(())
+}
+
+/// Closes an independent session window
+///
+/// Drafts and scroll position are lost; `close_session_window_saving` keeps
+/// them for the next time the tab is detached.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window to close
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn close_session_window(app: AppHandle, window_label: String) -> Result<(), AppError> {
+    reported!(app, "close_session_window", {
+        let _scope = logging::LogScope::new("close_session_window", &window_label);
+        if let Some(window) = app.get_webview_window(&window_label) {
+            close_and_unregister(&app, &window)?;
+            Ok(())
+        } else {
+            Err(window_not_found(&window_label))
+        }
+    })
+}
+
+/// Gets a list of all open session windows
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+///
+/// # Returns
+/// * `Result<Vec<String>, AppError>` - List of window labels
+#[tauri::command]
+pub async fn list_session_windows(app: AppHandle) -> Result<Vec<String>, AppError> {
+    reported!(app, "list_session_windows", {
+        let windows: Vec<String> = app
+            .webview_windows()
+            .keys()
+            .filter(|label| is_session_window_label(label))
+            .cloned()
+            .collect();
+
+        Ok(windows)
+    })
+}
+
+/// Session window as listed by `list_session_windows_detailed`
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionWindowView {
+    /// Registry metadata (label, tab, engine, busy, zoom, ...)
+    #[serde(flatten)]
+    pub meta: WindowMeta,
+    /// Current window title
+    pub title: String,
+    /// Whether the window has keyboard focus
+    pub focused: bool,
+    /// Current position and size, if it could be read
+    pub geometry: Option<geometry::WindowGeometry>,
+}
+
+/// Gets all open session windows with their metadata
+///
+/// Everything a window switcher needs in one call; windows the registry
+/// doesn't know about are left out.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+///
+/// # Returns
+/// * `Result<Vec<SessionWindowView>, AppError>` - Windows sorted by label
+#[tauri::command]
+pub async fn list_session_windows_detailed(
+    app: AppHandle,
+) -> Result<Vec<SessionWindowView>, AppError> {
+    reported!(app, "list_session_windows_detailed", {
+        let mut windows: Vec<SessionWindowView> = app
+            .webview_windows()
+            .into_iter()
+            .filter(|(label, _)| is_session_window_label(label))
+            .filter_map(|(label, window)| {
+                let meta =
+                    registry::with_registry(&app, |registry| registry.get(&label)).flatten()?;
+                Some(SessionWindowView {
+                    meta,
+                    title: window.title().unwrap_or_default(),
+                    focused: window.is_focused().unwrap_or(false),
+                    geometry: geometry::capture_geometry(&window).ok(),
+                })
+            })
+            .collect();
+        windows.sort_by(|a, b| a.meta.window_label.cmp(&b.meta.window_label));
+
+        Ok(windows)
+    })
+}
+
+/// Focuses a specific session window
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window to focus
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn focus_session_window(app: AppHandle, window_label: String) -> Result<(), AppError> {
+    reported!(app, "focus_session_window", {
+        if let Some(window) = app.get_webview_window(&window_label) {
+            focus::bring_to_front(&window)?;
+            Ok(())
+        } else {
+            Err(window_not_found(&window_label))
+        }
+    })
+}
+
+/// Checks whether a window exists, without focusing or otherwise touching it
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+///
+/// # Returns
+/// * `Result<bool, AppError>` - Whether a window with this label is open
+#[tauri::command]
+pub async fn session_window_exists(app: AppHandle, window_label: String) -> Result<bool, AppError> {
+    reported!(app, "session_window_exists", {
+        Ok(app.get_webview_window(&window_label).is_some())
+    })
+}
+
+/// Trims a window title and rejects blank ones
+fn normalize_window_title(title: &str) -> Result<String, String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Invalid title: must not be empty".to_string());
+    }
+    Ok(title.to_string())
+}
+
+/// Payload of the `title-changed` event
+#[derive(Debug, Clone, Serialize)]
+pub struct TitleChangedPayload {
+    /// Label of the renamed window
+    pub window_label: String,
+    /// The new title
+    pub title: String,
+}
+
+/// Renames a session window after creation
+///
+/// Emits `title-changed` to the window and to the main window so any UI that
+/// mirrors the title updates too.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `title` - The new title (surrounding whitespace is trimmed)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_session_window_title(
+    app: AppHandle,
+    window_label: String,
+    title: String,
+) -> Result<(), AppError> {
+    reported!(app, "set_session_window_title", {
+        let title = normalize_window_title(&title).map_err(AppError::InvalidParam)?;
+
+        if let Some(window) = app.get_webview_window(&window_label) {
+            window
+                .set_title(&title)
+                .map_err(|e| format!("Failed to set window title: {}", e))?;
+
+            // Keep the restored window's title current as well
+            if let Some(tab_id) = tab_id_from_label(&window_label) {
+                restore::rename_open_window(&app, tab_id, &title);
+            }
+
+            let payload = TitleChangedPayload {
+                window_label,
+                title,
+            };
+            app.emit_filter("title-changed", &payload, |target| match target {
+                EventTarget::WebviewWindow { label } => {
+                    label == &payload.window_label || label == "main"
+                }
+                _ => false,
+            })
+            .map_err(|e| AppError::Emit(format!("Failed to emit event: {}", e)))?;
+            Ok(())
+        } else {
+            Err(window_not_found(&window_label))
+        }
+    })
+}
+
+/// Pins a window above all other windows, or unpins it
+///
+/// Emits `always-on-top-changed` with the new state to the window so its
+/// title bar can show a pin indicator.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `on_top` - Whether the window should stay on top
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_always_on_top(
+    app: AppHandle,
+    window_label: String,
+    on_top: bool,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_always_on_top", {
+        if let Some(window) = app.get_webview_window(&window_label) {
+            window
+                .set_always_on_top(on_top)
+                .map_err(|e| format!("Failed to set always on top: {}", e))?;
+            window
+                .emit_to(
+                    EventTarget::webview_window(&window_label),
+                    "always-on-top-changed",
+                    on_top,
+                )
+                .map_err(|e| AppError::Emit(format!("Failed to emit event: {}", e)))?;
+            Ok(())
+        } else {
+            Err(window_not_found(&window_label))
+        }
+    })
+}
+
+/// Toggles fullscreen for a window
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+///
+/// # Returns
+/// * `Result<bool, AppError>` - Whether the window is now fullscreen, or an error message
+#[tauri::command]
+pub async fn toggle_window_fullscreen(
+    app: AppHandle,
+    window_label: String,
+) -> Result<bool, AppError> {
+    reported!(app, "toggle_window_fullscreen", {
+        if let Some(window) = app.get_webview_window(&window_label) {
+            let fullscreen = !window
+                .is_fullscreen()
+                .map_err(|e| format!("Failed to get fullscreen state: {}", e))?;
+            window
+                .set_fullscreen(fullscreen)
+                .map_err(|e| format!("Failed to set fullscreen: {}", e))?;
+            Ok(fullscreen)
+        } else {
+            Err(window_not_found(&window_label))
+        }
+    })
+}
+
+/// Toggles maximize for a window
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+///
+/// # Returns
+/// * `Result<bool, AppError>` - Whether the window is now maximized, or an error message
+#[tauri::command]
+pub async fn toggle_window_maximize(
+    app: AppHandle,
+    window_label: String,
+) -> Result<bool, AppError> {
+    reported!(app, "toggle_window_maximize", {
+        if let Some(window) = app.get_webview_window(&window_label) {
+            let maximized = window
+                .is_maximized()
+                .map_err(|e| format!("Failed to get maximized state: {}", e))?;
+            if maximized {
+                window
+                    .unmaximize()
+                    .map_err(|e| format!("Failed to unmaximize window: {}", e))?;
+            } else {
+                window
+                    .maximize()
+                    .map_err(|e| format!("Failed to maximize window: {}", e))?;
+            }
+            Ok(!maximized)
+        } else {
+            Err(window_not_found(&window_label))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params_with_session(session_id: &str) -> CreateSessionWindowParams {
+        CreateSessionWindowParams {
+            tab_id: "tab-1".to_string(),
+            session_id: Some(session_id.to_string()),
+            project_path: Some("C:\\Users\\me\\my project".to_string()),
+            title: "Session".to_string(),
+            engine: Some(Engine::Codex),
+            ..Default::default()
+        }
+    }
+
+    /// Parses the query the same way the frontend's URLSearchParams does
+    fn parse_query(url: &str) -> HashMap<String, String> {
+        tauri::Url::parse(&format!("http://localhost{}", url))
+            .unwrap()
+            .query_pairs()
+            .into_owned()
+            .collect()
+    }
+
+    #[test]
+    fn test_window_label_for_tab_valid() {
+        assert_eq!(
+            window_label_for_workspace_tab(None, "tab-1_A").unwrap(),
+            "session-window-tab-1_A"
+        );
+        assert_eq!(
+            window_label_for_workspace_tab(None, "1700000000000-abc").unwrap(),
+            "session-window-1700000000000-abc"
+        );
+    }
+
+    #[test]
+    fn test_window_label_for_workspace_tab() {
+        assert_eq!(
+            window_label_for_workspace_tab(Some("work-1"), "1700000000000-abc").unwrap(),
+            "session-window-work-1:1700000000000-abc"
+        );
+        assert!(window_label_for_workspace_tab(Some(""), "tab-1").is_err());
+        assert!(window_label_for_workspace_tab(Some("a:b"), "tab-1").is_err());
+        assert!(window_label_for_workspace_tab(Some("ws"), "").is_err());
+    }
+
+    #[test]
+    fn test_parse_session_window_label_round_trips_hyphenated_ids() {
+        for (workspace, tab_id) in [
+            (None, "tab-1"),
+            (None, "1700000000000-abc-def"),
+            (Some("work-space"), "tab-1-2"),
+            (Some("ws_1"), "a"),
+        ] {
+            let label = window_label_for_workspace_tab(workspace, tab_id).unwrap();
+            assert!(is_session_window_label(&label));
+            assert_eq!(
+                parse_session_window_label(&label),
+                Some(SessionWindowLabel { workspace, tab_id })
+            );
+            assert_eq!(tab_id_from_label(&label), Some(tab_id));
+        }
+    }
+
+    #[test]
+    fn test_parse_session_window_label_rejects_other_labels() {
+        assert!(!is_session_window_label("main"));
+        assert_eq!(parse_session_window_label("main"), None);
+        assert_eq!(parse_session_window_label("session-window-"), None);
+        assert_eq!(parse_session_window_label("session-window-ws:"), None);
+        assert_eq!(parse_session_window_label("session-window-:tab"), None);
+    }
+
+    #[test]
+    fn test_window_label_for_tab_rejects_malformed() {
+        assert!(window_label_for_workspace_tab(None, "").is_err());
+        assert!(window_label_for_workspace_tab(None, "has space").is_err());
+        assert!(window_label_for_workspace_tab(None, "a/b").is_err());
+        assert!(window_label_for_workspace_tab(None, "../main").is_err());
+        assert!(window_label_for_workspace_tab(None, "tab:1").is_err());
+        assert!(window_label_for_workspace_tab(None, "标签").is_err());
+    }
+
+    #[test]
+    fn test_session_window_url_round_trips_special_characters() {
+        let url = session_window_url(&params_with_session("a&b=c"));
+        let query = parse_query(&url);
+
+        assert_eq!(query.get("window").map(String::as_str), Some("session"));
+        assert_eq!(query.get("tab_id").map(String::as_str), Some("tab-1"));
+        assert_eq!(query.get("session_id").map(String::as_str), Some("a&b=c"));
+        assert_eq!(
+            query.get("project_path").map(String::as_str),
+            Some("C:\\Users\\me\\my project")
+        );
+        assert_eq!(query.get("engine").map(String::as_str), Some("codex"));
+        assert_eq!(query.len(), 5);
+    }
+
+    #[test]
+    fn test_build_url_round_trips_any_value() {
+        let values = [
+            "plain",
+            "a&b",
+            "k=v",
+            "#fragment",
+            "two words",
+            "a+b",
+            "%20",
+            "&=# ?/",
+            "中文 & émoji 🚀",
+            "",
+        ];
+
+        for value in values {
+            for key in ["tab_id", "odd key&=#"] {
+                let query = parse_query(&build_url([("window", "session"), (key, value)]));
+                assert_eq!(
+                    query.get(key).map(String::as_str),
+                    Some(value),
+                    "{:?}",
+                    value
+                );
+                assert_eq!(query.get("window").map(String::as_str), Some("session"));
+                assert_eq!(query.len(), 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_extra_params_round_trip_through_url() {
+        let params = CreateSessionWindowParams {
+            extra_params: Some(HashMap::from([
+                ("theme".to_string(), "high contrast".to_string()),
+                ("layout".to_string(), "split&wide".to_string()),
+                ("readonly".to_string(), "1".to_string()),
+            ])),
+            ..params_with_session("s1")
+        };
+        assert_eq!(
+            validate_extra_params(params.extra_params.as_ref().unwrap()),
+            Ok(())
+        );
+
+        let query = parse_query(&session_window_url(&params));
+        assert_eq!(
+            query.get("theme").map(String::as_str),
+            Some("high contrast")
+        );
+        assert_eq!(query.get("layout").map(String::as_str), Some("split&wide"));
+        assert_eq!(query.get("readonly").map(String::as_str), Some("1"));
+        assert_eq!(query.get("tab_id").map(String::as_str), Some("tab-1"));
+    }
+
+    #[test]
+    fn test_extra_params_reject_reserved_keys() {
+        let extra_params = HashMap::from([
+            ("window".to_string(), "main".to_string()),
+            ("tab_id".to_string(), "other".to_string()),
+            ("theme".to_string(), "dark".to_string()),
+        ]);
+        assert_eq!(
+            validate_extra_params(&extra_params),
+            Err("Invalid extra_params: reserved keys tab_id, window".to_string())
+        );
+    }
+
+    #[test]
+    fn test_session_info_from_url_round_trips() {
+        let url = session_window_url(&params_with_session("a&b=c"));
+        let url = tauri::Url::parse(&format!("http://localhost{}", url)).unwrap();
+
+        let info = session_info_from_url("session-window-tab-1", &url).unwrap();
+        assert_eq!(
+            info,
+            SessionWindowInfo {
+                window_label: "session-window-tab-1".to_string(),
+                tab_id: "tab-1".to_string(),
+                session_id: Some("a&b=c".to_string()),
+                project_path: Some("C:\\Users\\me\\my project".to_string()),
+                engine: Some(Engine::Codex),
+            }
+        );
+    }
+
+    #[test]
+    fn test_session_info_from_url_requires_tab_id() {
+        let url = tauri::Url::parse("http://localhost/?window=session").unwrap();
+        assert!(session_info_from_url("session-window-x", &url).is_err());
+    }
+
+    #[test]
+    fn test_engine_accepts_known_values() {
+        assert_eq!("claude".parse::<Engine>(), Ok(Engine::Claude));
+        assert_eq!("codex".parse::<Engine>(), Ok(Engine::Codex));
+        assert_eq!("gemini".parse::<Engine>(), Ok(Engine::Gemini));
+
+        let params: CreateSessionWindowParams = serde_json::from_str(
+            r#"{"tab_id":"t","session_id":null,"project_path":null,"title":"T","engine":"claude"}"#,
+        )
+        .unwrap();
+        assert_eq!(params.engine, Some(Engine::Claude));
+        assert_eq!(serde_json::to_string(&Engine::Codex).unwrap(), r#""codex""#);
+    }
+
+    #[test]
+    fn test_engine_none_is_allowed() {
+        let params: CreateSessionWindowParams =
+            serde_json::from_str(r#"{"tab_id":"t","title":"T","engine":null}"#).unwrap();
+        assert_eq!(params.engine, None);
+    }
+
+    #[test]
+    fn test_engine_rejects_unknown_values() {
+        assert_eq!(
+            "gpt5".parse::<Engine>(),
+            Err("unsupported engine: gpt5".to_string())
+        );
+        assert!("Claude".parse::<Engine>().is_err());
+
+        let err = serde_json::from_str::<CreateSessionWindowParams>(
+            r#"{"tab_id":"t","title":"T","engine":"gpt5"}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unsupported engine: gpt5"));
+    }
+
+    #[test]
+    fn test_resolve_window_size_defaults() {
+        assert_eq!(
+            resolve_window_size(&CreateSessionWindowParams::default()).unwrap(),
+            SessionWindowSize {
+                width: 1000.0,
+                height: 700.0,
+                min_width: 600.0,
+                min_height: 400.0,
+                max_width: None,
+                max_height: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_window_size_clamps_to_maximum() {
+        let params = CreateSessionWindowParams {
+            width: Some(3000.0),
+            max_width: Some(1800.0),
+            ..Default::default()
+        };
+        let size = resolve_window_size(&params).unwrap();
+        assert_eq!((size.width, size.height), (1800.0, 700.0));
+        assert_eq!((size.max_width, size.max_height), (Some(1800.0), None));
+
+        let params = CreateSessionWindowParams {
+            min_height: Some(500.0),
+            max_height: Some(450.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_window_size(&params),
+            Err(
+                "Invalid size constraints: min_height 500 is larger than max_height 450"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_resolve_window_size_clamps_to_minimum() {
+        let params = CreateSessionWindowParams {
+            width: Some(420.0),
+            height: Some(1200.0),
+            min_width: Some(480.0),
+            ..Default::default()
+        };
+
+        let size = resolve_window_size(&params).unwrap();
+        assert_eq!((size.width, size.height), (480.0, 1200.0));
+        assert_eq!((size.min_width, size.min_height), (480.0, 400.0));
+    }
+
+    #[test]
+    fn test_resolve_window_size_rejects_non_positive() {
+        for params in [
+            CreateSessionWindowParams {
+                width: Some(0.0),
+                ..Default::default()
+            },
+            CreateSessionWindowParams {
+                height: Some(-100.0),
+                ..Default::default()
+            },
+            CreateSessionWindowParams {
+                min_width: Some(f64::NAN),
+                ..Default::default()
+            },
+        ] {
+            assert!(resolve_window_size(&params).is_err());
+        }
+    }
+
+    #[test]
+    fn test_creation_result_reports_requested_size() {
+        let params = CreateSessionWindowParams {
+            width: Some(640.0),
+            height: Some(1100.0),
+            ..Default::default()
+        };
+        let size = resolve_window_size(&params).unwrap();
+
+        // What the window reports once shown
+        let measured = geometry::WindowGeometry {
+            x: 200.0,
+            y: 50.0,
+            width: 640.0,
+            height: 1100.0,
+        };
+        let result = WindowCreationResult::from_measured(
+            "session-window-t".to_string(),
+            Some(measured),
+            size.width,
+            size.height,
+        );
+        assert_eq!((result.x, result.y), (200.0, 50.0));
+        assert_eq!((result.width, result.height), (640.0, 1100.0));
+
+        // A window that still reports an empty size falls back to the request
+        let result = WindowCreationResult::from_measured(
+            "session-window-t".to_string(),
+            Some(geometry::WindowGeometry {
+                width: 0.0,
+                height: 0.0,
+                ..measured
+            }),
+            size.width,
+            size.height,
+        );
+        assert_eq!((result.width, result.height), (640.0, 1100.0));
+    }
+
+    #[test]
+    fn test_resolved_params_echo_the_canonical_project_path() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("project")).unwrap();
+        let input = format!("{}/project/../project/.", dir.path().display());
+        let params = CreateSessionWindowParams {
+            tab_id: "t".to_string(),
+            title: "T".to_string(),
+            project_path: Some(input.clone()),
+            engine: Some(Engine::Gemini),
+            ..Default::default()
+        };
+
+        let resolved = resolved_params(&params).unwrap();
+        let canonical = resolved.project_path.clone().unwrap();
+        assert_ne!(canonical, input);
+        assert_eq!(
+            std::path::Path::new(&canonical),
+            std::fs::canonicalize(dir.path().join("project")).unwrap()
+        );
+        assert_eq!(resolved.engine, Some(Engine::Gemini));
+        assert_eq!(
+            (resolved.width, resolved.height),
+            (Some(1000.0), Some(700.0))
+        );
+        assert_eq!(resolved.decorations, Some(false));
+
+        let result = WindowCreationResult::from_measured(
+            "session-window-t".to_string(),
+            None,
+            1000.0,
+            700.0,
+        )
+        .with_params(resolved);
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["params"]["project_path"], canonical.as_str());
+        assert_eq!(value["params"]["engine"], "gemini");
+    }
+
+    #[test]
+    fn test_in_flight_guard_lets_one_creation_through() {
+        let label = "session-window-race-test";
+        let barrier = std::sync::Arc::new(std::sync::Barrier::new(2));
+
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    let guard = InFlightGuard::acquire(label);
+                    let acquired = guard.is_some();
+                    // Hold the claim until both threads have tried
+                    barrier.wait();
+                    acquired
+                })
+            })
+            .collect();
+
+        let acquired: Vec<bool> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(acquired.iter().filter(|a| **a).count(), 1);
+
+        // Both guards are gone, so the label can be claimed again
+        assert!(InFlightGuard::acquire(label).is_some());
+    }
+
+    #[test]
+    fn test_session_window_url_carries_short_initial_prompt() {
+        let params = CreateSessionWindowParams {
+            tab_id: "tab-3".to_string(),
+            initial_prompt: Some("Fix the tests & rerun".to_string()),
+            ..Default::default()
+        };
+
+        let query = parse_query(&session_window_url(&params));
+        assert_eq!(
+            query.get("initial_prompt").map(String::as_str),
+            Some("Fix the tests & rerun")
+        );
+        assert!(!query.contains_key("initial_prompt_event"));
+    }
+
+    #[test]
+    fn test_session_window_url_defers_long_initial_prompt() {
+        let params = CreateSessionWindowParams {
+            tab_id: "tab-3".to_string(),
+            initial_prompt: Some("x".repeat(MAX_URL_PROMPT_LEN + 1)),
+            ..Default::default()
+        };
+
+        assert_eq!(url_prompt(&params), None);
+        let query = parse_query(&session_window_url(&params));
+        assert!(!query.contains_key("initial_prompt"));
+        assert_eq!(
+            query.get("initial_prompt_event").map(String::as_str),
+            Some("1")
+        );
+    }
+
+    #[test]
+    fn test_session_window_url_defers_long_project_path() {
+        let project_path = format!("/repo/{}", "packages/nested/".repeat(40));
+        let params = CreateSessionWindowParams {
+            tab_id: "tab-3".to_string(),
+            project_path: Some(project_path.clone()),
+            ..Default::default()
+        };
+
+        assert_eq!(url_project_path(&params), None);
+        let query = parse_query(&session_window_url(&params));
+        assert!(!query.contains_key("project_path"));
+        assert_eq!(
+            query.get("project_path_event").map(String::as_str),
+            Some("1")
+        );
+        assert_eq!(
+            session_window_meta("session-window-tab-3", &params).project_path,
+            Some(project_path)
+        );
+    }
+
+    #[test]
+    fn test_builder_gets_requested_decorations() {
+        let frameless = CreateSessionWindowParams {
+            tab_id: "tab-3".to_string(),
+            ..Default::default()
+        };
+        assert!(!native_decorations(&frameless));
+        assert!(!parse_query(&session_window_url(&frameless)).contains_key("decorated"));
+        assert!(!session_window_meta("session-window-tab-3", &frameless).decorated);
+
+        let native = CreateSessionWindowParams {
+            decorations: Some(true),
+            ..frameless
+        };
+        assert!(native_decorations(&native));
+        assert_eq!(
+            parse_query(&session_window_url(&native))
+                .get("decorated")
+                .map(String::as_str),
+            Some("1")
+        );
+        assert!(session_window_meta("session-window-tab-3", &native).decorated);
+    }
+
+    #[test]
+    fn test_normalize_window_title() {
+        assert_eq!(
+            normalize_window_title("  Fix login bug  ").unwrap(),
+            "Fix login bug"
+        );
+        assert!(normalize_window_title("").is_err());
+        assert!(normalize_window_title(" \t\n ").is_err());
+    }
+
+    #[test]
+    fn test_session_window_url_omits_missing_values() {
+        let params = CreateSessionWindowParams {
+            tab_id: "tab-2".to_string(),
+            session_id: None,
+            project_path: None,
+            title: "New".to_string(),
+            engine: None,
+            ..Default::default()
+        };
+
+        assert_eq!(session_window_url(&params), "/?window=session&tab_id=tab-2");
+    }
+
+    #[test]
+    fn test_session_window_view_flattens_meta() {
+        let view = SessionWindowView {
+            meta: WindowMeta::new(
+                "session-window-a",
+                "a",
+                Some("s1".to_string()),
+                None,
+                Some(Engine::Gemini),
+            ),
+            title: "Project A".to_string(),
+            focused: true,
+            geometry: None,
+        };
+
+        let value = serde_json::to_value(view).unwrap();
+        assert_eq!(value["window_label"], "session-window-a");
+        assert_eq!(value["tab_id"], "a");
+        assert_eq!(value["engine"], "gemini");
+        assert_eq!(value["title"], "Project A");
+        assert_eq!(value["focused"], true);
+        assert!(value["geometry"].is_null());
+        assert!(value.get("meta").is_none());
+    }
+
+    #[test]
+    fn test_validate_project_path() {
+        let dir = std::env::temp_dir().join(format!("window-path-check-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("file.txt");
+        std::fs::write(&file, "not a dir").unwrap();
+        let missing = dir.join("missing");
+
+        assert_eq!(validate_project_path(dir.to_str().unwrap()), Ok(()));
+        assert_eq!(
+            validate_project_path(missing.to_str().unwrap()),
+            Err(format!(
+                "project path does not exist: {}",
+                missing.to_str().unwrap()
+            ))
+        );
+        assert_eq!(
+            validate_project_path(file.to_str().unwrap()),
+            Err(format!(
+                "project path is not a directory: {}",
+                file.to_str().unwrap()
+            ))
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_duplicate_label_build_error_leaves_no_registry_entry() {
+        let registry = WindowRegistry::default();
+        let label = "session-window-dup";
+
+        let registration = PendingRegistration::register(
+            Some(&registry),
+            WindowMeta::new(label, "dup", None, None, Some(Engine::Claude)),
+        );
+        assert!(registry.get(label).is_some());
+
+        let error = BuildError::from(tauri::Error::WebviewLabelAlreadyExists(label.to_string()));
+        assert_eq!(error, BuildError::LabelTaken);
+        drop(registration);
+        assert_eq!(registry.get(label), None);
+
+        // A successful build keeps the entry
+        PendingRegistration::register(
+            Some(&registry),
+            WindowMeta::new(label, "dup", None, None, None),
+        )
+        .commit();
+        assert!(registry.get(label).is_some());
+    }
+
+    #[test]
+    fn test_label_taken_keeps_the_live_window_entry() {
+        let registry = WindowRegistry::default();
+        let label = "session-window-live";
+        // Registered by the creation that built the live window
+        registry.register(WindowMeta::new(label, "live", None, None, None));
+        registry.update(label, |meta| meta.busy = true);
+
+        let registration = PendingRegistration::register(
+            Some(&registry),
+            WindowMeta::new(label, "live", None, None, Some(Engine::Codex)),
+        );
+        assert_eq!(
+            BuildError::from(tauri::Error::WebviewLabelAlreadyExists(label.to_string())),
+            BuildError::LabelTaken
+        );
+        drop(registration);
+
+        let meta = registry.get(label).expect("live window stays registered");
+        assert!(meta.busy);
+        assert_eq!(meta.engine, None);
+    }
+
+    #[test]
+    fn test_other_build_errors_keep_their_message() {
+        assert_eq!(
+            BuildError::from(tauri::Error::WindowNotFound),
+            BuildError::Other("Failed to create window: window not found".to_string())
+        );
+        assert!(matches!(
+            BuildError::from_message(
+                "Failed to create window: failed to create webview: GPU process exited".to_string()
+            ),
+            BuildError::Transient(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_transient_build_failure_is_retried_until_it_succeeds() {
+        let mut attempts = 0;
+        let mut waits = Vec::new();
+        let result = retry_transient_build(
+            "session-window-a",
+            || {
+                attempts += 1;
+                if attempts < 3 {
+                    Err(BuildError::Transient("GPU process exited".to_string()))
+                } else {
+                    Ok("window")
+                }
+            },
+            |delay| {
+                waits.push(delay);
+                async {}
+            },
+        )
+        .await;
+
+        assert_eq!(result, Ok("window"));
+        assert_eq!(attempts, 3);
+        assert_eq!(waits, BUILD_RETRY_BACKOFF[..2].to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_build_retries_give_up_and_permanent_errors_fail_fast() {
+        let mut attempts = 0;
+        let result: Result<(), _> = retry_transient_build(
+            "session-window-a",
+            || {
+                attempts += 1;
+                Err(BuildError::Transient("GPU process exited".to_string()))
+            },
+            |_| async {},
+        )
+        .await;
+        assert!(matches!(result, Err(BuildError::Transient(_))));
+        // The first attempt plus one retry per backoff step
+        assert_eq!(attempts, 1 + BUILD_RETRY_BACKOFF.len());
+
+        let mut attempts = 0;
+        let result: Result<(), _> = retry_transient_build(
+            "session-window-a",
+            || {
+                attempts += 1;
+                Err(BuildError::LabelTaken)
+            },
+            |_| async { panic!("permanent errors are not retried") },
+        )
+        .await;
+        assert_eq!(result, Err(BuildError::LabelTaken));
+        assert_eq!(attempts, 1);
+    }
+}
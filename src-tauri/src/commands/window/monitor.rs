@@ -0,0 +1,439 @@
+/**
+ * Monitors
+ *
+ * Lists the connected displays for "move to display" menus, and sends a
+ * window to another display ("send to display 2"): it is centered in that
+ * monitor's work area and shrunk if it wouldn't fit there.
+ *
+ * Positions can also be read and set relative to a monitor, for layouts
+ * that should survive the monitors being rearranged.
+ */
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, LogicalPosition, LogicalSize, Manager};
+
+use super::error::{window_not_found, AppError};
+use super::geometry::{self, WindowGeometry};
+
+/// Event broadcast when a window was sent to another monitor
+const WINDOW_MOVED_EVENT: &str = "window-moved";
+
+/// Payload of the `window-moved` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct WindowMovedPayload {
+    window_label: String,
+    monitor_index: usize,
+    /// Name of the target monitor, if the platform reports one
+    monitor_name: Option<String>,
+    geometry: WindowGeometry,
+}
+
+/// A connected monitor as listed by `list_monitors`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonitorInfo {
+    /// Position in the list, as taken by `move_window_to_monitor`
+    pub index: usize,
+    /// Name of the monitor, if the platform reports one
+    pub name: Option<String>,
+    /// Full bounds in logical pixels
+    pub bounds: WindowGeometry,
+    /// Bounds without the taskbar, dock and menu bar, in logical pixels
+    pub work_area: WindowGeometry,
+    pub scale_factor: f64,
+    pub is_primary: bool,
+    /// Whether the main window is on this monitor
+    pub has_main_window: bool,
+}
+
+/// Flags the primary monitor and the one holding the main window.
+///
+/// Monitors are matched by their bounds, which no two connected monitors
+/// share; names aren't reported on every platform.
+fn mark_monitors(
+    monitors: &mut [MonitorInfo],
+    primary: Option<&WindowGeometry>,
+    main: Option<&WindowGeometry>,
+) {
+    for monitor in monitors {
+        monitor.is_primary = primary == Some(&monitor.bounds);
+        monitor.has_main_window = main == Some(&monitor.bounds);
+    }
+}
+
+/// Lists the connected monitors
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+///
+/// # Returns
+/// * `Result<Vec<MonitorInfo>, AppError>` - The monitors or an error message
+#[tauri::command]
+pub async fn list_monitors(app: AppHandle) -> Result<Vec<MonitorInfo>, AppError> {
+    reported!(app, "list_monitors", {
+        let mut monitors: Vec<MonitorInfo> = app
+            .available_monitors()
+            .map_err(|e| format!("Failed to enumerate monitors: {}", e))?
+            .iter()
+            .enumerate()
+            .map(|(index, monitor)| MonitorInfo {
+                index,
+                name: monitor.name().cloned(),
+                bounds: geometry::bounds_of(monitor),
+                work_area: geometry::work_area_of(monitor).area,
+                scale_factor: monitor.scale_factor(),
+                is_primary: false,
+                has_main_window: false,
+            })
+            .collect();
+
+        let primary = app
+            .primary_monitor()
+            .ok()
+            .flatten()
+            .map(|monitor| geometry::bounds_of(&monitor));
+        let main = app
+            .get_webview_window("main")
+            .and_then(|main| main.current_monitor().ok().flatten())
+            .map(|monitor| geometry::bounds_of(&monitor));
+        mark_monitors(&mut monitors, primary.as_ref(), main.as_ref());
+
+        Ok(monitors)
+    })
+}
+
+/// Fits a window of the given size into the area and centers it there.
+///
+/// A window larger than the area is scaled down, keeping its aspect ratio.
+fn fit_in(area: &WindowGeometry, width: f64, height: f64) -> WindowGeometry {
+    let scale = (area.width / width).min(area.height / height).min(1.0);
+    let width = width * scale;
+    let height = height * scale;
+    let (x, y) = geometry::center_in(area, width, height);
+
+    WindowGeometry {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+/// Moves a window to the monitor at the given index, centered on it
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `monitor_index` - Index into the list of connected monitors
+///
+/// # Returns
+/// * `Result<WindowGeometry, AppError>` - The new window geometry or an error message
+#[tauri::command]
+pub async fn move_window_to_monitor(
+    app: AppHandle,
+    window_label: String,
+    monitor_index: usize,
+) -> Result<WindowGeometry, AppError> {
+    reported!(app, "move_window_to_monitor", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        let monitors = app
+            .available_monitors()
+            .map_err(|e| format!("Failed to enumerate monitors: {}", e))?;
+        let monitor = monitors.get(monitor_index).ok_or_else(|| {
+            format!(
+                "Invalid monitor_index: {} (only {} monitors connected)",
+                monitor_index,
+                monitors.len()
+            )
+        })?;
+        let target_area = geometry::work_area_of(monitor);
+
+        // A maximized or fullscreen window would stay on its current monitor
+        if window.is_fullscreen().unwrap_or(false) {
+            window
+                .set_fullscreen(false)
+                .map_err(|e| format!("Failed to exit fullscreen: {}", e))?;
+        }
+        if window.is_maximized().unwrap_or(false) {
+            window
+                .unmaximize()
+                .map_err(|e| format!("Failed to unmaximize window: {}", e))?;
+        }
+
+        let current = geometry::capture_geometry(&window)?;
+        let target = fit_in(&target_area.area, current.width, current.height);
+
+        window
+            .set_position(LogicalPosition::new(target.x, target.y))
+            .map_err(|e| format!("Failed to set window position: {}", e))?;
+        window
+            .set_size(LogicalSize::new(target.width, target.height))
+            .map_err(|e| format!("Failed to set window size: {}", e))?;
+
+        window_log!(
+            info,
+            window_label,
+            "Moved to monitor {} ({})",
+            monitor_index,
+            target_area.name.as_deref().unwrap_or("unnamed")
+        );
+
+        let payload = WindowMovedPayload {
+            window_label,
+            monitor_index,
+            monitor_name: target_area.name,
+            geometry: target,
+        };
+        if let Err(e) = app.emit(WINDOW_MOVED_EVENT, payload) {
+            log::warn!("[Window] Failed to emit {}: {}", WINDOW_MOVED_EVENT, e);
+        }
+
+        Ok(target)
+    })
+}
+
+/// A window position relative to the top-left corner of a monitor
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RelativePosition {
+    /// Name of the monitor, if the platform reports one
+    pub monitor_name: Option<String>,
+    /// Offset from the monitor's left edge in logical pixels
+    pub rel_x: f64,
+    /// Offset from the monitor's top edge in logical pixels
+    pub rel_y: f64,
+}
+
+/// Offset of an absolute position from a monitor's top-left corner
+fn to_relative(x: f64, y: f64, monitor: &WindowGeometry) -> (f64, f64) {
+    (x - monitor.x, y - monitor.y)
+}
+
+/// Absolute position of a window placed at an offset on a monitor
+///
+/// The offset is clamped so a window of the given size stays on the
+/// monitor, which matters when it lands on a smaller one than it was saved on.
+fn to_absolute(
+    rel_x: f64,
+    rel_y: f64,
+    monitor: &WindowGeometry,
+    width: f64,
+    height: f64,
+) -> (f64, f64) {
+    let rel_x = rel_x.clamp(0.0, (monitor.width - width).max(0.0));
+    let rel_y = rel_y.clamp(0.0, (monitor.height - height).max(0.0));
+    (monitor.x + rel_x, monitor.y + rel_y)
+}
+
+/// Gets a window's position relative to the monitor it is on
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+///
+/// # Returns
+/// * `Result<RelativePosition, AppError>` - The relative position or an error message
+#[tauri::command]
+pub async fn get_window_relative_position(
+    app: AppHandle,
+    window_label: String,
+) -> Result<RelativePosition, AppError> {
+    reported!(app, "get_window_relative_position", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+        let monitor = window
+            .current_monitor()
+            .map_err(|e| format!("Failed to get current monitor: {}", e))?
+            .ok_or_else(|| format!("Window {} is on no monitor", window_label))?;
+
+        let current = geometry::capture_geometry(&window)?;
+        let (rel_x, rel_y) = to_relative(current.x, current.y, &geometry::bounds_of(&monitor));
+        Ok(RelativePosition {
+            monitor_name: monitor.name().cloned(),
+            rel_x,
+            rel_y,
+        })
+    })
+}
+
+/// Moves a window to a position relative to a monitor
+///
+/// A monitor that is no longer connected, or no name at all, falls back to
+/// the primary monitor. The window is kept on the monitor.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `monitor_name` - Name of the monitor, as returned by `get_window_relative_position`
+/// * `rel_x` - Offset from the monitor's left edge in logical pixels
+/// * `rel_y` - Offset from the monitor's top edge in logical pixels
+///
+/// # Returns
+/// * `Result<RelativePosition, AppError>` - The position applied, with the monitor used, or an error message
+#[tauri::command]
+pub async fn set_window_relative_position(
+    app: AppHandle,
+    window_label: String,
+    monitor_name: Option<String>,
+    rel_x: f64,
+    rel_y: f64,
+) -> Result<RelativePosition, AppError> {
+    reported!(app, "set_window_relative_position", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        let named = match monitor_name.as_deref() {
+            Some(name) => {
+                let found = app
+                    .available_monitors()
+                    .map_err(|e| format!("Failed to enumerate monitors: {}", e))?
+                    .into_iter()
+                    .find(|monitor| monitor.name().map(String::as_str) == Some(name));
+                if found.is_none() {
+                    window_log!(
+                        warn,
+                        window_label,
+                        "Monitor {} is not connected, using the primary monitor",
+                        name
+                    );
+                }
+                found
+            }
+            None => None,
+        };
+        let monitor = match named {
+            Some(monitor) => monitor,
+            None => app
+                .primary_monitor()
+                .map_err(|e| format!("Failed to get primary monitor: {}", e))?
+                .ok_or_else(|| "No primary monitor".to_string())?,
+        };
+
+        let bounds = geometry::bounds_of(&monitor);
+        let current = geometry::capture_geometry(&window)?;
+        let (x, y) = to_absolute(rel_x, rel_y, &bounds, current.width, current.height);
+        window
+            .set_position(LogicalPosition::new(x, y))
+            .map_err(|e| format!("Failed to set window position: {}", e))?;
+
+        let (rel_x, rel_y) = to_relative(x, y, &bounds);
+        Ok(RelativePosition {
+            monitor_name: monitor.name().cloned(),
+            rel_x,
+            rel_y,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1280x800 laptop screen to the right of a larger monitor
+    const AREA: WindowGeometry = WindowGeometry {
+        x: 2560.0,
+        y: 0.0,
+        width: 1280.0,
+        height: 760.0,
+    };
+
+    fn monitor(index: usize, bounds: WindowGeometry) -> MonitorInfo {
+        MonitorInfo {
+            index,
+            name: None,
+            bounds,
+            work_area: bounds,
+            scale_factor: 1.0,
+            is_primary: false,
+            has_main_window: false,
+        }
+    }
+
+    #[test]
+    fn test_mark_monitors_flags_primary_and_main_window_monitor() {
+        let desktop = WindowGeometry {
+            x: 0.0,
+            y: 0.0,
+            width: 2560.0,
+            height: 1440.0,
+        };
+        let laptop = WindowGeometry {
+            x: 2560.0,
+            y: 0.0,
+            width: 1280.0,
+            height: 800.0,
+        };
+        let mut monitors = vec![monitor(0, desktop), monitor(1, laptop)];
+
+        mark_monitors(&mut monitors, Some(&desktop), Some(&laptop));
+        assert!(monitors[0].is_primary && !monitors[0].has_main_window);
+        assert!(!monitors[1].is_primary && monitors[1].has_main_window);
+
+        // Headless or unknown: nothing is flagged
+        mark_monitors(&mut monitors, None, None);
+        assert!(monitors.iter().all(|m| !m.is_primary && !m.has_main_window));
+    }
+
+    #[test]
+    fn test_fit_in_centers_a_window_that_fits() {
+        let fitted = fit_in(&AREA, 1000.0, 700.0);
+        assert_eq!(
+            fitted,
+            WindowGeometry {
+                x: 2700.0,
+                y: 30.0,
+                width: 1000.0,
+                height: 700.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_fit_in_shrinks_a_window_for_a_smaller_monitor() {
+        let fitted = fit_in(&AREA, 2000.0, 1000.0);
+        // Width is the tighter fit: 1280 / 2000 = 0.64
+        assert_eq!(fitted.width, 1280.0);
+        assert_eq!(fitted.height, 640.0);
+        assert_eq!((fitted.x, fitted.y), (2560.0, 60.0));
+    }
+
+    #[test]
+    fn test_relative_position_round_trips_across_monitor_moves() {
+        // The laptop screen saved at x 2560, now arranged left of the desktop
+        let moved = WindowGeometry {
+            x: -1280.0,
+            y: 200.0,
+            ..AREA
+        };
+        let (rel_x, rel_y) = to_relative(2660.0, 40.0, &AREA);
+        assert_eq!((rel_x, rel_y), (100.0, 40.0));
+        assert_eq!(
+            to_absolute(rel_x, rel_y, &moved, 800.0, 600.0),
+            (-1180.0, 240.0)
+        );
+        assert_eq!(
+            to_absolute(rel_x, rel_y, &AREA, 800.0, 600.0),
+            (2660.0, 40.0)
+        );
+    }
+
+    #[test]
+    fn test_to_absolute_keeps_the_window_on_a_smaller_monitor() {
+        // Saved near the right edge of a wide monitor
+        assert_eq!(
+            to_absolute(2000.0, 900.0, &AREA, 800.0, 600.0),
+            (3040.0, 160.0)
+        );
+        assert_eq!(
+            to_absolute(-50.0, -10.0, &AREA, 800.0, 600.0),
+            (2560.0, 0.0)
+        );
+        // Larger than the monitor: pinned to its corner
+        assert_eq!(
+            to_absolute(300.0, 300.0, &AREA, 2000.0, 1000.0),
+            (2560.0, 0.0)
+        );
+    }
+}
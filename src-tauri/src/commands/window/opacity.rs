@@ -0,0 +1,162 @@
+/**
+ * Per-Window Opacity
+ *
+ * Lets inactive session windows recede by lowering their opacity. Windows
+ * uses a layered window, macOS the NSWindow alpha value, and other platforms
+ * hand the value to the frontend through an `opacity-changed` event.
+ */
+use tauri::{AppHandle, Emitter, EventTarget, Manager, WebviewWindow};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+
+/// Lowest and highest accepted opacity
+const MIN_OPACITY: f64 = 0.2;
+const MAX_OPACITY: f64 = 1.0;
+
+/// Event sent to a window whose opacity has to be applied by the frontend
+const OPACITY_CHANGED_EVENT: &str = "opacity-changed";
+
+/// Checks that an opacity is within the supported range
+fn validate_opacity(opacity: f64) -> Result<f64, String> {
+    if opacity.is_finite() && (MIN_OPACITY..=MAX_OPACITY).contains(&opacity) {
+        Ok(opacity)
+    } else {
+        Err(format!(
+            "Invalid opacity {}: must be between {} and {}",
+            opacity, MIN_OPACITY, MAX_OPACITY
+        ))
+    }
+}
+
+/// Converts an opacity into the 0-255 alpha used by layered windows
+#[cfg_attr(not(any(target_os = "windows", test)), allow(dead_code))]
+fn opacity_to_alpha(opacity: f64) -> u8 {
+    (opacity * 255.0).round() as u8
+}
+
+/// Applies the opacity through a layered window
+///
+/// Returns `Ok(true)` once the native call went through.
+#[cfg(target_os = "windows")]
+fn set_native_opacity(window: &WebviewWindow, opacity: f64) -> Result<bool, String> {
+    use windows::Win32::Foundation::{COLORREF, HWND};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongW, SetLayeredWindowAttributes, SetWindowLongW, GWL_EXSTYLE, LWA_ALPHA,
+        WS_EX_LAYERED,
+    };
+
+    let hwnd = window
+        .hwnd()
+        .map_err(|e| format!("Failed to get window handle: {}", e))?;
+    let hwnd = HWND(hwnd.0);
+
+    unsafe {
+        let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+        if ex_style & WS_EX_LAYERED.0 as i32 == 0 {
+            SetWindowLongW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as i32);
+        }
+        SetLayeredWindowAttributes(hwnd, COLORREF(0), opacity_to_alpha(opacity), LWA_ALPHA)
+    }
+    .map_err(|e| format!("Failed to set window opacity: {}", e))?;
+
+    Ok(true)
+}
+
+/// Applies the opacity through the NSWindow alpha value
+///
+/// Returns `Ok(true)` once the native call went through.
+#[cfg(target_os = "macos")]
+fn set_native_opacity(window: &WebviewWindow, opacity: f64) -> Result<bool, String> {
+    use objc2_app_kit::NSWindow;
+
+    let ns_window = window
+        .ns_window()
+        .map_err(|e| format!("Failed to get NSWindow: {}", e))?;
+    let ns_window = ns_window as usize;
+
+    // AppKit objects may only be touched from the main thread
+    window
+        .run_on_main_thread(move || unsafe {
+            let ns_window = &*(ns_window as *const NSWindow);
+            ns_window.setAlphaValue(opacity);
+        })
+        .map_err(|e| format!("Failed to run on main thread: {}", e))?;
+
+    Ok(true)
+}
+
+/// No native per-window opacity; the frontend applies it instead
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn set_native_opacity(_window: &WebviewWindow, _opacity: f64) -> Result<bool, String> {
+    Ok(false)
+}
+
+/// Sets the opacity of a window
+///
+/// Where the platform has no native per-window opacity (Linux), the value is
+/// sent to the window as `opacity-changed` for the frontend to apply.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `opacity` - The opacity (0.2 - 1.0, 1.0 is fully opaque)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_opacity(
+    app: AppHandle,
+    window_label: String,
+    opacity: f64,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_opacity", {
+        let opacity = validate_opacity(opacity).map_err(AppError::InvalidParam)?;
+
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        if !set_native_opacity(&window, opacity)? {
+            window
+                .emit_to(
+                    EventTarget::webview_window(&window_label),
+                    OPACITY_CHANGED_EVENT,
+                    opacity,
+                )
+                .map_err(|e| AppError::Emit(format!("Failed to emit event: {}", e)))?;
+        }
+
+        with_registry(&app, |registry| {
+            registry.update(&window_label, |meta| meta.opacity = opacity)
+        });
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_opacity_accepts_range() {
+        assert_eq!(validate_opacity(0.2), Ok(0.2));
+        assert_eq!(validate_opacity(0.6), Ok(0.6));
+        assert_eq!(validate_opacity(1.0), Ok(1.0));
+    }
+
+    #[test]
+    fn test_validate_opacity_rejects_out_of_range() {
+        assert!(validate_opacity(0.19).is_err());
+        assert!(validate_opacity(1.01).is_err());
+        assert!(validate_opacity(0.0).is_err());
+        assert!(validate_opacity(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_opacity_to_alpha() {
+        assert_eq!(opacity_to_alpha(1.0), 255);
+        assert_eq!(opacity_to_alpha(0.2), 51);
+        assert_eq!(opacity_to_alpha(0.5), 128);
+    }
+}
@@ -0,0 +1,120 @@
+/**
+ * Session Preview Windows
+ *
+ * Small borderless, always-on-top windows that show a session's latest
+ * output without detaching its tab. A preview closes as soon as it loses
+ * focus, like a tooltip.
+ */
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+
+use super::error::AppError;
+use super::{build_url, validate_label_segment};
+
+/// Prefix of every preview window label
+const PREVIEW_LABEL_PREFIX: &str = "preview-window-";
+
+/// Inner size of a preview window in logical pixels
+const PREVIEW_WIDTH: f64 = 400.0;
+const PREVIEW_HEIGHT: f64 = 300.0;
+
+/// Builds the preview window label for a tab
+fn preview_label(tab_id: &str) -> Result<String, String> {
+    validate_label_segment("tab_id", tab_id)?;
+    Ok(format!("{}{}", PREVIEW_LABEL_PREFIX, tab_id))
+}
+
+/// Builds the webview URL of a preview window
+fn preview_url(tab_id: &str) -> String {
+    build_url([("window", "preview"), ("tab_id", tab_id)])
+}
+
+/// Opens a preview of a session's latest output at the given position
+///
+/// An open preview of the same tab is moved to the new anchor instead.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `tab_id` - The tab to preview
+/// * `anchor_x` - Logical x coordinate of the preview's top-left corner
+/// * `anchor_y` - Logical y coordinate of the preview's top-left corner
+///
+/// # Returns
+/// * `Result<String, AppError>` - The preview window label or an error message
+#[tauri::command]
+pub async fn create_preview_window(
+    app: AppHandle,
+    tab_id: String,
+    anchor_x: f64,
+    anchor_y: f64,
+) -> Result<String, AppError> {
+    reported!(app, "create_preview_window", {
+        let window_label = preview_label(&tab_id).map_err(AppError::InvalidParam)?;
+
+        if let Some(window) = app.get_webview_window(&window_label) {
+            window
+                .set_position(tauri::LogicalPosition::new(anchor_x, anchor_y))
+                .map_err(|e| format!("Failed to set window position: {}", e))?;
+            window
+                .set_focus()
+                .map_err(|e| format!("Failed to focus window: {}", e))?;
+            return Ok(window_label);
+        }
+
+        let url = preview_url(&tab_id);
+        window_log!(
+            info,
+            window_label,
+            "Creating preview window with URL: {}",
+            url
+        );
+
+        let window = WebviewWindowBuilder::new(&app, &window_label, WebviewUrl::App(url.into()))
+            .title("Preview")
+            .inner_size(PREVIEW_WIDTH, PREVIEW_HEIGHT)
+            .position(anchor_x, anchor_y)
+            .resizable(false)
+            .maximizable(false)
+            .minimizable(false)
+            .decorations(false)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .focused(true)
+            .build()
+            .map_err(|e| format!("Failed to create preview window: {}", e))?;
+
+        // Close like a tooltip once the user clicks elsewhere
+        let window_for_blur = window.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::Focused(false) = event {
+                if let Err(e) = window_for_blur.close() {
+                    window_log!(
+                        warn,
+                        window_for_blur.label(),
+                        "Failed to close preview window: {}",
+                        e
+                    );
+                }
+            }
+        });
+
+        Ok(window_label)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_label_and_url() {
+        assert_eq!(preview_label("tab-1").unwrap(), "preview-window-tab-1");
+        assert!(preview_label("").is_err());
+        assert_eq!(preview_url("tab-1"), "/?window=preview&tab_id=tab-1");
+    }
+
+    #[test]
+    fn test_preview_is_not_a_session_window() {
+        let label = preview_label("tab-1").unwrap();
+        assert!(!super::super::is_session_window_label(&label));
+    }
+}
@@ -0,0 +1,296 @@
+/**
+ * Session Window Reattach
+ *
+ * Moves a detached session back into the main window as a tab. The main window
+ * is asked to open the tab first and the session window is only closed once it
+ * acknowledges, so a main window that never answers doesn't lose the session.
+ *
+ * Mirroring is the non-destructive variant: the main window opens a read-only
+ * view of the session and the detached window stays open.
+ */
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Listener, Manager};
+use tokio::sync::oneshot;
+
+use super::error::{window_not_found, AppError};
+use super::eventlog::{record_window_event, WindowEventKind};
+use super::ipc::emit_to_main_window;
+use super::lifecycle::close_programmatically;
+use super::registry::{with_registry, WindowMeta};
+use super::snapshot;
+use super::{session_info_from_url, SessionWindowInfo};
+
+/// Event sent to the main window asking it to reopen the tab
+const REATTACH_REQUESTED_EVENT: &str = "tab-reattach-requested";
+
+/// Event the main window sends back (via `emit_to_window`) once the tab is open
+const REATTACH_ACK_EVENT: &str = "tab-reattach-ack";
+
+/// How long to wait for the main window before giving up
+const REATTACH_ACK_TIMEOUT: Duration = Duration::from_millis(5000);
+
+/// Event asking the main window to show a read-only view of a detached session
+const MIRROR_SESSION_EVENT: &str = "mirror-session";
+
+/// Extracts the tab_id from an ack payload.
+///
+/// `emit_to_window` forwards its payload as a JSON string, so the ack may
+/// arrive either as an object or as a string containing the object.
+fn ack_tab_id(raw_payload: &str) -> Option<String> {
+    let mut value: serde_json::Value = serde_json::from_str(raw_payload).ok()?;
+    if let serde_json::Value::String(inner) = &value {
+        value = serde_json::from_str(inner).ok()?;
+    }
+
+    value
+        .get("tab_id")
+        .or_else(|| value.get("tabId"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+/// Builds the session info of the `tab-reattach-requested` payload
+///
+/// The URL only knows the engine the window was opened with; the registry
+/// also tracks switches made since, so its engine wins. Project paths too
+/// long for the URL are only known to the registry.
+fn reattach_request(from_url: SessionWindowInfo, meta: Option<WindowMeta>) -> SessionWindowInfo {
+    let Some(meta) = meta else {
+        return from_url;
+    };
+    SessionWindowInfo {
+        engine: meta.engine.or(from_url.engine),
+        project_path: from_url.project_path.or(meta.project_path),
+        ..from_url
+    }
+}
+
+/// Refuses to reattach a window flagged busy
+///
+/// Its close would be held back by the busy guard after the main window
+/// already opened the tab, leaving the session open in both places.
+fn check_not_busy(meta: Option<&WindowMeta>, window_label: &str) -> Result<(), AppError> {
+    if meta.is_some_and(|meta| meta.busy) {
+        return Err(AppError::Other(format!(
+            "Window {} is busy; reattach it once the run has finished",
+            window_label
+        )));
+    }
+    Ok(())
+}
+
+/// Payload of the `tab-reattach-requested` event
+#[derive(Debug, Clone, Serialize)]
+struct ReattachPayload {
+    #[serde(flatten)]
+    session: SessionWindowInfo,
+    /// UI state of the window, so the tab resumes where the window left off
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_snapshot: Option<String>,
+}
+
+/// Reattaches a detached session window to the main window as a tab
+///
+/// Emits `tab-reattach-requested` to the main window and waits for a
+/// `tab-reattach-ack` carrying the same tab_id before closing the session window.
+/// The window is first asked for its session snapshot, which travels with the
+/// request so the tab resumes identically. A window flagged busy is refused.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the session window to reattach
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message (the window stays open on error)
+#[tauri::command]
+pub async fn reattach_session_window(app: AppHandle, window_label: String) -> Result<(), AppError> {
+    reported!(app, "reattach_session_window", {
+        let _scope = super::logging::LogScope::new("reattach_session_window", &window_label);
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        let meta = with_registry(&app, |registry| registry.get(&window_label)).flatten();
+        check_not_busy(meta.as_ref(), &window_label)?;
+
+        let url = window
+            .url()
+            .map_err(|e| format!("Failed to read window URL: {}", e))?;
+        let request = reattach_request(session_info_from_url(&window_label, &url)?, meta);
+        let request = ReattachPayload {
+            session_snapshot: snapshot::request_snapshot(&app, &window_label).await,
+            session: request,
+        };
+
+        // Register for the ack before emitting so a fast reply can't be missed
+        let (tx, rx) = oneshot::channel::<()>();
+        let tx = Arc::new(Mutex::new(Some(tx)));
+        let expected_tab_id = request.session.tab_id.clone();
+        let listener_id = app.listen(REATTACH_ACK_EVENT, move |event| {
+            if ack_tab_id(event.payload()).as_deref() == Some(expected_tab_id.as_str()) {
+                if let Some(tx) = tx.lock().ok().and_then(|mut tx| tx.take()) {
+                    let _ = tx.send(());
+                }
+            }
+        });
+
+        if let Err(e) = emit_to_main_window(&app, REATTACH_REQUESTED_EVENT, &request) {
+            app.unlisten(listener_id);
+            return Err(e);
+        }
+
+        let acked = tokio::time::timeout(REATTACH_ACK_TIMEOUT, rx).await;
+        app.unlisten(listener_id);
+
+        match acked {
+            Ok(Ok(())) => {
+                record_window_event(&app, WindowEventKind::Reattached, &window_label);
+                close_programmatically(&window)?;
+                window_log!(info, window_label, "Session window reattached");
+                Ok(())
+            }
+            _ => Err(AppError::Other(format!(
+            "Main window did not acknowledge reattach of {} within {}ms; keeping the window open",
+            window_label,
+            REATTACH_ACK_TIMEOUT.as_millis()
+        ))),
+        }
+    })
+}
+
+/// Payload of the `mirror-session` event, taken from the registry
+///
+/// The registry follows engine switches and knows project paths too long
+/// for the window URL, so it is the better source for a live window.
+fn mirror_payload(meta: WindowMeta) -> SessionWindowInfo {
+    SessionWindowInfo {
+        window_label: meta.window_label,
+        tab_id: meta.tab_id,
+        session_id: meta.session_id,
+        project_path: meta.project_path,
+        engine: meta.engine,
+    }
+}
+
+/// Shows a detached session read-only in the main window, keeping the window open
+///
+/// Emits `mirror-session` to the main window with the session details.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the session window to mirror
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn mirror_session_in_main(app: AppHandle, window_label: String) -> Result<(), AppError> {
+    reported!(app, "mirror_session_in_main", {
+        if app.get_webview_window(&window_label).is_none() {
+            return Err(window_not_found(&window_label));
+        }
+        let meta = with_registry(&app, |registry| registry.get(&window_label))
+            .flatten()
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        emit_to_main_window(&app, MIRROR_SESSION_EVENT, mirror_payload(meta))?;
+        window_log!(info, window_label, "Session mirrored in the main window");
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirror_payload_matches_registry_metadata() {
+        use super::super::Engine;
+
+        let meta = WindowMeta::new(
+            "session-window-tab-1",
+            "tab-1",
+            Some("s1".to_string()),
+            Some("/projects/app".to_string()),
+            Some(Engine::Gemini),
+        );
+        assert_eq!(
+            serde_json::to_value(mirror_payload(meta)).unwrap(),
+            serde_json::json!({
+                "window_label": "session-window-tab-1",
+                "tab_id": "tab-1",
+                "session_id": "s1",
+                "project_path": "/projects/app",
+                "engine": "gemini"
+            })
+        );
+    }
+
+    #[test]
+    fn test_ack_tab_id_accepts_object_and_string_payloads() {
+        assert_eq!(
+            ack_tab_id(r#"{"tab_id":"tab-1"}"#).as_deref(),
+            Some("tab-1")
+        );
+        assert_eq!(ack_tab_id(r#"{"tabId":"tab-1"}"#).as_deref(), Some("tab-1"));
+        // Payload forwarded as a string by emit_to_window
+        assert_eq!(
+            ack_tab_id(r#""{\"tab_id\":\"tab-1\"}""#).as_deref(),
+            Some("tab-1")
+        );
+        assert_eq!(ack_tab_id("not json"), None);
+        assert_eq!(ack_tab_id(r#"{"other":1}"#), None);
+    }
+
+    #[test]
+    fn test_busy_window_is_not_reattached() {
+        let label = "session-window-tab-1";
+        let mut meta = WindowMeta::new(label, "tab-1", None, None, None);
+        assert_eq!(check_not_busy(Some(&meta), label), Ok(()));
+        assert_eq!(check_not_busy(None, label), Ok(()));
+
+        meta.busy = true;
+        assert_eq!(
+            check_not_busy(Some(&meta), label),
+            Err(AppError::Other(
+                "Window session-window-tab-1 is busy; reattach it once the run has finished"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_reattach_request_carries_mid_session_engine_change() {
+        use super::super::registry::WindowRegistry;
+        use super::super::Engine;
+
+        let label = "session-window-tab-1";
+        let url = tauri::Url::parse(
+            "http://localhost/?window=session&tab_id=tab-1&session_id=s1&engine=claude",
+        )
+        .unwrap();
+        let registry = WindowRegistry::default();
+        registry.register(WindowMeta::new(
+            label,
+            "tab-1",
+            Some("s1".to_string()),
+            None,
+            Some(Engine::Claude),
+        ));
+
+        // The user switched to Codex inside the window
+        registry.update(label, |meta| meta.engine = Some(Engine::Codex));
+
+        let request = reattach_request(
+            session_info_from_url(label, &url).unwrap(),
+            registry.get(label),
+        );
+        assert_eq!(request.engine, Some(Engine::Codex));
+        assert_eq!(request.session_id.as_deref(), Some("s1"));
+
+        // Windows the registry doesn't know keep the engine from their URL
+        let request = reattach_request(session_info_from_url(label, &url).unwrap(), None);
+        assert_eq!(request.engine, Some(Engine::Claude));
+    }
+}
@@ -0,0 +1,615 @@
+/**
+ * Session Window Registry
+ *
+ * Per-window metadata the backend needs after a session window has been
+ * built (Tauri itself only knows the label). Managed as app state, filled in
+ * when a session window is created and pruned when it closes.
+ */
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use super::activity::WindowActivity;
+use super::error::{window_not_found, AppError};
+use super::{
+    is_session_window_label, session_info_from_url, tab_id_from_label, Engine, SessionWindowInfo,
+};
+
+/// Metadata of one session window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WindowMeta {
+    /// Label of the session window
+    pub window_label: String,
+    /// Tab identifier of the detached session
+    pub tab_id: String,
+    /// Session ID, if the session has started
+    pub session_id: Option<String>,
+    /// Project path for the session
+    pub project_path: Option<String>,
+    /// Execution engine of the session
+    pub engine: Option<Engine>,
+    /// Whether a run is in progress (closing asks for confirmation)
+    pub busy: bool,
+    /// Current zoom factor (1.0 is 100%)
+    pub zoom: f64,
+    /// Current opacity (1.0 is fully opaque)
+    pub opacity: f64,
+    /// What the session is doing, as last reported by the window
+    pub activity: WindowActivity,
+    /// Whether the window is shown (hidden windows keep running)
+    pub visible: bool,
+    /// Engine whose taskbar icon the window shows, if one was set
+    pub icon: Option<Engine>,
+    /// Whether the window uses native decorations instead of the custom title bar
+    pub decorated: bool,
+    /// Whether the window is pinned to all virtual desktops
+    pub visible_on_all_workspaces: bool,
+    /// Whether the user can resize the window
+    pub resizable: bool,
+    /// Whether the user can maximize the window
+    pub maximizable: bool,
+    /// Whether the window is left out of the taskbar (it stays on screen)
+    pub skip_taskbar: bool,
+    /// Group the window was tagged with by `set_window_group`
+    pub group_id: Option<String>,
+    /// Whether the window has keyboard focus, following its focus events
+    #[serde(skip)]
+    pub focused: bool,
+    /// Whether `minimize_all_session_windows` minimized the window, so
+    /// `restore_all_session_windows` may bring it back
+    pub minimized_by_app: bool,
+    /// Last thumbnail (PNG data URL), served while the window can't paint
+    #[serde(skip)]
+    pub thumbnail: Option<String>,
+    /// UI state handed over on detach, until the window has picked it up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_snapshot: Option<String>,
+}
+
+impl WindowMeta {
+    /// Creates the entry of a freshly built window
+    pub fn new(
+        window_label: &str,
+        tab_id: &str,
+        session_id: Option<String>,
+        project_path: Option<String>,
+        engine: Option<Engine>,
+    ) -> Self {
+        Self {
+            window_label: window_label.to_string(),
+            tab_id: tab_id.to_string(),
+            session_id,
+            project_path,
+            engine,
+            busy: false,
+            zoom: 1.0,
+            opacity: 1.0,
+            activity: WindowActivity::default(),
+            visible: true,
+            icon: None,
+            decorated: false,
+            visible_on_all_workspaces: false,
+            resizable: true,
+            maximizable: true,
+            skip_taskbar: false,
+            group_id: None,
+            focused: false,
+            minimized_by_app: false,
+            thumbnail: None,
+            session_snapshot: None,
+        }
+    }
+}
+
+/// Best-effort entry for a live window the registry doesn't know about
+///
+/// Uses the session details from the window URL when they can be read, and
+/// otherwise only the tab ID encoded in the label.
+fn stub_meta(window_label: &str, info: Option<SessionWindowInfo>) -> WindowMeta {
+    match info {
+        Some(info) => WindowMeta::new(
+            window_label,
+            &info.tab_id,
+            info.session_id,
+            info.project_path,
+            info.engine,
+        ),
+        None => WindowMeta::new(
+            window_label,
+            tab_id_from_label(window_label).unwrap_or(window_label),
+            None,
+            None,
+            None,
+        ),
+    }
+}
+
+/// Outcome of `reconcile_windows`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ReconcileResult {
+    /// Entries dropped because their window no longer exists
+    pub pruned: u32,
+    /// Live windows that were missing and got a stub entry
+    pub adopted: u32,
+}
+
+/// Metadata of every open session window, keyed by window label
+#[derive(Debug, Default)]
+pub struct WindowRegistry {
+    windows: Mutex<HashMap<String, WindowMeta>>,
+    /// Title bar theme last reported by the frontend (true = dark)
+    theme: Mutex<Option<bool>>,
+}
+
+impl WindowRegistry {
+    /// Remembers the app theme, for the title bars of windows opened later
+    pub fn set_theme(&self, is_dark: bool) {
+        if let Ok(mut theme) = self.theme.lock() {
+            *theme = Some(is_dark);
+        }
+    }
+
+    /// Returns the app theme last reported by the frontend (true = dark)
+    pub fn theme(&self) -> Option<bool> {
+        self.theme.lock().ok().and_then(|theme| *theme)
+    }
+
+    /// Adds or replaces the entry of a window, returning the one it replaced
+    pub fn register(&self, meta: WindowMeta) -> Option<WindowMeta> {
+        self.windows
+            .lock()
+            .ok()
+            .and_then(|mut windows| windows.insert(meta.window_label.clone(), meta))
+    }
+
+    /// Removes the entry of a window
+    pub fn unregister(&self, window_label: &str) {
+        if let Ok(mut windows) = self.windows.lock() {
+            windows.remove(window_label);
+        }
+    }
+
+    /// Returns a copy of the entry of a window
+    pub fn get(&self, window_label: &str) -> Option<WindowMeta> {
+        self.windows
+            .lock()
+            .ok()
+            .and_then(|windows| windows.get(window_label).cloned())
+    }
+
+    /// Applies `update` to the entry of a window; returns false if it isn't registered
+    pub fn update(&self, window_label: &str, update: impl FnOnce(&mut WindowMeta)) -> bool {
+        match self.windows.lock() {
+            Ok(mut windows) => match windows.get_mut(window_label) {
+                Some(meta) => {
+                    update(meta);
+                    true
+                }
+                None => false,
+            },
+            Err(_) => false,
+        }
+    }
+
+    /// Removes and returns the session snapshot of a window
+    pub fn take_session_snapshot(&self, window_label: &str) -> Option<String> {
+        self.windows
+            .lock()
+            .ok()
+            .and_then(|mut windows| windows.get_mut(window_label)?.session_snapshot.take())
+    }
+
+    /// Records a focus change; a window gaining focus takes it from all others
+    pub fn set_focused(&self, window_label: &str, focused: bool) {
+        if let Ok(mut windows) = self.windows.lock() {
+            for (label, meta) in windows.iter_mut() {
+                if label == window_label {
+                    meta.focused = focused;
+                    // Brought back by the user; a later minimize is theirs
+                    if focused {
+                        meta.minimized_by_app = false;
+                    }
+                } else if focused {
+                    meta.focused = false;
+                }
+            }
+        }
+    }
+
+    /// Returns the label of the session window that has focus, if any
+    pub fn focused_label(&self) -> Option<String> {
+        self.windows.lock().ok().and_then(|windows| {
+            windows
+                .values()
+                .find(|meta| meta.focused)
+                .map(|meta| meta.window_label.clone())
+        })
+    }
+
+    /// Returns the engine of a window, if known
+    pub fn engine_of(&self, window_label: &str) -> Option<Engine> {
+        self.get(window_label).and_then(|meta| meta.engine)
+    }
+
+    /// Whether a window is flagged busy
+    pub fn is_busy(&self, window_label: &str) -> bool {
+        self.get(window_label)
+            .map(|meta| meta.busy)
+            .unwrap_or(false)
+    }
+
+    /// Whether a window uses native decorations instead of the custom title bar
+    pub fn is_decorated(&self, window_label: &str) -> bool {
+        self.get(window_label)
+            .map(|meta| meta.decorated)
+            .unwrap_or(false)
+    }
+
+    /// Returns the labels of the windows running the given engine, sorted
+    pub fn labels_for_engine(&self, engine: Engine) -> Vec<String> {
+        let mut labels: Vec<String> = self
+            .windows
+            .lock()
+            .map(|windows| {
+                windows
+                    .iter()
+                    .filter(|(_, meta)| meta.engine == Some(engine))
+                    .map(|(label, _)| label.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        labels.sort();
+        labels
+    }
+
+    /// Returns the labels of the windows in a group, sorted
+    pub fn labels_in_group(&self, group_id: &str) -> Vec<String> {
+        let mut labels: Vec<String> = self
+            .windows
+            .lock()
+            .map(|windows| {
+                windows
+                    .iter()
+                    .filter(|(_, meta)| meta.group_id.as_deref() == Some(group_id))
+                    .map(|(label, _)| label.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        labels.sort();
+        labels
+    }
+
+    /// Returns the labels of the windows minimized by `minimize_all_session_windows`, sorted
+    pub fn labels_minimized_by_app(&self) -> Vec<String> {
+        let mut labels: Vec<String> = self
+            .windows
+            .lock()
+            .map(|windows| {
+                windows
+                    .iter()
+                    .filter(|(_, meta)| meta.minimized_by_app)
+                    .map(|(label, _)| label.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        labels.sort();
+        labels
+    }
+
+    /// Brings the registry in line with the live session windows
+    ///
+    /// Entries without a window in `live` are dropped; windows from `live`
+    /// that aren't registered yet are added as given. Known entries are kept.
+    pub fn reconcile(&self, live: Vec<WindowMeta>) -> ReconcileResult {
+        let mut result = ReconcileResult::default();
+        let Ok(mut windows) = self.windows.lock() else {
+            return result;
+        };
+
+        let live_labels: HashSet<String> =
+            live.iter().map(|meta| meta.window_label.clone()).collect();
+        windows.retain(|label, _| {
+            let keep = live_labels.contains(label);
+            if !keep {
+                result.pruned += 1;
+            }
+            keep
+        });
+
+        for meta in live {
+            if !windows.contains_key(&meta.window_label) {
+                windows.insert(meta.window_label.clone(), meta);
+                result.adopted += 1;
+            }
+        }
+
+        result
+    }
+}
+
+/// Runs `f` with the registry, if it has been registered with the app
+pub(super) fn with_registry<T>(app: &AppHandle, f: impl FnOnce(&WindowRegistry) -> T) -> Option<T> {
+    app.try_state::<WindowRegistry>()
+        .map(|registry| f(registry.inner()))
+}
+
+/// Gets the metadata of a session window
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the session window
+///
+/// # Returns
+/// * `Result<WindowMeta, AppError>` - The window metadata or an error message
+#[tauri::command]
+pub async fn get_window_meta(app: AppHandle, window_label: String) -> Result<WindowMeta, AppError> {
+    reported!(app, "get_window_meta", {
+        with_registry(&app, |registry| registry.get(&window_label))
+            .flatten()
+            .ok_or_else(|| window_not_found(&window_label))
+    })
+}
+
+/// Records that a session window switched engines mid-session
+///
+/// Keeps the registry authoritative for reattach and restore, and re-tints the
+/// window's title bar for the new engine.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the session window
+/// * `engine` - The engine the session now runs on
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn update_window_engine(
+    app: AppHandle,
+    window_label: String,
+    engine: Engine,
+) -> Result<(), AppError> {
+    reported!(app, "update_window_engine", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+        let updated = with_registry(&app, |registry| {
+            registry.update(&window_label, |meta| meta.engine = Some(engine))
+        })
+        .unwrap_or(false);
+        if !updated {
+            return Err(window_not_found(&window_label));
+        }
+
+        if let Some(tab_id) = tab_id_from_label(&window_label) {
+            super::restore::set_open_window_engine(&app, tab_id, engine);
+        }
+        super::titlebar::apply_current_theme(&app, &window);
+
+        window_log!(info, window_label, "Engine switched to {}", engine);
+        Ok(())
+    })
+}
+
+/// Re-syncs the registry with the session windows that are actually open
+///
+/// Drops entries whose window is gone and adopts live `session-window-*`
+/// windows the registry has lost track of (e.g. after a crash) as stubs.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+///
+/// # Returns
+/// * `Result<ReconcileResult, AppError>` - Number of pruned and adopted entries
+#[tauri::command]
+pub async fn reconcile_windows(app: AppHandle) -> Result<ReconcileResult, AppError> {
+    reported!(app, "reconcile_windows", {
+        let live: Vec<WindowMeta> = app
+            .webview_windows()
+            .into_iter()
+            .filter(|(label, _)| is_session_window_label(label))
+            .map(|(label, window)| {
+                let info = window
+                    .url()
+                    .ok()
+                    .and_then(|url| session_info_from_url(&label, &url).ok());
+                stub_meta(&label, info)
+            })
+            .collect();
+
+        let result = with_registry(&app, |registry| registry.reconcile(live))
+            .ok_or_else(|| "Window registry is not available".to_string())?;
+        log::info!(
+            "[Window] Registry reconciled: {} pruned, {} adopted",
+            result.pruned,
+            result.adopted
+        );
+
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(tab_id: &str, engine: Option<Engine>) -> WindowMeta {
+        WindowMeta::new(
+            &format!("session-window-{}", tab_id),
+            tab_id,
+            None,
+            None,
+            engine,
+        )
+    }
+
+    #[test]
+    fn test_labels_for_engine_filters_mixed_windows() {
+        let registry = WindowRegistry::default();
+        registry.register(meta("a", Some(Engine::Codex)));
+        registry.register(meta("b", Some(Engine::Claude)));
+        registry.register(meta("c", Some(Engine::Codex)));
+        registry.register(meta("d", None));
+
+        assert_eq!(
+            registry.labels_for_engine(Engine::Codex),
+            vec!["session-window-a", "session-window-c"]
+        );
+        assert_eq!(
+            registry.labels_for_engine(Engine::Claude),
+            vec!["session-window-b"]
+        );
+        assert!(registry.labels_for_engine(Engine::Gemini).is_empty());
+    }
+
+    #[test]
+    fn test_unregister_removes_entry() {
+        let registry = WindowRegistry::default();
+        registry.register(meta("a", Some(Engine::Claude)));
+        assert_eq!(registry.engine_of("session-window-a"), Some(Engine::Claude));
+
+        registry.unregister("session-window-a");
+        assert_eq!(registry.get("session-window-a"), None);
+        assert!(registry.labels_for_engine(Engine::Claude).is_empty());
+    }
+
+    #[test]
+    fn test_update_busy_and_zoom() {
+        let registry = WindowRegistry::default();
+        registry.register(meta("a", None));
+        assert!(!registry.is_busy("session-window-a"));
+
+        assert!(registry.update("session-window-a", |meta| {
+            meta.busy = true;
+            meta.zoom = 1.5;
+        }));
+        let updated = registry.get("session-window-a").unwrap();
+        assert!(updated.busy);
+        assert_eq!(updated.zoom, 1.5);
+
+        // Unknown windows are left alone
+        assert!(!registry.update("session-window-x", |meta| meta.busy = true));
+        assert!(!registry.is_busy("session-window-x"));
+    }
+
+    #[test]
+    fn test_restore_all_only_targets_windows_minimized_by_app() {
+        let registry = WindowRegistry::default();
+        registry.register(meta("a", None));
+        registry.register(meta("b", None));
+        registry.register(meta("c", None));
+
+        // minimize-all got a and b; c was minimized by the user beforehand
+        registry.update("session-window-a", |meta| meta.minimized_by_app = true);
+        registry.update("session-window-b", |meta| meta.minimized_by_app = true);
+        assert_eq!(
+            registry.labels_minimized_by_app(),
+            vec!["session-window-a", "session-window-b"]
+        );
+
+        // The user brought b back; minimizing it again is their call
+        registry.set_focused("session-window-b", true);
+        assert_eq!(registry.labels_minimized_by_app(), vec!["session-window-a"]);
+    }
+
+    #[test]
+    fn test_focus_follows_the_last_focused_window() {
+        let registry = WindowRegistry::default();
+        registry.register(meta("a", None));
+        registry.register(meta("b", None));
+        assert_eq!(registry.focused_label(), None);
+
+        registry.set_focused("session-window-a", true);
+        assert_eq!(
+            registry.focused_label().as_deref(),
+            Some("session-window-a")
+        );
+
+        // Focus moves straight to b, whatever order the blur of a arrives in
+        registry.set_focused("session-window-b", true);
+        registry.set_focused("session-window-a", false);
+        assert_eq!(
+            registry.focused_label().as_deref(),
+            Some("session-window-b")
+        );
+
+        // A focused window that closes takes the focus with it
+        registry.unregister("session-window-b");
+        assert_eq!(registry.focused_label(), None);
+    }
+
+    #[test]
+    fn test_window_meta_serialization() {
+        let value = serde_json::to_value(meta("a", Some(Engine::Codex))).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "window_label": "session-window-a",
+                "tab_id": "a",
+                "session_id": null,
+                "project_path": null,
+                "engine": "codex",
+                "busy": false,
+                "zoom": 1.0,
+                "opacity": 1.0,
+                "activity": "idle",
+                "visible": true,
+                "icon": null,
+                "decorated": false,
+                "visible_on_all_workspaces": false,
+                "resizable": true,
+                "maximizable": true,
+                "skip_taskbar": false,
+                "group_id": null,
+                "minimized_by_app": false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_reconcile_prunes_and_adopts() {
+        let registry = WindowRegistry::default();
+        registry.register(meta("a", Some(Engine::Codex)));
+        registry.register(meta("gone", Some(Engine::Claude)));
+
+        let live = || {
+            vec![
+                stub_meta("session-window-a", None),
+                stub_meta("session-window-b", None),
+            ]
+        };
+
+        assert_eq!(
+            registry.reconcile(live()),
+            ReconcileResult {
+                pruned: 1,
+                adopted: 1
+            }
+        );
+        assert_eq!(registry.get("session-window-gone"), None);
+        // Known entries keep their metadata instead of being replaced by the stub
+        assert_eq!(registry.engine_of("session-window-a"), Some(Engine::Codex));
+        assert_eq!(registry.get("session-window-b").unwrap().tab_id, "b");
+
+        // Already in sync: nothing to do
+        assert_eq!(registry.reconcile(live()), ReconcileResult::default());
+    }
+
+    #[test]
+    fn test_stub_meta_prefers_url_info() {
+        let info = SessionWindowInfo {
+            window_label: "session-window-x".to_string(),
+            tab_id: "tab-1".to_string(),
+            session_id: Some("s1".to_string()),
+            project_path: Some("/tmp/p".to_string()),
+            engine: Some(Engine::Gemini),
+        };
+        let stub = stub_meta("session-window-x", Some(info));
+        assert_eq!(stub.tab_id, "tab-1");
+        assert_eq!(stub.engine, Some(Engine::Gemini));
+
+        let stub = stub_meta("session-window-tab-2", None);
+        assert_eq!(stub.tab_id, "tab-2");
+        assert_eq!(stub.engine, None);
+    }
+}
@@ -0,0 +1,76 @@
+/**
+ * Session Window Reload
+ *
+ * Recovers a wedged session window by reloading its page under the same URL,
+ * instead of closing it and losing its state. The window gets a
+ * `window-reloading` event first so it can persist in-memory state.
+ */
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, EventTarget, Manager};
+
+use super::error::{window_not_found, AppError};
+
+/// Event sent to a window right before its page is reloaded
+const WINDOW_RELOADING_EVENT: &str = "window-reloading";
+
+/// Time the frontend gets to persist its state before the reload
+const RELOAD_GRACE: Duration = Duration::from_millis(300);
+
+/// Reloads the page after emptying the Cache Storage the page controls
+const HARD_RELOAD_SCRIPT: &str = "(window.caches \
+    ? caches.keys().then(keys => Promise.all(keys.map(key => caches.delete(key)))) \
+    : Promise.resolve()).finally(() => location.reload())";
+
+/// Reloads a session window's page, keeping its URL
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `hard` - Whether to empty the page's caches first (default: false)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn reload_session_window(
+    app: AppHandle,
+    window_label: String,
+    hard: Option<bool>,
+) -> Result<(), AppError> {
+    reported!(app, "reload_session_window", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        // A wedged page may never handle it; the reload goes ahead regardless
+        if let Err(e) = window.emit_to(
+            EventTarget::webview_window(&window_label),
+            WINDOW_RELOADING_EVENT,
+            &window_label,
+        ) {
+            window_log!(
+                warn,
+                window_label,
+                "Failed to emit {}: {}",
+                WINDOW_RELOADING_EVENT,
+                e
+            );
+        }
+        tokio::time::sleep(RELOAD_GRACE).await;
+
+        let hard = hard.unwrap_or(false);
+        if hard {
+            window.eval(HARD_RELOAD_SCRIPT)
+        } else {
+            window.reload()
+        }
+        .map_err(|e| format!("Failed to reload window: {}", e))?;
+
+        window_log!(
+            info,
+            window_label,
+            "Reloading session window{}",
+            if hard { " (hard)" } else { "" }
+        );
+        Ok(())
+    })
+}
@@ -0,0 +1,247 @@
+/**
+ * Request/Response Between Windows
+ *
+ * Lets one side ask a window for data and await the answer. The request is
+ * emitted with a generated correlation id and the target replies on
+ * `<request_name>-response` (or an event the protocol names) with the same id.
+ */
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, EventTarget, Listener, Manager};
+use tokio::sync::oneshot;
+
+use super::error::{window_not_found, AppError};
+
+/// How long to wait for a response when the caller doesn't say
+const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 5000;
+
+/// Requests still waiting for a response, keyed by correlation id
+static PENDING_REQUESTS: Lazy<Mutex<HashMap<String, oneshot::Sender<serde_json::Value>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Envelope of both the request and the response events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowRequestEnvelope {
+    /// Id tying a response to its request
+    #[serde(alias = "correlationId")]
+    pub correlation_id: String,
+    /// Request or response data
+    #[serde(default)]
+    pub payload: serde_json::Value,
+}
+
+/// Name of the event the target answers on
+fn response_event_name(request_name: &str) -> String {
+    format!("{}-response", request_name)
+}
+
+/// Parses a response event payload.
+///
+/// Replies sent through `emit_to_window` arrive as a JSON string, so the
+/// envelope may be either an object or a string containing the object.
+fn parse_response(raw_payload: &str) -> Option<WindowRequestEnvelope> {
+    let mut value: serde_json::Value = serde_json::from_str(raw_payload).ok()?;
+    if let serde_json::Value::String(inner) = &value {
+        value = serde_json::from_str(inner).ok()?;
+    }
+    serde_json::from_value(value).ok()
+}
+
+fn register_pending(correlation_id: &str) -> oneshot::Receiver<serde_json::Value> {
+    let (tx, rx) = oneshot::channel();
+    if let Ok(mut pending) = PENDING_REQUESTS.lock() {
+        pending.insert(correlation_id.to_string(), tx);
+    }
+    rx
+}
+
+fn take_pending(correlation_id: &str) -> Option<oneshot::Sender<serde_json::Value>> {
+    PENDING_REQUESTS
+        .lock()
+        .ok()
+        .and_then(|mut pending| pending.remove(correlation_id))
+}
+
+/// Hands a response to the request waiting on its correlation id, if any
+fn resolve_pending(response: WindowRequestEnvelope) -> bool {
+    match take_pending(&response.correlation_id) {
+        Some(tx) => tx.send(response.payload).is_ok(),
+        None => false,
+    }
+}
+
+/// Waits for the response and drops the pending entry if none comes in time
+async fn await_response(
+    correlation_id: &str,
+    rx: oneshot::Receiver<serde_json::Value>,
+    timeout: Duration,
+) -> Option<serde_json::Value> {
+    match tokio::time::timeout(timeout, rx).await {
+        Ok(Ok(payload)) => Some(payload),
+        _ => {
+            take_pending(correlation_id);
+            None
+        }
+    }
+}
+
+/// Sends a request to a window and waits for its response.
+///
+/// Shared by the `request_from_window` command and backend callers.
+pub(crate) async fn send_window_request(
+    app: &AppHandle,
+    target_label: &str,
+    request_name: &str,
+    payload: serde_json::Value,
+    timeout: Duration,
+) -> Result<serde_json::Value, String> {
+    send_window_request_answered_on(
+        app,
+        target_label,
+        request_name,
+        &response_event_name(request_name),
+        payload,
+        timeout,
+    )
+    .await
+}
+
+/// Sends a request to a window and waits for the reply on `response_event`
+pub(crate) async fn send_window_request_answered_on(
+    app: &AppHandle,
+    target_label: &str,
+    request_name: &str,
+    response_event: &str,
+    payload: serde_json::Value,
+    timeout: Duration,
+) -> Result<serde_json::Value, String> {
+    if app.get_webview_window(target_label).is_none() {
+        return Err(window_not_found(target_label).to_string());
+    }
+
+    let correlation_id = uuid::Uuid::new_v4().to_string();
+
+    // Register before emitting so a fast reply can't be missed
+    let rx = register_pending(&correlation_id);
+    let listener_id = app.listen(response_event, |event| {
+        if let Some(response) = parse_response(event.payload()) {
+            resolve_pending(response);
+        }
+    });
+
+    let request = WindowRequestEnvelope {
+        correlation_id: correlation_id.clone(),
+        payload,
+    };
+    if let Err(e) = app.emit_to(
+        EventTarget::webview_window(target_label),
+        request_name,
+        &request,
+    ) {
+        app.unlisten(listener_id);
+        take_pending(&correlation_id);
+        return Err(format!("Failed to emit event: {}", e));
+    }
+
+    let response = await_response(&correlation_id, rx, timeout).await;
+    app.unlisten(listener_id);
+
+    response.ok_or_else(|| {
+        format!(
+            "Window {} did not respond to {} within {}ms",
+            target_label,
+            request_name,
+            timeout.as_millis()
+        )
+    })
+}
+
+/// Sends a request to a window and waits for its response
+///
+/// The target receives `request_name` with `{ correlation_id, payload }` and
+/// must answer on `<request_name>-response` with the same correlation_id.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `target_label` - The label of the window to ask
+/// * `request_name` - The request event name
+/// * `payload` - The request payload
+/// * `timeout_ms` - How long to wait for the response (default 5000ms)
+///
+/// # Returns
+/// * `Result<serde_json::Value, AppError>` - The response payload or an error message
+#[tauri::command]
+pub async fn request_from_window(
+    app: AppHandle,
+    target_label: String,
+    request_name: String,
+    payload: serde_json::Value,
+    timeout_ms: Option<u64>,
+) -> Result<serde_json::Value, AppError> {
+    reported!(app, "request_from_window", {
+        let timeout = Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_REQUEST_TIMEOUT_MS));
+        Ok(send_window_request(&app, &target_label, &request_name, payload, timeout).await?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_pending(correlation_id: &str) -> bool {
+        PENDING_REQUESTS
+            .lock()
+            .unwrap()
+            .contains_key(correlation_id)
+    }
+
+    #[test]
+    fn test_parse_response_accepts_object_and_string_payloads() {
+        let object = parse_response(r#"{"correlation_id":"c1","payload":{"n":1}}"#).unwrap();
+        assert_eq!(object.correlation_id, "c1");
+        assert_eq!(object.payload, serde_json::json!({"n": 1}));
+
+        let camel = parse_response(r#"{"correlationId":"c2"}"#).unwrap();
+        assert_eq!(camel.correlation_id, "c2");
+        assert_eq!(camel.payload, serde_json::Value::Null);
+
+        // Reply forwarded as a string by emit_to_window
+        let string = parse_response(r#""{\"correlation_id\":\"c3\",\"payload\":true}""#).unwrap();
+        assert_eq!(string.correlation_id, "c3");
+
+        assert!(parse_response(r#"{"payload":1}"#).is_none());
+        assert!(parse_response("not json").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_response_resolves_matching_request() {
+        let rx = register_pending("test-resolve");
+        assert!(resolve_pending(WindowRequestEnvelope {
+            correlation_id: "test-resolve".to_string(),
+            payload: serde_json::json!("done"),
+        }));
+
+        let response = await_response("test-resolve", rx, Duration::from_millis(100)).await;
+        assert_eq!(response, Some(serde_json::json!("done")));
+        assert!(!is_pending("test-resolve"));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_removes_pending_request() {
+        let rx = register_pending("test-timeout");
+        assert!(is_pending("test-timeout"));
+
+        let response = await_response("test-timeout", rx, Duration::from_millis(10)).await;
+        assert_eq!(response, None);
+        assert!(!is_pending("test-timeout"));
+
+        // A late reply finds nothing to resolve
+        assert!(!resolve_pending(WindowRequestEnvelope {
+            correlation_id: "test-timeout".to_string(),
+            payload: serde_json::Value::Null,
+        }));
+    }
+}
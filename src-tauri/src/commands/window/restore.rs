@@ -0,0 +1,905 @@
+/**
+ * Session Window Restore
+ *
+ * Keeps a snapshot of the detached session windows that are currently open
+ * and recreates them on the next launch. The snapshot is rewritten whenever a
+ * session window is created or closed, so it survives crashes as well, and
+ * brought up to date once more when the app exits.
+ */
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+use crate::utils::config_utils::{load_json_config, save_json_config};
+
+use super::error::AppError;
+use super::registry::{with_registry, WindowMeta};
+use super::{
+    build_session_window, window_state_file, CreateSessionWindowParams, Engine,
+    WindowCreationResult,
+};
+use super::{decorations, desktops, icon};
+
+/// File name of the open-window snapshot inside the app data dir
+const SNAPSHOT_FILE: &str = "session_windows.json";
+
+/// Serializes read-modify-write cycles on the snapshot file
+static SNAPSHOT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Set once the main window starts closing, so the session windows it closes
+/// on the way out stay in the snapshot for the next launch
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+fn default_restore_enabled() -> bool {
+    true
+}
+
+/// On-disk layout of the open-window snapshot
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionWindowSnapshot {
+    /// Whether windows should be recreated on startup (user opt-out)
+    #[serde(default = "default_restore_enabled")]
+    restore_enabled: bool,
+    /// Every session window alive at the last write
+    #[serde(default)]
+    windows: Vec<SavedWindow>,
+}
+
+/// A session window in the snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedWindow {
+    /// Creation params (flattened, so older snapshots of bare params still load)
+    #[serde(flatten)]
+    params: CreateSessionWindowParams,
+    /// When the window last gained focus, in milliseconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_focused_at: Option<u64>,
+    /// Engine icon set with `set_window_icon`, reapplied on restore
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    icon: Option<Engine>,
+    /// Native decorations choice from `set_window_decorations`, reapplied on restore
+    ///
+    /// Stored under its own key: the flattened params already have a
+    /// `decorations` field for the choice made at creation.
+    #[serde(
+        rename = "runtime_decorations",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    decorations: Option<bool>,
+    /// Pin from `set_window_visible_on_all_workspaces`, reapplied on restore
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    all_workspaces: Option<bool>,
+    /// Resize lock from `set_window_resizable`, reapplied on restore
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resizable: Option<bool>,
+    /// Maximize lock from `set_window_maximizable`, reapplied on restore
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    maximizable: Option<bool>,
+    /// Taskbar opt-out from `set_window_skip_taskbar`, reapplied on restore
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    skip_taskbar: Option<bool>,
+}
+
+impl SavedWindow {
+    /// Takes the runtime settings of a live window, turned-off ones included
+    ///
+    /// Settings at their default are left out of the snapshot. Decorations are
+    /// only stored when they differ from the choice made at creation.
+    fn copy_settings_from(&mut self, meta: &WindowMeta) {
+        let created_decorated = self.params.decorations.unwrap_or(false);
+
+        self.icon = meta.icon;
+        self.decorations = (meta.decorated != created_decorated).then_some(meta.decorated);
+        self.all_workspaces = meta.visible_on_all_workspaces.then_some(true);
+        self.resizable = (!meta.resizable).then_some(false);
+        self.maximizable = (!meta.maximizable).then_some(false);
+        self.skip_taskbar = meta.skip_taskbar.then_some(true);
+    }
+}
+
+impl Default for SessionWindowSnapshot {
+    fn default() -> Self {
+        Self {
+            restore_enabled: default_restore_enabled(),
+            windows: Vec::new(),
+        }
+    }
+}
+
+fn load_snapshot(app: &AppHandle) -> Result<SessionWindowSnapshot, String> {
+    let path = window_state_file(app, SNAPSHOT_FILE)?;
+    let _guard = SNAPSHOT_LOCK.lock().map_err(|e| e.to_string())?;
+    load_json_config(&path)
+}
+
+fn update_snapshot<F>(app: &AppHandle, update: F) -> Result<(), String>
+where
+    F: FnOnce(&mut SessionWindowSnapshot),
+{
+    let path = window_state_file(app, SNAPSHOT_FILE)?;
+    let _guard = SNAPSHOT_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let mut snapshot: SessionWindowSnapshot = load_json_config(&path)?;
+    update(&mut snapshot);
+    save_json_config(&snapshot, &path)
+}
+
+/// Records a newly created session window in the snapshot.
+///
+/// The initial prompt is left out, so a restored window doesn't run it again,
+/// and so is the initial state, so it reopens in the state it was closed in.
+pub(super) fn remember_open_window(app: &AppHandle, params: &CreateSessionWindowParams) {
+    let params = CreateSessionWindowParams {
+        initial_prompt: None,
+        initial_state: None,
+        ..params.clone()
+    };
+    let result = update_snapshot(app, |snapshot| {
+        remember_window(&mut snapshot.windows, params, now_millis())
+    });
+
+    if let Err(e) = result {
+        log::warn!("[Window] Failed to record open session window: {}", e);
+    }
+}
+
+/// Puts a window's creation params into the saved windows, marked as just focused
+///
+/// A window restored from the snapshot is remembered again as it is built, so
+/// the settings changed while it was open (icon, decorations, pin, locks,
+/// taskbar) are kept from the entry it replaces.
+fn remember_window(
+    windows: &mut Vec<SavedWindow>,
+    params: CreateSessionWindowParams,
+    focused_at: u64,
+) {
+    let previous = windows
+        .iter()
+        .find(|w| w.params.tab_id == params.tab_id)
+        .cloned();
+    windows.retain(|w| w.params.tab_id != params.tab_id);
+
+    let window = match previous {
+        Some(previous) => SavedWindow {
+            params,
+            last_focused_at: Some(focused_at),
+            ..previous
+        },
+        None => SavedWindow {
+            params,
+            last_focused_at: Some(focused_at),
+            icon: None,
+            decorations: None,
+            all_workspaces: None,
+            resizable: None,
+            maximizable: None,
+            skip_taskbar: None,
+        },
+    };
+    windows.push(window);
+}
+
+/// Applies `update` to the snapshot entry of a tab's window
+fn update_open_window(
+    app: &AppHandle,
+    tab_id: &str,
+    what: &str,
+    mut update: impl FnMut(&mut SavedWindow),
+) {
+    let result = update_snapshot(app, |snapshot| {
+        for window in snapshot
+            .windows
+            .iter_mut()
+            .filter(|w| w.params.tab_id == tab_id)
+        {
+            update(window);
+        }
+    });
+
+    if let Err(e) = result {
+        log::warn!("[Window] Failed to update session window {}: {}", what, e);
+    }
+}
+
+/// Updates the title a session window will be restored with
+pub(super) fn rename_open_window(app: &AppHandle, tab_id: &str, title: &str) {
+    update_open_window(app, tab_id, "title", |window| {
+        window.params.title = title.to_string()
+    });
+}
+
+/// Updates the engine a session window will be restored with
+pub(super) fn set_open_window_engine(app: &AppHandle, tab_id: &str, engine: Engine) {
+    update_open_window(app, tab_id, "engine", |window| {
+        window.params.engine = Some(engine)
+    });
+}
+
+/// Updates the icon a session window will be restored with
+pub(super) fn set_open_window_icon(app: &AppHandle, tab_id: &str, icon: Engine) {
+    update_open_window(app, tab_id, "icon", |window| window.icon = Some(icon));
+}
+
+/// Updates whether a session window will be restored with native decorations
+pub(super) fn set_open_window_decorations(app: &AppHandle, tab_id: &str, decorated: bool) {
+    update_open_window(app, tab_id, "decorations", |window| {
+        window.decorations = Some(decorated)
+    });
+}
+
+/// Updates whether a session window will be restored on all desktops
+pub(super) fn set_open_window_all_workspaces(app: &AppHandle, tab_id: &str, enabled: bool) {
+    update_open_window(app, tab_id, "all workspaces", |window| {
+        window.all_workspaces = Some(enabled)
+    });
+}
+
+/// Updates whether a session window will be restored resizable
+pub(super) fn set_open_window_resizable(app: &AppHandle, tab_id: &str, resizable: bool) {
+    update_open_window(app, tab_id, "resizable", |window| {
+        window.resizable = Some(resizable)
+    });
+}
+
+/// Updates whether a session window will be restored maximizable
+pub(super) fn set_open_window_maximizable(app: &AppHandle, tab_id: &str, maximizable: bool) {
+    update_open_window(app, tab_id, "maximizable", |window| {
+        window.maximizable = Some(maximizable)
+    });
+}
+
+/// Updates whether a session window will be restored without a taskbar entry
+pub(super) fn set_open_window_skip_taskbar(app: &AppHandle, tab_id: &str, skip: bool) {
+    update_open_window(app, tab_id, "skip taskbar", |window| {
+        window.skip_taskbar = Some(skip)
+    });
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Records that a session window gained focus, for the restore order
+pub(super) fn touch_open_window(app: &AppHandle, tab_id: &str) {
+    let focused_at = now_millis();
+    update_open_window(app, tab_id, "focus", |window| {
+        window.last_focused_at = Some(focused_at)
+    });
+}
+
+/// Drops a closed session window from the snapshot.
+///
+/// Skipped while the app is shutting down, since those windows are exactly the
+/// ones that should come back on the next launch.
+pub(super) fn forget_open_window(app: &AppHandle, tab_id: &str) {
+    if is_app_shutting_down() {
+        return;
+    }
+
+    if let Err(e) = update_snapshot(app, |snapshot| {
+        snapshot.windows.retain(|w| w.params.tab_id != tab_id);
+    }) {
+        log::warn!("[Window] Failed to remove closed session window: {}", e);
+    }
+}
+
+/// A live session window as seen by the exit hook
+#[derive(Debug, Clone)]
+pub(super) struct LiveWindow {
+    pub(super) meta: WindowMeta,
+    pub(super) title: String,
+    pub(super) workspace: Option<String>,
+}
+
+/// Brings the saved windows in line with the live ones.
+///
+/// Saved entries pick up what changed while the window was open (session ID,
+/// engine, title, runtime settings); live windows missing from the snapshot
+/// are added.
+fn merge_live_windows(windows: &mut Vec<SavedWindow>, live: &[LiveWindow]) {
+    for window in live {
+        let meta = &window.meta;
+        match windows.iter_mut().find(|w| w.params.tab_id == meta.tab_id) {
+            Some(saved) => {
+                if meta.session_id.is_some() {
+                    saved.params.session_id = meta.session_id.clone();
+                }
+                if meta.engine.is_some() {
+                    saved.params.engine = meta.engine;
+                }
+                saved.params.title = window.title.clone();
+                saved.copy_settings_from(meta);
+            }
+            None => {
+                let mut saved = SavedWindow {
+                    params: CreateSessionWindowParams {
+                        tab_id: meta.tab_id.clone(),
+                        session_id: meta.session_id.clone(),
+                        project_path: meta.project_path.clone(),
+                        title: window.title.clone(),
+                        engine: meta.engine,
+                        workspace: window.workspace.clone(),
+                        ..Default::default()
+                    },
+                    last_focused_at: None,
+                    icon: None,
+                    decorations: None,
+                    all_workspaces: None,
+                    resizable: None,
+                    maximizable: None,
+                    skip_taskbar: None,
+                };
+                saved.copy_settings_from(meta);
+                windows.push(saved);
+            }
+        }
+    }
+}
+
+/// Writes the live session windows into the snapshot, on the way out
+pub(super) fn save_live_windows(app: &AppHandle, live: &[LiveWindow]) -> Result<(), String> {
+    update_snapshot(app, |snapshot| {
+        merge_live_windows(&mut snapshot.windows, live)
+    })
+}
+
+/// Marks the app as shutting down so closing session windows keeps them in the snapshot
+pub(super) fn mark_app_shutting_down() {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+}
+
+/// Whether the main window has started closing the app
+pub(super) fn is_app_shutting_down() -> bool {
+    SHUTTING_DOWN.load(Ordering::SeqCst)
+}
+
+/// Restores the resize and maximize locks of a window
+fn reapply_locks(window: &WebviewWindow, saved: &SavedWindow) -> Result<(), String> {
+    if let Some(resizable) = saved.resizable {
+        window
+            .set_resizable(resizable)
+            .map_err(|e| format!("Failed to set resizable: {}", e))?;
+    }
+    if let Some(maximizable) = saved.maximizable {
+        window
+            .set_maximizable(maximizable)
+            .map_err(|e| format!("Failed to set maximizable: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Gives a restored window the icon, decorations, desktop pin, taskbar entry
+/// and locks it had, in the registry as well
+fn reapply_window_settings(app: &AppHandle, window_label: &str, saved: &SavedWindow) {
+    let Some(window) = app.get_webview_window(window_label) else {
+        return;
+    };
+
+    if let Some(engine) = saved.icon {
+        match icon::apply_engine_icon(app, &window, engine) {
+            Ok(()) => {
+                with_registry(app, |registry| {
+                    registry.update(window_label, |meta| meta.icon = Some(engine))
+                });
+            }
+            Err(e) => window_log!(warn, window_label, "Failed to restore icon: {}", e),
+        }
+    }
+
+    if let Some(decorated) = saved.decorations {
+        match decorations::apply_decorations(app, &window, decorated) {
+            Ok(()) => {
+                with_registry(app, |registry| {
+                    registry.update(window_label, |meta| meta.decorated = decorated)
+                });
+            }
+            Err(e) => window_log!(warn, window_label, "Failed to restore decorations: {}", e),
+        }
+    }
+
+    if let Some(enabled) = saved.all_workspaces {
+        match desktops::apply_visible_on_all_workspaces(&window, enabled) {
+            Ok(()) => {
+                with_registry(app, |registry| {
+                    registry.update(window_label, |meta| {
+                        meta.visible_on_all_workspaces = enabled
+                    })
+                });
+            }
+            Err(e) => window_log!(warn, window_label, "Failed to restore desktop pin: {}", e),
+        }
+    }
+
+    if let Some(skip) = saved.skip_taskbar {
+        match window.set_skip_taskbar(skip) {
+            Ok(()) => {
+                with_registry(app, |registry| {
+                    registry.update(window_label, |meta| meta.skip_taskbar = skip)
+                });
+            }
+            Err(e) => window_log!(warn, window_label, "Failed to restore skip taskbar: {}", e),
+        }
+    }
+
+    match reapply_locks(&window, saved) {
+        Ok(()) => {
+            with_registry(app, |registry| {
+                registry.update(window_label, |meta| {
+                    meta.resizable = saved.resizable.unwrap_or(true);
+                    meta.maximizable = saved.maximizable.unwrap_or(true);
+                })
+            });
+        }
+        Err(e) => window_log!(warn, window_label, "Failed to restore size locks: {}", e),
+    }
+}
+
+/// Keeps only the windows that can still be restored.
+///
+/// A window whose project folder has been deleted or renamed since it was
+/// saved would only fail inside the webview, so it is dropped here.
+fn restorable_windows(windows: Vec<SavedWindow>) -> Vec<SavedWindow> {
+    windows
+        .into_iter()
+        .filter(|SavedWindow { params, .. }| match params.project_path {
+            Some(ref project_path) if !Path::new(project_path).exists() => {
+                log::info!(
+                    "[Window] Skipping restore of tab {}: project path no longer exists: {}",
+                    params.tab_id,
+                    project_path
+                );
+                false
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+/// Orders windows oldest-focused first.
+///
+/// Each new window lands on top of the previous ones, so the window focused
+/// last before shutdown ends up on top. Windows without a focus time go first.
+fn restore_order(mut windows: Vec<SavedWindow>) -> Vec<SavedWindow> {
+    windows.sort_by_key(|window| window.last_focused_at);
+    windows
+}
+
+/// Recreates every session window from the snapshot
+async fn restore_windows(app: &AppHandle) -> Result<Vec<WindowCreationResult>, String> {
+    let mut windows = Vec::new();
+    update_snapshot(app, |snapshot| {
+        if snapshot.restore_enabled {
+            snapshot.windows = restorable_windows(std::mem::take(&mut snapshot.windows));
+            windows = snapshot.windows.clone();
+        } else {
+            // Opted out: start clean and don't keep stale entries around
+            snapshot.windows.clear();
+        }
+    })?;
+
+    let mut results = Vec::with_capacity(windows.len());
+    let mut last_label = None;
+    for saved in restore_order(windows) {
+        match build_session_window(app, &saved.params).await {
+            Ok(result) => {
+                reapply_window_settings(app, &result.window_label, &saved);
+                last_label = Some(result.window_label.clone());
+                results.push(result);
+            }
+            Err(e) => log::warn!(
+                "[Window] Failed to restore session window for tab {}: {}",
+                saved.params.tab_id,
+                e
+            ),
+        }
+    }
+
+    // Windows are revealed as their pages load, which may not be in build
+    // order; the most recently focused one gets the focus back explicitly
+    if let Some(window) = last_label.and_then(|label| app.get_webview_window(&label)) {
+        if let Err(e) = window.set_focus() {
+            window_log!(
+                warn,
+                window.label(),
+                "Failed to focus restored window: {}",
+                e
+            );
+        }
+    }
+
+    if !results.is_empty() {
+        log::info!("[Window] Restored {} session window(s)", results.len());
+    }
+
+    Ok(results)
+}
+
+/// Restores the session windows that were open at the last shutdown.
+///
+/// Called from the app setup hook; runs in the background so startup isn't
+/// blocked on webview creation.
+pub fn restore_session_windows_on_startup(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = restore_windows(&app).await {
+            log::error!("[Window] Failed to restore session windows: {}", e);
+        }
+    });
+}
+
+/// Recreates the session windows recorded in the snapshot
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+///
+/// # Returns
+/// * `Result<Vec<WindowCreationResult>, AppError>` - The restored windows or an error message
+#[tauri::command]
+pub async fn restore_session_windows(
+    app: AppHandle,
+) -> Result<Vec<WindowCreationResult>, AppError> {
+    reported!(app, "restore_session_windows", {
+        Ok(restore_windows(&app).await?)
+    })
+}
+
+/// Gets whether session windows are restored on startup
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+///
+/// # Returns
+/// * `Result<bool, AppError>` - Whether restore is enabled or an error message
+#[tauri::command]
+pub async fn get_session_window_restore_enabled(app: AppHandle) -> Result<bool, AppError> {
+    reported!(app, "get_session_window_restore_enabled", {
+        Ok(load_snapshot(&app)?.restore_enabled)
+    })
+}
+
+/// Enables or disables restoring session windows on startup
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `enabled` - Whether windows should be restored on the next launch
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_session_window_restore_enabled(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), AppError> {
+    reported!(app, "set_session_window_restore_enabled", {
+        update_snapshot(&app, |snapshot| snapshot.restore_enabled = enabled)?;
+        log::info!(
+            "[Window] Session window restore {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Engine;
+    use super::*;
+
+    fn params(tab_id: &str, project_path: Option<String>) -> SavedWindow {
+        SavedWindow {
+            params: CreateSessionWindowParams {
+                tab_id: tab_id.to_string(),
+                session_id: None,
+                project_path,
+                title: format!("Tab {}", tab_id),
+                engine: Some(Engine::Claude),
+                ..Default::default()
+            },
+            last_focused_at: None,
+            icon: None,
+            decorations: None,
+            all_workspaces: None,
+            resizable: None,
+            maximizable: None,
+            skip_taskbar: None,
+        }
+    }
+
+    fn focused_at(tab_id: &str, last_focused_at: Option<u64>) -> SavedWindow {
+        SavedWindow {
+            last_focused_at,
+            ..params(tab_id, None)
+        }
+    }
+
+    #[test]
+    fn test_restorable_windows_skips_missing_project_path() {
+        let project = tempfile::tempdir().unwrap();
+        let existing = project.path().to_string_lossy().to_string();
+        let missing = project.path().join("deleted").to_string_lossy().to_string();
+
+        let kept = restorable_windows(vec![
+            params("a", Some(existing)),
+            params("b", Some(missing)),
+            params("c", None),
+        ]);
+
+        let tab_ids: Vec<&str> = kept.iter().map(|w| w.params.tab_id.as_str()).collect();
+        assert_eq!(tab_ids, vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_snapshot_defaults_to_restore_enabled() {
+        let snapshot: SessionWindowSnapshot = serde_json::from_str(r#"{"windows": []}"#).unwrap();
+        assert!(snapshot.restore_enabled);
+        assert!(SessionWindowSnapshot::default().restore_enabled);
+    }
+
+    #[test]
+    fn test_snapshot_round_trips_params() {
+        let snapshot = SessionWindowSnapshot {
+            restore_enabled: false,
+            windows: vec![params("tab-1", Some("/tmp/project".to_string()))],
+        };
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let loaded: SessionWindowSnapshot = serde_json::from_str(&json).unwrap();
+
+        assert!(!loaded.restore_enabled);
+        assert_eq!(loaded.windows.len(), 1);
+        let loaded = &loaded.windows[0].params;
+        assert_eq!(loaded.tab_id, "tab-1");
+        assert_eq!(loaded.project_path.as_deref(), Some("/tmp/project"));
+        assert_eq!(loaded.engine, Some(Engine::Claude));
+    }
+
+    #[test]
+    fn test_creation_and_runtime_decorations_round_trip_separately() {
+        let mut window = params("tab-1", None);
+        window.params.decorations = Some(true);
+        window.decorations = Some(false);
+        let snapshot = SessionWindowSnapshot {
+            restore_enabled: true,
+            windows: vec![window],
+        };
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let loaded: SessionWindowSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.windows[0].params.decorations, Some(true));
+        assert_eq!(loaded.windows[0].decorations, Some(false));
+
+        // Written again after a reload, it still loads
+        let rewritten = serde_json::to_string(&loaded).unwrap();
+        assert!(serde_json::from_str::<SessionWindowSnapshot>(&rewritten).is_ok());
+    }
+
+    #[test]
+    fn test_snapshot_without_focus_times_still_loads() {
+        let snapshot: SessionWindowSnapshot =
+            serde_json::from_str(r#"{"windows": [{"tab_id": "tab-1", "title": "Tab"}]}"#).unwrap();
+        assert_eq!(snapshot.windows[0].params.tab_id, "tab-1");
+        assert_eq!(snapshot.windows[0].last_focused_at, None);
+    }
+
+    #[test]
+    fn test_merge_live_windows_updates_and_adds() {
+        let mut windows = vec![params("a", None), params("gone", None)];
+        let live = |tab_id: &str, session_id: &str| LiveWindow {
+            meta: WindowMeta::new(
+                &format!("session-window-{}", tab_id),
+                tab_id,
+                Some(session_id.to_string()),
+                None,
+                Some(Engine::Codex),
+            ),
+            title: format!("Live {}", tab_id),
+            workspace: None,
+        };
+
+        merge_live_windows(&mut windows, &[live("a", "s1"), live("new", "s2")]);
+
+        let tab_ids: Vec<&str> = windows.iter().map(|w| w.params.tab_id.as_str()).collect();
+        // Entries of windows not in `live` are left to the close handlers
+        assert_eq!(tab_ids, vec!["a", "gone", "new"]);
+        assert_eq!(windows[0].params.session_id.as_deref(), Some("s1"));
+        assert_eq!(windows[0].params.engine, Some(Engine::Codex));
+        assert_eq!(windows[0].params.title, "Live a");
+        assert_eq!(windows[2].params.session_id.as_deref(), Some("s2"));
+    }
+
+    #[test]
+    fn test_size_locks_survive_save_and_restore() {
+        let mut meta = WindowMeta::new("session-window-a", "a", None, None, None);
+        meta.resizable = false;
+        meta.maximizable = false;
+        let live = LiveWindow {
+            meta,
+            title: "Dashboard".to_string(),
+            workspace: None,
+        };
+
+        // A window the snapshot doesn't know yet, and one it does
+        let mut windows = vec![params("b", None)];
+        let mut locked_b = live.clone();
+        locked_b.meta.tab_id = "b".to_string();
+        merge_live_windows(&mut windows, &[live, locked_b]);
+
+        let snapshot = SessionWindowSnapshot {
+            restore_enabled: true,
+            windows,
+        };
+        let loaded: SessionWindowSnapshot =
+            serde_json::from_str(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+        for window in &loaded.windows {
+            assert_eq!(window.resizable, Some(false));
+            assert_eq!(window.maximizable, Some(false));
+        }
+
+        // Unlocked windows leave the fields out, so older snapshots still load
+        let json = serde_json::to_value(params("c", None)).unwrap();
+        assert!(json.get("resizable").is_none());
+        assert!(json.get("maximizable").is_none());
+    }
+
+    #[test]
+    fn test_skip_taskbar_survives_save_and_restore() {
+        let mut meta = WindowMeta::new("session-window-a", "a", None, None, None);
+        meta.skip_taskbar = true;
+        let live = LiveWindow {
+            meta,
+            title: "Preview".to_string(),
+            workspace: None,
+        };
+
+        let mut windows = vec![params("a", None), params("b", None)];
+        merge_live_windows(&mut windows, &[live]);
+        let snapshot = SessionWindowSnapshot {
+            restore_enabled: true,
+            windows,
+        };
+        let loaded: SessionWindowSnapshot =
+            serde_json::from_str(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+        assert_eq!(loaded.windows[0].skip_taskbar, Some(true));
+        // Windows that keep their taskbar entry leave the field out
+        assert_eq!(loaded.windows[1].skip_taskbar, None);
+        assert!(serde_json::to_value(&loaded.windows[1])
+            .unwrap()
+            .get("skip_taskbar")
+            .is_none());
+    }
+
+    #[test]
+    fn test_merge_live_windows_keeps_desktop_pin() {
+        let mut windows = vec![params("a", None)];
+        let pinned = |tab_id: &str| {
+            let mut meta = WindowMeta::new(
+                &format!("session-window-{}", tab_id),
+                tab_id,
+                None,
+                None,
+                None,
+            );
+            meta.visible_on_all_workspaces = true;
+            LiveWindow {
+                meta,
+                title: tab_id.to_string(),
+                workspace: None,
+            }
+        };
+
+        merge_live_windows(&mut windows, &[pinned("a"), pinned("new")]);
+
+        assert_eq!(windows[0].all_workspaces, Some(true));
+        assert_eq!(windows[1].all_workspaces, Some(true));
+        let json = serde_json::to_value(&windows[1]).unwrap();
+        assert_eq!(json["all_workspaces"], true);
+    }
+
+    /// A live window with every runtime setting away from its default
+    fn customized_meta(tab_id: &str) -> WindowMeta {
+        let mut meta = WindowMeta::new(
+            &format!("session-window-{}", tab_id),
+            tab_id,
+            None,
+            None,
+            None,
+        );
+        meta.icon = Some(Engine::Gemini);
+        meta.decorated = true;
+        meta.visible_on_all_workspaces = true;
+        meta.resizable = false;
+        meta.maximizable = false;
+        meta.skip_taskbar = true;
+        meta
+    }
+
+    fn live(meta: WindowMeta) -> LiveWindow {
+        LiveWindow {
+            title: meta.tab_id.clone(),
+            meta,
+            workspace: None,
+        }
+    }
+
+    #[test]
+    fn test_remembering_a_restored_window_keeps_its_settings() {
+        let mut windows = vec![params("a", None)];
+        merge_live_windows(&mut windows, &[live(customized_meta("a"))]);
+
+        // Restoring builds the window again, which remembers it again
+        let restored = windows[0].params.clone();
+        remember_window(&mut windows, restored, 1_000);
+
+        assert_eq!(windows.len(), 1);
+        let window = &windows[0];
+        assert_eq!(window.last_focused_at, Some(1_000));
+        assert_eq!(window.icon, Some(Engine::Gemini));
+        assert_eq!(window.decorations, Some(true));
+        assert_eq!(window.all_workspaces, Some(true));
+        assert_eq!(window.resizable, Some(false));
+        assert_eq!(window.maximizable, Some(false));
+        assert_eq!(window.skip_taskbar, Some(true));
+
+        // A tab seen for the first time starts from the defaults
+        remember_window(&mut windows, params("b", None).params, 2_000);
+        assert_eq!(windows[1].icon, None);
+        assert_eq!(windows[1].skip_taskbar, None);
+    }
+
+    #[test]
+    fn test_merge_live_windows_copies_every_setting() {
+        let mut windows = vec![params("a", None)];
+        merge_live_windows(&mut windows, &[live(customized_meta("a"))]);
+        assert_eq!(windows[0].decorations, Some(true));
+
+        // Turning everything back off clears the saved settings
+        let reset = WindowMeta::new("session-window-a", "a", None, None, None);
+        merge_live_windows(&mut windows, &[live(reset)]);
+
+        let window = &windows[0];
+        assert_eq!(window.icon, None);
+        assert_eq!(window.decorations, None);
+        assert_eq!(window.all_workspaces, None);
+        assert_eq!(window.resizable, None);
+        assert_eq!(window.maximizable, None);
+        assert_eq!(window.skip_taskbar, None);
+    }
+
+    #[test]
+    fn test_merge_live_windows_stores_decorations_turned_off() {
+        let mut created_native = params("a", None);
+        created_native.params.decorations = Some(true);
+        let mut windows = vec![created_native];
+
+        let frameless = WindowMeta::new("session-window-a", "a", None, None, None);
+        merge_live_windows(&mut windows, &[live(frameless)]);
+
+        assert_eq!(windows[0].decorations, Some(false));
+    }
+
+    #[test]
+    fn test_restore_order_puts_last_focused_on_top() {
+        let order = restore_order(vec![
+            focused_at("b", Some(300)),
+            focused_at("never", None),
+            focused_at("a", Some(900)),
+            focused_at("c", Some(100)),
+        ]);
+
+        // Read from the top of the window stack down, i.e. last created first
+        let stack: Vec<&str> = order
+            .iter()
+            .rev()
+            .map(|w| w.params.tab_id.as_str())
+            .collect();
+        assert_eq!(stack, vec!["a", "b", "c", "never"]);
+    }
+}
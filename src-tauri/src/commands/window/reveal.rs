@@ -0,0 +1,106 @@
+/**
+ * Window Reveal
+ *
+ * Session windows are built hidden so the white webview background never
+ * flashes before the theme has painted. The frontend emits `window-ready`
+ * once it has rendered; if it never does, the window is shown anyway after
+ * a timeout.
+ */
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{Listener, WebviewWindow};
+
+use super::geometry::WindowState;
+use super::lifecycle::event_addressed_to;
+
+/// Event a session window sends once its first frame has rendered
+pub(super) const WINDOW_READY_EVENT: &str = "window-ready";
+
+/// How long a window stays hidden waiting for `window-ready`
+const READY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Whether this caller is the one that reveals the window.
+///
+/// The ready event and the timeout race each other; only the first one wins.
+fn claim_reveal(revealed: &AtomicBool) -> bool {
+    !revealed.swap(true, Ordering::SeqCst)
+}
+
+/// Shows the window and puts it into the state it was asked to open in
+fn reveal(window: &WebviewWindow, state: WindowState) {
+    let label = window.label();
+    if let Err(e) = window.show() {
+        // Most likely closed before it was ever shown
+        window_log!(debug, label, "Failed to show window: {}", e);
+        return;
+    }
+
+    let state_applied = match state {
+        WindowState::Normal => Ok(()),
+        WindowState::Maximized => window.maximize(),
+        WindowState::Minimized => window.minimize(),
+        WindowState::Fullscreen => window.set_fullscreen(true),
+    };
+    if let Err(e) = state_applied {
+        window_log!(warn, label, "Failed to open as {:?}: {}", state, e);
+    }
+
+    // Focus the new window, unless it was asked to stay out of the way
+    if state != WindowState::Minimized {
+        if let Err(e) = window.set_focus() {
+            window_log!(warn, label, "Failed to focus new window: {}", e);
+        }
+    }
+}
+
+/// Shows a hidden session window once its frontend is ready
+///
+/// # Arguments
+/// * `window` - The freshly built, hidden window
+/// * `state` - The state to open the window in
+pub(super) fn reveal_when_ready(window: &WebviewWindow, state: WindowState) {
+    let revealed = Arc::new(AtomicBool::new(false));
+
+    let window_for_ready = window.clone();
+    let revealed_for_ready = revealed.clone();
+    let ready_listener = window.listen(WINDOW_READY_EVENT, move |event| {
+        if !event_addressed_to(event.payload(), window_for_ready.label()) {
+            return;
+        }
+        if claim_reveal(&revealed_for_ready) {
+            reveal(&window_for_ready, state);
+        }
+    });
+
+    let window = window.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(READY_TIMEOUT).await;
+        window.unlisten(ready_listener);
+        if claim_reveal(&revealed) {
+            window_log!(
+                warn,
+                window.label(),
+                "No {} within {:?}, showing window anyway",
+                WINDOW_READY_EVENT,
+                READY_TIMEOUT
+            );
+            reveal(&window, state);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_is_revealed_once() {
+        let revealed = AtomicBool::new(false);
+        // The ready event arrives first...
+        assert!(claim_reveal(&revealed));
+        // ...so neither the fallback timer nor a repeated event reveal it again
+        assert!(!claim_reveal(&revealed));
+        assert!(!claim_reveal(&revealed));
+    }
+}
@@ -0,0 +1,82 @@
+/**
+ * Window Scale Factor
+ *
+ * Gives the frontend the DPI scale factor of a window and tells it when the
+ * factor changes, e.g. when a window is dragged to a monitor with another DPI.
+ */
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, EventTarget, Manager, Window};
+
+use super::error::{window_not_found, AppError};
+
+/// Event sent to a window whose scale factor changed
+const SCALE_FACTOR_CHANGED_EVENT: &str = "scale-factor-changed";
+
+/// Payload of the `scale-factor-changed` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ScaleFactorChangedPayload {
+    window_label: String,
+    scale_factor: f64,
+}
+
+/// Forwards a window's `ScaleFactorChanged` event to its frontend
+pub fn handle_scale_factor_change(window: &Window, scale_factor: f64) {
+    let payload = ScaleFactorChangedPayload {
+        window_label: window.label().to_string(),
+        scale_factor,
+    };
+    if let Err(e) = window.emit_to(
+        EventTarget::webview_window(window.label()),
+        SCALE_FACTOR_CHANGED_EVENT,
+        payload,
+    ) {
+        window_log!(
+            warn,
+            window.label(),
+            "Failed to emit {}: {}",
+            SCALE_FACTOR_CHANGED_EVENT,
+            e
+        );
+    }
+}
+
+/// Gets the DPI scale factor of a window
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+///
+/// # Returns
+/// * `Result<f64, AppError>` - The scale factor (1.0 is 96 DPI) or an error message
+#[tauri::command]
+pub async fn get_window_scale_factor(
+    app: AppHandle,
+    window_label: String,
+) -> Result<f64, AppError> {
+    reported!(app, "get_window_scale_factor", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        window
+            .scale_factor()
+            .map_err(|e| AppError::Other(format!("Failed to get scale factor: {}", e)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_factor_changed_payload() {
+        let payload = ScaleFactorChangedPayload {
+            window_label: "session-window-a".to_string(),
+            scale_factor: 1.5,
+        };
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::json!({ "window_label": "session-window-a", "scale_factor": 1.5 })
+        );
+    }
+}
@@ -0,0 +1,116 @@
+/**
+ * Graceful Shutdown
+ *
+ * Quitting through the OS (Cmd+Q, the taskbar's close action) tears the
+ * windows down without running their close handlers, so nothing would be
+ * saved for the next launch. This hook snapshots the geometry and metadata
+ * of every session window first, bounded by a short timeout so a stuck
+ * disk never holds up the exit.
+ */
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use super::geometry::{self, WindowGeometry, WindowState};
+use super::registry::with_registry;
+use super::restore::{self, LiveWindow};
+use super::{is_session_window_label, parse_session_window_label};
+
+/// Longest the exit waits for the state to be written
+const EXIT_SAVE_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Set once the state has been saved; the exit paths may overlap
+static EXIT_STATE_SAVED: AtomicBool = AtomicBool::new(false);
+
+/// What gets written for one session window
+struct ExitRecord {
+    window: LiveWindow,
+    geometry: Option<(WindowGeometry, WindowState)>,
+}
+
+/// Reads the state of every open session window.
+///
+/// Runs on the calling thread: window getters need the event loop, which is
+/// blocked while the exit waits for the writer.
+fn collect_records(app: &AppHandle) -> Vec<ExitRecord> {
+    app.webview_windows()
+        .into_iter()
+        .filter(|(label, _)| is_session_window_label(label))
+        .filter_map(|(label, window)| {
+            let meta = with_registry(app, |registry| registry.get(&label)).flatten()?;
+            let geometry = geometry::capture_geometry(&window)
+                .ok()
+                .map(|geometry| (geometry, geometry::capture_state(&window)));
+            let title = window.title().unwrap_or_else(|_| meta.tab_id.clone());
+            let workspace = parse_session_window_label(&label)
+                .and_then(|label| label.workspace)
+                .map(str::to_string);
+            Some(ExitRecord {
+                window: LiveWindow {
+                    meta,
+                    title,
+                    workspace,
+                },
+                geometry,
+            })
+        })
+        .collect()
+}
+
+fn write_records(app: &AppHandle, records: Vec<ExitRecord>) {
+    for record in &records {
+        if let Some((geometry, state)) = record.geometry {
+            let meta = &record.window.meta;
+            if let Err(e) = geometry::store_geometry(
+                app,
+                meta.project_path.as_deref(),
+                &meta.tab_id,
+                geometry,
+                state,
+            ) {
+                window_log!(
+                    warn,
+                    record.window.meta.window_label,
+                    "Failed to save geometry on exit: {}",
+                    e
+                );
+            }
+        }
+    }
+
+    let live: Vec<LiveWindow> = records.into_iter().map(|record| record.window).collect();
+    if let Err(e) = restore::save_live_windows(app, &live) {
+        log::warn!("[Window] Failed to save session windows on exit: {}", e);
+    }
+}
+
+/// Saves the state of all session windows before the app exits
+///
+/// Called when the main window closes and when the app is asked to exit;
+/// only the first call does the work. Returns once everything is written or
+/// after `EXIT_SAVE_TIMEOUT`, whichever comes first.
+pub fn persist_windows_on_exit(app: &AppHandle) {
+    // Session windows closed on the way out stay in the snapshot
+    restore::mark_app_shutting_down();
+    if EXIT_STATE_SAVED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let records = collect_records(app);
+    let count = records.len();
+    let (done_tx, done_rx) = mpsc::channel();
+    let app = app.clone();
+    std::thread::spawn(move || {
+        write_records(&app, records);
+        let _ = done_tx.send(());
+    });
+
+    match done_rx.recv_timeout(EXIT_SAVE_TIMEOUT) {
+        Ok(()) => log::info!("[Window] Saved {} session window(s) on exit", count),
+        Err(_) => log::warn!(
+            "[Window] Saving session windows on exit took longer than {:?}, exiting anyway",
+            EXIT_SAVE_TIMEOUT
+        ),
+    }
+}
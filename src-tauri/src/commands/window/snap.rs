@@ -0,0 +1,174 @@
+/**
+ * Window Snapping
+ *
+ * Tiles a window to a half or a quadrant of the monitor it is on, for
+ * side-by-side comparisons. Uses the monitor's work area, so the taskbar or
+ * dock is never covered.
+ */
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, LogicalPosition, LogicalSize, Manager, WebviewWindow};
+
+use super::error::{window_not_found, AppError};
+use super::geometry::{self, WindowGeometry};
+
+/// Part of the work area a window can be snapped to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapPosition {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Returns the rectangle of `area` a window snapped to `position` covers
+fn snap_rect(area: &WindowGeometry, position: SnapPosition) -> WindowGeometry {
+    let half_width = area.width / 2.0;
+    let half_height = area.height / 2.0;
+
+    // (column offset, row offset, width, height)
+    let (dx, dy, width, height) = match position {
+        SnapPosition::Left => (0.0, 0.0, half_width, area.height),
+        SnapPosition::Right => (half_width, 0.0, half_width, area.height),
+        SnapPosition::Top => (0.0, 0.0, area.width, half_height),
+        SnapPosition::Bottom => (0.0, half_height, area.width, half_height),
+        SnapPosition::TopLeft => (0.0, 0.0, half_width, half_height),
+        SnapPosition::TopRight => (half_width, 0.0, half_width, half_height),
+        SnapPosition::BottomLeft => (0.0, half_height, half_width, half_height),
+        SnapPosition::BottomRight => (half_width, half_height, half_width, half_height),
+    };
+
+    WindowGeometry {
+        x: area.x + dx,
+        y: area.y + dy,
+        width,
+        height,
+    }
+}
+
+/// Moves and resizes a window to exactly cover `target`
+///
+/// Leaves fullscreen and maximized first, since either would ignore the new size.
+pub(super) fn place_window(window: &WebviewWindow, target: &WindowGeometry) -> Result<(), String> {
+    if window.is_fullscreen().unwrap_or(false) {
+        window
+            .set_fullscreen(false)
+            .map_err(|e| format!("Failed to exit fullscreen: {}", e))?;
+    }
+    if window.is_maximized().unwrap_or(false) {
+        window
+            .unmaximize()
+            .map_err(|e| format!("Failed to unmaximize window: {}", e))?;
+    }
+
+    window
+        .set_position(LogicalPosition::new(target.x, target.y))
+        .map_err(|e| format!("Failed to set window position: {}", e))?;
+    window
+        .set_size(LogicalSize::new(target.width, target.height))
+        .map_err(|e| format!("Failed to set window size: {}", e))
+}
+
+/// Snaps a window to a half or quadrant of its current monitor
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `position` - Where to snap the window
+///
+/// # Returns
+/// * `Result<WindowGeometry, AppError>` - The new window geometry or an error message
+#[tauri::command]
+pub async fn snap_window(
+    app: AppHandle,
+    window_label: String,
+    position: SnapPosition,
+) -> Result<WindowGeometry, AppError> {
+    reported!(app, "snap_window", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        let monitor = window
+            .current_monitor()
+            .map_err(|e| format!("Failed to get current monitor: {}", e))?
+            .ok_or_else(|| format!("Window {} is not on any detectable monitor", window_label))?;
+        let target = snap_rect(&geometry::work_area_of(&monitor).area, position);
+        place_window(&window, &target)?;
+
+        window_log!(info, window_label, "Snapped to {:?}", position);
+        Ok(target)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1920x1080 screen with a 40px taskbar at the bottom, offset like a second monitor
+    const AREA: WindowGeometry = WindowGeometry {
+        x: 1920.0,
+        y: 0.0,
+        width: 1920.0,
+        height: 1040.0,
+    };
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> WindowGeometry {
+        WindowGeometry {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_snap_rect_halves() {
+        assert_eq!(
+            snap_rect(&AREA, SnapPosition::Left),
+            rect(1920.0, 0.0, 960.0, 1040.0)
+        );
+        assert_eq!(
+            snap_rect(&AREA, SnapPosition::Right),
+            rect(2880.0, 0.0, 960.0, 1040.0)
+        );
+        assert_eq!(
+            snap_rect(&AREA, SnapPosition::Top),
+            rect(1920.0, 0.0, 1920.0, 520.0)
+        );
+        assert_eq!(
+            snap_rect(&AREA, SnapPosition::Bottom),
+            rect(1920.0, 520.0, 1920.0, 520.0)
+        );
+    }
+
+    #[test]
+    fn test_snap_rect_quadrants_stay_in_work_area() {
+        assert_eq!(
+            snap_rect(&AREA, SnapPosition::TopLeft),
+            rect(1920.0, 0.0, 960.0, 520.0)
+        );
+        assert_eq!(
+            snap_rect(&AREA, SnapPosition::BottomRight),
+            rect(2880.0, 520.0, 960.0, 520.0)
+        );
+        // Nothing reaches into the taskbar below the work area
+        for position in [SnapPosition::BottomLeft, SnapPosition::BottomRight] {
+            let snapped = snap_rect(&AREA, position);
+            assert_eq!(snapped.y + snapped.height, AREA.y + AREA.height);
+        }
+    }
+
+    #[test]
+    fn test_snap_position_serde() {
+        assert_eq!(
+            serde_json::from_str::<SnapPosition>("\"top_left\"").unwrap(),
+            SnapPosition::TopLeft
+        );
+        assert!(serde_json::from_str::<SnapPosition>("\"center\"").is_err());
+    }
+}
@@ -0,0 +1,105 @@
+/**
+ * Session Snapshot Handoff
+ *
+ * Carries the UI state of a session (scroll position, draft input, expanded
+ * panels, ...) across detach and reattach so the session resumes where it
+ * was instead of reloading. The snapshot is opaque JSON owned by the
+ * frontend; it is kept in the registry rather than in the URL, and the new
+ * window takes it with `take_session_snapshot` once its page has mounted.
+ * Pulling it rather than pushing it means it can't arrive before the lazily
+ * loaded session page listens for it.
+ */
+use std::time::Duration;
+use tauri::AppHandle;
+
+use super::error::AppError;
+use super::registry::{with_registry, WindowRegistry};
+use super::request::send_window_request;
+
+/// Request a session window answers with its current snapshot
+const SNAPSHOT_REQUEST: &str = "session-snapshot-request";
+
+/// How long reattach waits for the window's snapshot before going without
+const SNAPSHOT_REQUEST_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Takes the snapshot a window was opened with.
+///
+/// The snapshot is handed out once; a later call (e.g. after a reload) must
+/// not restore stale state.
+fn take_snapshot(registry: &WindowRegistry, window_label: &str) -> Option<String> {
+    registry.take_session_snapshot(window_label)
+}
+
+/// Takes the session snapshot a window was opened with (session windows only)
+///
+/// Returns null when the window was opened without one, or already took it.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the calling window
+///
+/// # Returns
+/// * `Result<Option<String>, AppError>` - The snapshot JSON, if any
+#[tauri::command]
+pub async fn take_session_snapshot(
+    app: AppHandle,
+    window_label: String,
+) -> Result<Option<String>, AppError> {
+    reported!(app, "take_session_snapshot", {
+        let snapshot =
+            with_registry(&app, |registry| take_snapshot(registry, &window_label)).flatten();
+        if snapshot.is_some() {
+            window_log!(debug, window_label, "Session snapshot delivered");
+        }
+        Ok(snapshot)
+    })
+}
+
+/// Asks a session window for its current snapshot
+///
+/// Returns None when the window doesn't answer in time; the caller carries
+/// on without one.
+pub(super) async fn request_snapshot(app: &AppHandle, window_label: &str) -> Option<String> {
+    match send_window_request(
+        app,
+        window_label,
+        SNAPSHOT_REQUEST,
+        serde_json::Value::Null,
+        SNAPSHOT_REQUEST_TIMEOUT,
+    )
+    .await
+    {
+        Ok(serde_json::Value::Null) => None,
+        Ok(serde_json::Value::String(snapshot)) => Some(snapshot),
+        Ok(snapshot) => Some(snapshot.to_string()),
+        Err(e) => {
+            window_log!(warn, window_label, "No session snapshot: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::registry::WindowMeta;
+    use super::*;
+
+    #[test]
+    fn test_snapshot_stored_at_create_is_taken_once() {
+        let registry = WindowRegistry::default();
+        let label = "session-window-a";
+        registry.register(WindowMeta {
+            session_snapshot: Some(r#"{"draft":"hello","scroll":420}"#.to_string()),
+            ..WindowMeta::new(label, "a", None, None, None)
+        });
+
+        // Another window can't take it
+        assert_eq!(take_snapshot(&registry, "session-window-b"), None);
+        assert_eq!(
+            take_snapshot(&registry, label).as_deref(),
+            Some(r#"{"draft":"hello","scroll":420}"#)
+        );
+        // Handed out once: a reload doesn't restore it again
+        assert_eq!(take_snapshot(&registry, label), None);
+    }
+}
@@ -0,0 +1,238 @@
+/**
+ * Soft Close
+ *
+ * Closes a session window without losing what the user had going in it.
+ * The window is asked to persist its state (unsent drafts, scroll position,
+ * ...) first; the snapshot it acknowledges with is kept per tab, and the next
+ * window opened for that tab gets it through the regular snapshot handoff.
+ *
+ * A window that doesn't acknowledge in time is closed anyway.
+ */
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::utils::config_utils::{load_json_config, save_json_config};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+use super::request::send_window_request_answered_on;
+use super::{close_and_unregister, logging, tab_id_from_label, window_state_file};
+
+/// Request asking a window to hand over its state before closing
+const PERSIST_STATE_REQUEST: &str = "persist-state";
+
+/// Event the window acknowledges the request on, with its snapshot
+const STATE_PERSISTED_EVENT: &str = "state-persisted";
+
+/// How long to wait for the acknowledgement before closing anyway
+const PERSIST_STATE_TIMEOUT: Duration = Duration::from_millis(2000);
+
+/// File name of the saved states inside the app data dir
+const SAVED_STATE_FILE: &str = "session_window_state.json";
+
+/// Serializes read-modify-write cycles on the saved-state file
+static SAVED_STATE_LOCK: Mutex<()> = Mutex::new(());
+
+/// On-disk layout of the saved states
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedStateStore {
+    /// Opaque snapshot JSON keyed by tab_id
+    #[serde(default)]
+    tabs: HashMap<String, String>,
+}
+
+fn update_store(app: &AppHandle, update: impl FnOnce(&mut SavedStateStore)) -> Result<(), String> {
+    let path = window_state_file(app, SAVED_STATE_FILE)?;
+    let _guard = SAVED_STATE_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let mut store: SavedStateStore = load_json_config(&path)?;
+    update(&mut store);
+    save_json_config(&store, &path)
+}
+
+fn store_saved_state(app: &AppHandle, tab_id: &str, snapshot: String) -> Result<(), String> {
+    update_store(app, |store| {
+        store.tabs.insert(tab_id.to_string(), snapshot);
+    })
+}
+
+/// State saved by the soft close of a tab's window, if any
+pub(super) fn load_saved_state(app: &AppHandle, tab_id: &str) -> Option<String> {
+    let path = window_state_file(app, SAVED_STATE_FILE).ok()?;
+    let _guard = SAVED_STATE_LOCK.lock().ok()?;
+
+    match load_json_config::<SavedStateStore>(&path) {
+        Ok(mut store) => store.tabs.remove(tab_id),
+        Err(e) => {
+            log::warn!("[Window] Failed to load saved session state: {}", e);
+            None
+        }
+    }
+}
+
+/// Drops a tab's saved state once a new window has taken it over
+pub(super) fn forget_saved_state(app: &AppHandle, tab_id: &str) {
+    if let Err(e) = update_store(app, |store| {
+        store.tabs.remove(tab_id);
+    }) {
+        log::warn!("[Window] Failed to remove saved session state: {}", e);
+    }
+}
+
+/// The snapshot in an acknowledgement; a window with nothing to keep sends null
+fn acked_snapshot(payload: serde_json::Value) -> Option<String> {
+    match payload {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(snapshot) => Some(snapshot),
+        snapshot => Some(snapshot.to_string()),
+    }
+}
+
+/// Waits for the window's state, stores it and closes the window
+///
+/// The window is closed whether or not its state arrived. Returns whether
+/// the state was captured.
+async fn persist_then_close(
+    window_label: &str,
+    persisted: impl Future<Output = Result<serde_json::Value, String>>,
+    store: impl FnOnce(String) -> Result<(), String>,
+    close: impl FnOnce() -> Result<(), String>,
+) -> Result<bool, String> {
+    let captured = match persisted.await {
+        Ok(payload) => match acked_snapshot(payload) {
+            Some(snapshot) => match store(snapshot) {
+                Ok(()) => true,
+                Err(e) => {
+                    window_log!(warn, window_label, "Failed to save session state: {}", e);
+                    false
+                }
+            },
+            // Acknowledged with nothing worth keeping
+            None => true,
+        },
+        Err(e) => {
+            window_log!(
+                warn,
+                window_label,
+                "Closing without saving session state: {}",
+                e
+            );
+            false
+        }
+    };
+    close()?;
+    Ok(captured)
+}
+
+/// Closes a session window after saving its state
+///
+/// Unlike `close_session_window`, the window is first sent `persist-state`
+/// and its `state-persisted` acknowledgement is awaited (2 s at most). The
+/// snapshot it carries is kept for the tab and handed to the next window
+/// opened for it, so unsent drafts and the scroll position come back. On
+/// timeout the window is closed anyway.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the session window
+///
+/// # Returns
+/// * `Result<bool, AppError>` - Whether the state was captured, or an error message
+#[tauri::command]
+pub async fn close_session_window_saving(
+    app: AppHandle,
+    window_label: String,
+) -> Result<bool, AppError> {
+    reported!(app, "close_session_window_saving", {
+        let _scope = logging::LogScope::new("close_session_window_saving", &window_label);
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+        let tab_id = with_registry(&app, |registry| registry.get(&window_label))
+            .flatten()
+            .map(|meta| meta.tab_id)
+            .or_else(|| tab_id_from_label(&window_label).map(str::to_string))
+            .ok_or_else(|| {
+                AppError::InvalidParam(format!("Not a session window: {}", window_label))
+            })?;
+
+        let persisted = send_window_request_answered_on(
+            &app,
+            &window_label,
+            PERSIST_STATE_REQUEST,
+            STATE_PERSISTED_EVENT,
+            serde_json::Value::Null,
+            PERSIST_STATE_TIMEOUT,
+        );
+        Ok(persist_then_close(
+            &window_label,
+            persisted,
+            |snapshot| store_saved_state(&app, &tab_id, snapshot),
+            || close_and_unregister(&app, &window),
+        )
+        .await?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[tokio::test]
+    async fn test_acked_state_is_stored_before_closing() {
+        let calls = RefCell::new(Vec::new());
+        let captured = persist_then_close(
+            "session-window-a",
+            async { Ok(serde_json::json!({ "draft": "hello", "scroll": 420 })) },
+            |snapshot| {
+                calls.borrow_mut().push(format!("store {}", snapshot));
+                Ok(())
+            },
+            || {
+                calls.borrow_mut().push("close".to_string());
+                Ok(())
+            },
+        )
+        .await;
+
+        assert_eq!(captured, Ok(true));
+        assert_eq!(
+            *calls.borrow(),
+            vec![
+                r#"store {"draft":"hello","scroll":420}"#.to_string(),
+                "close".to_string()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_window_closes_without_state() {
+        let calls = RefCell::new(Vec::new());
+        let captured = persist_then_close(
+            "session-window-a",
+            async {
+                Err(
+                    "Window session-window-a did not respond to persist-state within 2000ms"
+                        .to_string(),
+                )
+            },
+            |_| {
+                calls.borrow_mut().push("store");
+                Ok(())
+            },
+            || {
+                calls.borrow_mut().push("close");
+                Ok(())
+            },
+        )
+        .await;
+
+        assert_eq!(captured, Ok(false));
+        assert_eq!(*calls.borrow(), vec!["close"]);
+    }
+}
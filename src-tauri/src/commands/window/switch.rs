@@ -0,0 +1,205 @@
+/**
+ * Engine Switch In Place
+ *
+ * Moves a session window to another engine without closing it, so it keeps
+ * its geometry, focus and monitor. The registry and the restore snapshot
+ * take the new engine right away; the window's frontend is told through
+ * `engine-switch`, starts a session on the new engine and swaps its URL for
+ * the rebuilt one, so a reload or restore opens on the new engine too.
+ */
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, EventTarget, Manager};
+
+use super::error::{window_not_found, AppError};
+use super::registry::WindowRegistry;
+use super::{build_url, restore, tab_id_from_label, titlebar, Engine};
+
+/// Event sent to a session window that switches engines
+const ENGINE_SWITCH_EVENT: &str = "engine-switch";
+
+/// Query keys dropped on a switch: the session belongs to the old engine, and
+/// the initial prompt already ran
+const STALE_QUERY_KEYS: [&str; 3] = ["session_id", "initial_prompt", "initial_prompt_event"];
+
+/// Payload of the `engine-switch` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct EngineSwitchPayload {
+    window_label: String,
+    engine: Engine,
+    previous_engine: Option<Engine>,
+    /// The window's URL for the new engine, for `history.replaceState`
+    url: String,
+}
+
+/// Rebuilds a session window's URL for another engine
+///
+/// Everything else in the query (tab, project, extra params) is kept.
+fn switched_engine_url(url: &tauri::Url, engine: Engine) -> String {
+    let engine = engine.to_string();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| key != "engine" && !STALE_QUERY_KEYS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    build_url(
+        pairs
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .chain([("engine", engine.as_str())]),
+    )
+}
+
+/// Moves a registered window to `engine`
+///
+/// Returns the previous engine, or None if the window already runs on it.
+/// A busy window is left alone, like a busy window's close is held back.
+fn switch_registered_engine(
+    registry: &WindowRegistry,
+    window_label: &str,
+    engine: Engine,
+) -> Result<Option<Option<Engine>>, AppError> {
+    let meta = registry
+        .get(window_label)
+        .ok_or_else(|| window_not_found(window_label))?;
+    if meta.busy {
+        return Err(AppError::Other(format!(
+            "Window {} is busy; switch engines once the run has finished",
+            window_label
+        )));
+    }
+    if meta.engine == Some(engine) {
+        return Ok(None);
+    }
+    registry.update(window_label, |meta| meta.engine = Some(engine));
+    Ok(Some(meta.engine))
+}
+
+/// Switches a session window to another engine without recreating it
+///
+/// Refused while a run is in progress in the window.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the session window
+/// * `engine` - The engine to switch to
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn switch_window_engine(
+    app: AppHandle,
+    window_label: String,
+    engine: Engine,
+) -> Result<(), AppError> {
+    reported!(app, "switch_window_engine", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+        let url = window
+            .url()
+            .map_err(|e| format!("Failed to get window URL: {}", e))?;
+
+        let registry = app
+            .try_state::<WindowRegistry>()
+            .ok_or_else(|| window_not_found(&window_label))?;
+        let Some(previous_engine) = switch_registered_engine(&registry, &window_label, engine)?
+        else {
+            return Ok(());
+        };
+
+        if let Some(tab_id) = tab_id_from_label(&window_label) {
+            restore::set_open_window_engine(&app, tab_id, engine);
+        }
+        titlebar::apply_current_theme(&app, &window);
+
+        let payload = EngineSwitchPayload {
+            window_label: window_label.clone(),
+            engine,
+            previous_engine,
+            url: switched_engine_url(&url, engine),
+        };
+        window
+            .emit_to(
+                EventTarget::webview_window(&window_label),
+                ENGINE_SWITCH_EVENT,
+                payload,
+            )
+            .map_err(|e| AppError::Emit(format!("Failed to emit event: {}", e)))?;
+
+        window_log!(info, window_label, "Switched to {} in place", engine);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::window::registry::WindowMeta;
+
+    fn registry_with(engine: Engine) -> WindowRegistry {
+        let registry = WindowRegistry::default();
+        registry.register(WindowMeta::new(
+            "session-window-a",
+            "a",
+            Some("claude-session".to_string()),
+            None,
+            Some(engine),
+        ));
+        registry
+    }
+
+    #[test]
+    fn test_switch_updates_registry_and_emits_payload() {
+        let registry = registry_with(Engine::Claude);
+        let previous = switch_registered_engine(&registry, "session-window-a", Engine::Codex)
+            .unwrap()
+            .expect("switch to report an event");
+        assert_eq!(previous, Some(Engine::Claude));
+        assert_eq!(registry.engine_of("session-window-a"), Some(Engine::Codex));
+
+        let url = tauri::Url::parse(
+            "tauri://localhost/?window=session&tab_id=a&session_id=s1&project_path=%2Fp&engine=claude&theme=dark",
+        )
+        .unwrap();
+        let payload = EngineSwitchPayload {
+            window_label: "session-window-a".to_string(),
+            engine: Engine::Codex,
+            previous_engine: previous,
+            url: switched_engine_url(&url, Engine::Codex),
+        };
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::json!({
+                "window_label": "session-window-a",
+                "engine": "codex",
+                "previous_engine": "claude",
+                "url": "/?window=session&tab_id=a&project_path=%2Fp&theme=dark&engine=codex"
+            })
+        );
+
+        // Already on the engine: nothing to tell the window
+        assert_eq!(
+            switch_registered_engine(&registry, "session-window-a", Engine::Codex),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_busy_window_blocks_switch() {
+        let registry = registry_with(Engine::Claude);
+        registry.update("session-window-a", |meta| meta.busy = true);
+
+        assert!(switch_registered_engine(&registry, "session-window-a", Engine::Gemini).is_err());
+        assert_eq!(registry.engine_of("session-window-a"), Some(Engine::Claude));
+        let error =
+            switch_registered_engine(&registry, "session-window-gone", Engine::Gemini).unwrap_err();
+        assert_eq!(
+            serde_json::to_value(error).unwrap(),
+            serde_json::json!({
+                "kind": "not_found",
+                "message": "Window not found: session-window-gone",
+                "code": "window_not_found"
+            })
+        );
+    }
+}
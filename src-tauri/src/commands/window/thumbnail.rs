@@ -0,0 +1,212 @@
+/**
+ * Window Thumbnails
+ *
+ * Small previews of session windows for a visual window switcher.
+ *
+ * Tauri has no webview screenshot API, and the native capture APIs don't
+ * cover every platform (PrintWindow on Windows misses GPU-composited
+ * webviews, macOS needs the screen recording permission, Wayland offers no
+ * per-window capture at all). So the window renders itself: it is asked for
+ * a PNG data URL of its page through `window-thumbnail-request`, and the
+ * backend downscales and caches the result.
+ *
+ * Hidden and minimized windows can't paint, so they get the thumbnail
+ * cached when they last lost focus.
+ */
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use image::imageops::FilterType;
+use std::io::Cursor;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+use super::request::send_window_request;
+
+/// Request a session window answers with a PNG data URL of its page
+const THUMBNAIL_REQUEST: &str = "window-thumbnail-request";
+
+/// How long to wait for the window to render itself
+const THUMBNAIL_REQUEST_TIMEOUT: Duration = Duration::from_millis(2000);
+
+/// Width the cached thumbnail is kept at; requests downscale from it
+const CACHED_THUMBNAIL_WIDTH: u32 = 640;
+
+const PNG_DATA_URL_PREFIX: &str = "data:image/png;base64,";
+
+/// Decodes a `data:image/...;base64,` URL into the image bytes
+fn decode_data_url(data_url: &str) -> Result<Vec<u8>, String> {
+    let (header, data) = data_url
+        .split_once(',')
+        .ok_or_else(|| "Thumbnail is not a data URL".to_string())?;
+    if !header.starts_with("data:image/") || !header.ends_with(";base64") {
+        return Err(format!("Unsupported thumbnail format: {}", header));
+    }
+    STANDARD
+        .decode(data)
+        .map_err(|e| format!("Invalid thumbnail data: {}", e))
+}
+
+/// Scales an image down to at most `max_width`, keeping its aspect ratio,
+/// and returns it as a PNG data URL. Smaller images keep their size.
+fn downscale_to_data_url(bytes: &[u8], max_width: u32) -> Result<String, String> {
+    let image =
+        image::load_from_memory(bytes).map_err(|e| format!("Invalid thumbnail image: {}", e))?;
+    let image = if image.width() > max_width {
+        let height = (u64::from(image.height()) * u64::from(max_width) / u64::from(image.width()))
+            .max(1) as u32;
+        image.resize_exact(max_width, height, FilterType::Triangle)
+    } else {
+        image
+    };
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode thumbnail: {}", e))?;
+    Ok(format!("{}{}", PNG_DATA_URL_PREFIX, STANDARD.encode(png)))
+}
+
+/// Whether a window can paint a fresh thumbnail right now
+fn can_render(window: &tauri::WebviewWindow) -> bool {
+    window.is_visible().unwrap_or(false) && !window.is_minimized().unwrap_or(true)
+}
+
+/// Asks a window to render itself and caches the result
+///
+/// Returns the cached data URL, at `CACHED_THUMBNAIL_WIDTH` at most.
+async fn capture_and_cache(app: &AppHandle, window_label: &str) -> Result<String, String> {
+    let response = send_window_request(
+        app,
+        window_label,
+        THUMBNAIL_REQUEST,
+        serde_json::json!({ "max_width": CACHED_THUMBNAIL_WIDTH }),
+        THUMBNAIL_REQUEST_TIMEOUT,
+    )
+    .await?;
+    let data_url = response
+        .as_str()
+        .ok_or_else(|| "Window sent no thumbnail".to_string())?;
+    let thumbnail = downscale_to_data_url(&decode_data_url(data_url)?, CACHED_THUMBNAIL_WIDTH)?;
+
+    with_registry(app, |registry| {
+        registry.update(window_label, |meta| {
+            meta.thumbnail = Some(thumbnail.clone())
+        })
+    });
+    Ok(thumbnail)
+}
+
+/// Refreshes the cached thumbnail of a window that just lost focus
+///
+/// Runs in the background; a window that doesn't answer keeps its old one.
+pub(super) fn refresh_thumbnail_on_blur(app: &AppHandle, window_label: &str) {
+    let app = app.clone();
+    let window_label = window_label.to_string();
+    tauri::async_runtime::spawn(async move {
+        match app.get_webview_window(&window_label) {
+            Some(window) if can_render(&window) => {}
+            _ => return,
+        }
+        if let Err(e) = capture_and_cache(&app, &window_label).await {
+            window_log!(debug, window_label, "Thumbnail not refreshed: {}", e);
+        }
+    });
+}
+
+/// Captures a thumbnail of a session window
+///
+/// Hidden and minimized windows return the thumbnail cached when they last
+/// lost focus, or an error if there is none.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the session window
+/// * `max_width` - Largest width of the thumbnail in pixels
+///
+/// # Returns
+/// * `Result<String, AppError>` - The thumbnail as a PNG data URL or an error message
+#[tauri::command]
+pub async fn capture_window_thumbnail(
+    app: AppHandle,
+    window_label: String,
+    max_width: u32,
+) -> Result<String, AppError> {
+    reported!(app, "capture_window_thumbnail", {
+        if max_width == 0 {
+            return Err(AppError::InvalidParam(
+                "Invalid max_width: must be greater than 0".to_string(),
+            ));
+        }
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        let cached = || {
+            with_registry(&app, |registry| registry.get(&window_label))
+                .flatten()
+                .and_then(|meta| meta.thumbnail)
+        };
+        let thumbnail = if can_render(&window) {
+            match capture_and_cache(&app, &window_label).await {
+                Ok(thumbnail) => thumbnail,
+                Err(e) => {
+                    window_log!(warn, window_label, "Capture failed, using cache: {}", e);
+                    cached().ok_or(AppError::Other(e))?
+                }
+            }
+        } else {
+            cached().ok_or_else(|| {
+                AppError::Other(format!(
+                    "Window {} is hidden or minimized and has no cached thumbnail",
+                    window_label
+                ))
+            })?
+        };
+
+        Ok(downscale_to_data_url(
+            &decode_data_url(&thumbnail)?,
+            max_width,
+        )?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = image::RgbaImage::from_pixel(width, height, image::Rgba([40, 40, 48, 255]));
+        let mut bytes = Vec::new();
+        image::DynamicImage::ImageRgba8(image)
+            .write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_thumbnail_downscales_keeping_aspect_ratio() {
+        let data_url = downscale_to_data_url(&png(1000, 700), 200).unwrap();
+        assert!(data_url.starts_with(PNG_DATA_URL_PREFIX));
+
+        let thumbnail = image::load_from_memory(&decode_data_url(&data_url).unwrap()).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (200, 140));
+
+        // Already small enough: left as is
+        let small = downscale_to_data_url(&png(120, 80), 200).unwrap();
+        let small = image::load_from_memory(&decode_data_url(&small).unwrap()).unwrap();
+        assert_eq!((small.width(), small.height()), (120, 80));
+    }
+
+    #[test]
+    fn test_decode_data_url_rejects_other_formats() {
+        assert!(decode_data_url("not a data url").is_err());
+        assert!(decode_data_url("data:text/plain;base64,aGVsbG8=").is_err());
+        assert!(decode_data_url("data:image/png,raw").is_err());
+        assert_eq!(
+            decode_data_url("data:image/png;base64,aGVsbG8=").unwrap(),
+            b"hello"
+        );
+    }
+}
@@ -0,0 +1,838 @@
+/**
+ * Title Bar Theming
+ *
+ * Keeps the native title bar of every window in sync with the app theme.
+ * Windows uses the DWM caption color, macOS tints the NSWindow background
+ * behind a transparent title bar, and Linux hands the color to the
+ * frontend's custom title bar through a `titlebar-theme` event.
+ *
+ * OS dark-mode switches are followed automatically unless the frontend has
+ * pinned a theme; windows showing a custom color keep it. Theme changes
+ * arriving in quick succession are coalesced into a single pass over the
+ * windows, using the last one.
+ */
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+use super::Engine;
+
+/// Base caption colors in COLORREF format (0x00BBGGRR)
+/// Dark theme: Medium gray rgb(48, 48, 52) -> 0x00343030
+/// Light theme: Near white rgb(250, 250, 252) -> 0x00FCFAFA
+const DARK_CAPTION_COLOR: u32 = 0x00343030;
+const LIGHT_CAPTION_COLOR: u32 = 0x00FCFAFA;
+
+/// How strongly the engine accent is mixed into the base color, in percent
+const ENGINE_ACCENT_WEIGHT: u32 = 12;
+
+/// Colors set through `set_titlebar_color`, overriding the theme
+#[derive(Debug, Clone, Default)]
+struct CustomColors {
+    /// Color set for all windows, also used for windows opened later
+    all: Option<u32>,
+    /// Colors set for single windows, by label
+    windows: HashMap<String, u32>,
+}
+
+impl CustomColors {
+    /// Whether a window shows a custom color rather than the theme
+    fn covers(&self, window_label: &str) -> bool {
+        self.all.is_some() || self.windows.contains_key(window_label)
+    }
+}
+
+static CUSTOM_COLORS: Lazy<Mutex<CustomColors>> = Lazy::new(Default::default);
+
+/// Whether the frontend pinned its theme, so OS theme switches are ignored
+static THEME_PINNED: AtomicBool = AtomicBool::new(false);
+
+/// OS theme last seen (true = dark), to handle each switch once
+static SYSTEM_THEME: Mutex<Option<bool>> = Mutex::new(None);
+
+/// How long a theme change waits for a newer one before it is applied
+const THEME_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Number of theme changes requested so far, to tell the newest one apart
+static THEME_REQUESTS: AtomicU64 = AtomicU64::new(0);
+
+/// Event broadcast when the title bars followed an OS theme switch
+const SYSTEM_THEME_CHANGED_EVENT: &str = "system-theme-changed";
+
+/// Payload of the `system-theme-changed` event
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+struct SystemThemeChangedPayload {
+    is_dark: bool,
+}
+
+fn window_engine(app: &AppHandle, window_label: &str) -> Option<Engine> {
+    with_registry(app, |registry| registry.engine_of(window_label)).flatten()
+}
+
+fn is_native_decorated(app: &AppHandle, window_label: &str) -> bool {
+    with_registry(app, |registry| registry.is_decorated(window_label)).unwrap_or(false)
+}
+
+/// Drops the windows with native decorations from a theme pass
+///
+/// Their title bar is drawn by the OS and keeps the OS colors, so the DWM
+/// caption color must not be forced onto it.
+fn without_native_decorations<W>(
+    windows: impl IntoIterator<Item = (String, W)>,
+    is_decorated: impl Fn(&str) -> bool,
+) -> Vec<(String, W)> {
+    windows
+        .into_iter()
+        .filter(|(label, _)| !is_decorated(label))
+        .collect()
+}
+
+/// Accent color of each engine in COLORREF format
+fn engine_accent_color(engine: Engine) -> u32 {
+    match engine {
+        Engine::Claude => 0x00F08030, // blue rgb(48, 128, 240)
+        Engine::Codex => 0x0060B030,  // green rgb(48, 176, 96)
+        Engine::Gemini => 0x00E05090, // purple rgb(144, 80, 224)
+    }
+}
+
+/// Mixes `accent` into `base` by `weight` percent, per channel
+fn blend_colorref(base: u32, accent: u32, weight: u32) -> u32 {
+    let (br, bg, bb) = colorref_to_rgb(base);
+    let (ar, ag, ab) = colorref_to_rgb(accent);
+    let mix = |b: u8, a: u8| ((b as u32 * (100 - weight) + a as u32 * weight) / 100) as u8;
+
+    rgb_to_colorref(mix(br, ar), mix(bg, ag), mix(bb, ab))
+}
+
+/// Caption color for a window: the theme base, tinted by the window's engine
+fn caption_color(is_dark: bool, engine: Option<Engine>) -> u32 {
+    let base = if is_dark {
+        DARK_CAPTION_COLOR
+    } else {
+        LIGHT_CAPTION_COLOR
+    };
+
+    match engine {
+        Some(engine) => blend_colorref(base, engine_accent_color(engine), ENGINE_ACCENT_WEIGHT),
+        None => base,
+    }
+}
+
+/// Packs red, green and blue components into a COLORREF (0x00BBGGRR)
+///
+/// Red is the low byte, so `#FF0000` becomes `0x000000FF`.
+fn rgb_to_colorref(r: u8, g: u8, b: u8) -> u32 {
+    r as u32 | ((g as u32) << 8) | ((b as u32) << 16)
+}
+
+/// Splits a COLORREF (0x00BBGGRR) into its red, green and blue components
+fn colorref_to_rgb(color: u32) -> (u8, u8, u8) {
+    (
+        (color & 0xFF) as u8,
+        ((color >> 8) & 0xFF) as u8,
+        ((color >> 16) & 0xFF) as u8,
+    )
+}
+
+/// Parses a `#RRGGBB` color into its red, green and blue components
+///
+/// Shared with the window background color.
+pub(super) fn parse_hex_rgb(color_hex: &str) -> Result<(u8, u8, u8), String> {
+    let digits = color_hex
+        .strip_prefix('#')
+        .filter(|digits| digits.len() == 6 && digits.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| format!("Invalid color: {} (expected #RRGGBB)", color_hex))?;
+
+    let channel = |i: usize| {
+        u8::from_str_radix(&digits[i..i + 2], 16)
+            .map_err(|e| format!("Invalid color: {} ({})", color_hex, e))
+    };
+    Ok((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Parses a `#RRGGBB` color into COLORREF format
+fn parse_hex_color(color_hex: &str) -> Result<u32, String> {
+    let (r, g, b) = parse_hex_rgb(color_hex)?;
+    Ok(rgb_to_colorref(r, g, b))
+}
+
+/// Set Windows title bar color using DWM API
+///
+/// `DWMWA_CAPTION_COLOR` needs Windows 11 (build 22000); older builds reject
+/// it, which is reported instead of silently leaving the default caption.
+#[cfg(target_os = "windows")]
+fn set_titlebar_color_for_window(window: &tauri::WebviewWindow, color: u32) -> Result<(), String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::Graphics::Dwm::{DwmSetWindowAttribute, DWMWA_CAPTION_COLOR};
+
+    let hwnd = window
+        .hwnd()
+        .map_err(|e| format!("Failed to get window handle: {}", e))?;
+    let hwnd = HWND(hwnd.0);
+
+    unsafe {
+        DwmSetWindowAttribute(
+            hwnd,
+            DWMWA_CAPTION_COLOR,
+            &color as *const u32 as *const _,
+            std::mem::size_of::<u32>() as u32,
+        )
+    }
+    .map_err(|e| {
+        format!(
+            "DwmSetWindowAttribute failed with HRESULT 0x{:08X}: {}",
+            e.code().0 as u32,
+            e.message()
+        )
+    })
+}
+
+/// Set macOS title bar color through the NSWindow background
+///
+/// The title bar is made transparent so the window background (which matches
+/// the COLORREF chosen for the theme) shows through it.
+#[cfg(target_os = "macos")]
+fn set_titlebar_color_for_window(window: &tauri::WebviewWindow, color: u32) -> Result<(), String> {
+    use objc2_app_kit::{NSColor, NSWindow};
+
+    let ns_window = window
+        .ns_window()
+        .map_err(|e| format!("Failed to get NSWindow: {}", e))?;
+
+    let (r, g, b) = colorref_to_rgb(color);
+    let ns_window = ns_window as usize;
+
+    // AppKit objects may only be touched from the main thread
+    window
+        .run_on_main_thread(move || unsafe {
+            let ns_window = &*(ns_window as *const NSWindow);
+            let background = NSColor::colorWithSRGBRed_green_blue_alpha(
+                r as f64 / 255.0,
+                g as f64 / 255.0,
+                b as f64 / 255.0,
+                1.0,
+            );
+            ns_window.setBackgroundColor(Some(&background));
+            ns_window.setTitlebarAppearsTransparent(true);
+        })
+        .map_err(|e| format!("Failed to run on main thread: {}", e))
+}
+
+/// Payload of the `titlebar-theme` event, also returned by `get_titlebar_theme`
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct TitlebarThemePayload {
+    /// Resolved caption color as `#RRGGBB`
+    pub color: String,
+}
+
+fn titlebar_theme_payload(color: u32) -> TitlebarThemePayload {
+    let (r, g, b) = colorref_to_rgb(color);
+    TitlebarThemePayload {
+        color: format!("#{:02X}{:02X}{:02X}", r, g, b),
+    }
+}
+
+/// Hand the caption color to the frontend's custom title bar on Linux
+///
+/// There is no DWM equivalent under GNOME/KDE, so the frameless window's own
+/// title bar paints itself from the `titlebar-theme` event. A window that is
+/// still loading can't hear it; its page asks with `get_titlebar_theme`.
+#[cfg(target_os = "linux")]
+fn set_titlebar_color_for_window(window: &tauri::WebviewWindow, color: u32) -> Result<(), String> {
+    use tauri::EventTarget;
+
+    window
+        .emit_to(
+            EventTarget::webview_window(window.label()),
+            "titlebar-theme",
+            titlebar_theme_payload(color),
+        )
+        .map_err(|e| format!("Failed to emit event: {}", e))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn set_titlebar_color_for_window(
+    _window: &tauri::WebviewWindow,
+    _color: u32,
+) -> Result<(), String> {
+    // No-op for other platforms
+    Ok(())
+}
+
+/// OS theme (true = dark), as last reported or as the main window sees it
+fn system_theme(app: &AppHandle) -> Option<bool> {
+    SYSTEM_THEME
+        .lock()
+        .ok()
+        .and_then(|theme| *theme)
+        .or_else(|| {
+            app.get_webview_window("main")
+                .and_then(|main| main.theme().ok())
+                .map(|theme| theme == tauri::Theme::Dark)
+        })
+}
+
+/// Theme a freshly built window starts with (true = dark): the one the
+/// frontend last reported, else the OS theme
+pub(super) fn current_theme(app: &AppHandle) -> Option<bool> {
+    with_registry(app, |registry| registry.theme())
+        .flatten()
+        .or_else(|| system_theme(app))
+}
+
+/// Caption color a freshly built window starts with.
+///
+/// A custom color wins, then the theme the frontend last reported, then the
+/// OS theme, so a window opened before the frontend reported anything
+/// doesn't flash the default light caption.
+fn new_window_caption_color(
+    custom: Option<u32>,
+    reported_theme: Option<bool>,
+    system_theme: Option<bool>,
+    engine: Option<Engine>,
+) -> Option<u32> {
+    custom.or_else(|| {
+        reported_theme
+            .or(system_theme)
+            .map(|is_dark| caption_color(is_dark, engine))
+    })
+}
+
+/// Applies the current title bar theme to a window right after it was built
+pub(super) fn apply_current_theme(app: &AppHandle, window: &tauri::WebviewWindow) {
+    if is_native_decorated(app, window.label()) {
+        return;
+    }
+
+    let custom = CUSTOM_COLORS.lock().ok().and_then(|mut custom| {
+        // A color set for an earlier window with this label isn't carried over
+        custom.windows.remove(window.label());
+        custom.all
+    });
+    // Nothing listens yet; the page asks with `get_titlebar_theme` once mounted
+    if cfg!(target_os = "linux") {
+        return;
+    }
+
+    let Some(color) = new_window_caption_color(
+        custom,
+        with_registry(app, |registry| registry.theme()).flatten(),
+        system_theme(app),
+        window_engine(app, window.label()),
+    ) else {
+        return;
+    };
+
+    if let Err(e) = set_titlebar_color_for_window(window, color) {
+        window_log!(warn, window.label(), "Failed to set title bar color: {}", e);
+    }
+}
+
+/// Turns the per-window failures of a theme update into the command result
+fn titlebar_theme_result(failed: &[String]) -> Result<(), String> {
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Failed to set title bar color for {} window(s): {}",
+            failed.len(),
+            failed.join(", ")
+        ))
+    }
+}
+
+/// Colors the given windows, carrying on past the ones that fail
+///
+/// Returns the labels of the windows that kept their previous color, sorted.
+fn apply_color_to_windows<I>(windows: I, color_for: impl Fn(&str) -> u32) -> Vec<String>
+where
+    I: IntoIterator<Item = (String, tauri::WebviewWindow)>,
+{
+    let mut failed = Vec::new();
+    for (label, window) in windows {
+        if let Err(e) = set_titlebar_color_for_window(&window, color_for(&label)) {
+            window_log!(warn, label, "Failed to set title bar color: {}", e);
+            failed.push(label);
+        }
+    }
+    failed.sort();
+    failed
+}
+
+/// Colors the given windows, leaving out the ones with native decorations
+///
+/// Shared by `set_titlebar_color` and `set_titlebar_theme`. Returns the labels
+/// of the windows that kept their previous color, sorted.
+fn apply_color<I>(app: &AppHandle, windows: I, color_for: impl Fn(&str) -> u32) -> Vec<String>
+where
+    I: IntoIterator<Item = (String, tauri::WebviewWindow)>,
+{
+    let windows = without_native_decorations(windows, |label| is_native_decorated(app, label));
+    apply_color_to_windows(windows, color_for)
+}
+
+/// Colors every window for the given theme and remembers it for new windows
+///
+/// An explicit theme change (`keep_custom_colors` false) drops the colors set
+/// through `set_titlebar_color`; an OS theme switch leaves the windows showing
+/// one alone. Returns the labels of the windows that kept their previous
+/// color, sorted.
+fn apply_theme_to_all_windows(
+    app: &AppHandle,
+    is_dark: bool,
+    keep_custom_colors: bool,
+) -> Vec<String> {
+    with_registry(app, |registry| registry.set_theme(is_dark));
+    let custom = CUSTOM_COLORS
+        .lock()
+        .map(|mut custom| {
+            if !keep_custom_colors {
+                *custom = CustomColors::default();
+            }
+            custom.clone()
+        })
+        .unwrap_or_default();
+
+    let windows = app
+        .webview_windows()
+        .into_iter()
+        .filter(|(label, _)| !custom.covers(label));
+    apply_color(app, windows, |label| {
+        caption_color(is_dark, window_engine(app, label))
+    })
+}
+
+/// Whether `ticket` is still the newest theme request
+fn is_latest_request(requests: &AtomicU64, ticket: u64) -> bool {
+    requests.load(Ordering::SeqCst) == ticket
+}
+
+/// Waits out the debounce window of a theme change.
+///
+/// Returns false if a newer change came in meanwhile; that one is applied
+/// instead, so the final state always wins.
+async fn settle_theme_request() -> bool {
+    let ticket = THEME_REQUESTS.fetch_add(1, Ordering::SeqCst) + 1;
+    tokio::time::sleep(THEME_DEBOUNCE).await;
+    is_latest_request(&THEME_REQUESTS, ticket)
+}
+
+/// Records an OS theme report; returns true the first time a new theme is seen
+///
+/// Every window reports the same switch, so only the first report counts.
+fn record_system_theme(last: &mut Option<bool>, is_dark: bool) -> bool {
+    let changed = *last != Some(is_dark);
+    *last = Some(is_dark);
+    changed
+}
+
+/// Follows an OS dark-mode switch reported by a window's `ThemeChanged` event
+///
+/// Recolors every title bar and broadcasts `system-theme-changed` so the
+/// frontend can switch its palette, unless the frontend pinned a theme.
+pub fn handle_system_theme_change(app: &AppHandle, is_dark: bool) {
+    let changed = SYSTEM_THEME
+        .lock()
+        .map(|mut last| record_system_theme(&mut last, is_dark))
+        .unwrap_or(false);
+    if !changed {
+        return;
+    }
+
+    if THEME_PINNED.load(Ordering::SeqCst) {
+        log::info!("[Window] System theme changed, keeping the pinned theme");
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if !settle_theme_request().await {
+            return;
+        }
+
+        apply_theme_to_all_windows(&app, is_dark, true);
+        log::info!(
+            "[Window] Title bar theme follows system theme: {}",
+            if is_dark { "dark" } else { "light" }
+        );
+
+        if let Err(e) = app.emit(
+            SYSTEM_THEME_CHANGED_EVENT,
+            SystemThemeChangedPayload { is_dark },
+        ) {
+            log::warn!("[Window] Failed to emit system theme change: {}", e);
+        }
+    });
+}
+
+/// Sets a custom title bar color, e.g. to match a user-chosen accent theme
+///
+/// A color set for all windows is also used for windows opened later. Custom
+/// colors are kept when the OS switches between light and dark, until the next
+/// `set_titlebar_theme`.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The window to color, or None for all windows
+/// * `color_hex` - The color as `#RRGGBB`
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success, or an error listing the windows that kept
+///   their previous color
+#[tauri::command]
+pub async fn set_titlebar_color(
+    app: AppHandle,
+    window_label: Option<String>,
+    color_hex: String,
+) -> Result<(), AppError> {
+    reported!(app, "set_titlebar_color", {
+        let color = parse_hex_color(&color_hex).map_err(AppError::InvalidParam)?;
+
+        let failed = match window_label {
+            Some(label) => {
+                let window = app
+                    .get_webview_window(&label)
+                    .ok_or_else(|| window_not_found(&label))?;
+                if let Ok(mut custom) = CUSTOM_COLORS.lock() {
+                    custom.windows.insert(label.clone(), color);
+                }
+                apply_color(&app, [(label, window)], |_| color)
+            }
+            None => {
+                if let Ok(mut custom) = CUSTOM_COLORS.lock() {
+                    *custom = CustomColors {
+                        all: Some(color),
+                        windows: HashMap::new(),
+                    };
+                }
+                apply_color(&app, app.webview_windows(), |_| color)
+            }
+        };
+
+        Ok(titlebar_theme_result(&failed)?)
+    })
+}
+
+/// Caption color a window currently shows: its own custom color, the one
+/// set for all windows, then the theme
+fn resolved_caption_color(
+    custom: &CustomColors,
+    window_label: &str,
+    reported_theme: Option<bool>,
+    system_theme: Option<bool>,
+    engine: Option<Engine>,
+) -> Option<u32> {
+    new_window_caption_color(
+        custom.windows.get(window_label).copied().or(custom.all),
+        reported_theme,
+        system_theme,
+        engine,
+    )
+}
+
+/// Gets the color of a window's custom title bar (Linux only)
+///
+/// Lets a session page paint its title bar once it has mounted, since the
+/// `titlebar-theme` event sent while it loads would go unheard. Null on other
+/// platforms, where the OS paints the title bar, and for windows with native
+/// decorations.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+///
+/// # Returns
+/// * `Result<Option<TitlebarThemePayload>, AppError>` - The color, if the page paints one
+#[tauri::command]
+pub async fn get_titlebar_theme(
+    app: AppHandle,
+    window_label: String,
+) -> Result<Option<TitlebarThemePayload>, AppError> {
+    reported!(app, "get_titlebar_theme", {
+        if !cfg!(target_os = "linux") || is_native_decorated(&app, &window_label) {
+            return Ok(None);
+        }
+        if app.get_webview_window(&window_label).is_none() {
+            return Err(window_not_found(&window_label));
+        }
+
+        let custom = CUSTOM_COLORS
+            .lock()
+            .map(|custom| custom.clone())
+            .unwrap_or_default();
+        Ok(resolved_caption_color(
+            &custom,
+            &window_label,
+            with_registry(&app, |registry| registry.theme()).flatten(),
+            system_theme(&app),
+            window_engine(&app, &window_label),
+        )
+        .map(titlebar_theme_payload))
+    })
+}
+
+/// Sets the title bar color for all windows based on the current theme
+///
+/// The default-theme counterpart of `set_titlebar_color`, dropping the custom
+/// colors it set. Session windows get a subtle tint of their engine's accent
+/// color on top of the theme color, so Claude, Codex and Gemini windows are
+/// easy to tell apart.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `is_dark` - Whether dark theme is active
+/// * `pinned` - Whether the user picked this theme manually; pinned themes are
+///   kept when the OS switches between light and dark (default: false)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success, or an error listing the windows that kept
+///   their previous color (the others are still updated). A call superseded
+///   by a newer one within the debounce window returns success without
+///   touching any window.
+#[tauri::command]
+pub async fn set_titlebar_theme(
+    app: AppHandle,
+    is_dark: bool,
+    pinned: Option<bool>,
+) -> Result<(), AppError> {
+    reported!(app, "set_titlebar_theme", {
+        THEME_PINNED.store(pinned.unwrap_or(false), Ordering::SeqCst);
+
+        if !settle_theme_request().await {
+            return Ok(());
+        }
+
+        let failed = apply_theme_to_all_windows(&app, is_dark, false);
+
+        log::info!(
+            "[Window] Title bar theme updated to {}",
+            if is_dark { "dark" } else { "light" }
+        );
+
+        Ok(titlebar_theme_result(&failed)?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The native calls themselves need a real window and are verified manually:
+    // - Windows: toggle the theme and check the caption of the main and every
+    //   detached window switches between dark gray and near white.
+    // - macOS: detach a tab, toggle the theme and check the title bar area blends
+    //   into the window background instead of showing the default light chrome.
+
+    #[test]
+    fn test_colorref_to_rgb_theme_colors() {
+        assert_eq!(colorref_to_rgb(0x00343030), (48, 48, 52));
+        assert_eq!(colorref_to_rgb(0x00FCFAFA), (250, 250, 252));
+    }
+
+    #[test]
+    fn test_caption_color_distinct_per_engine() {
+        for is_dark in [true, false] {
+            let colors = [
+                caption_color(is_dark, None),
+                caption_color(is_dark, Some(Engine::Claude)),
+                caption_color(is_dark, Some(Engine::Codex)),
+                caption_color(is_dark, Some(Engine::Gemini)),
+            ];
+            for (i, a) in colors.iter().enumerate() {
+                for b in &colors[i + 1..] {
+                    assert_ne!(a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_new_window_caption_color_follows_current_theme() {
+        let engine = Some(Engine::Codex);
+        // Theme stored by `set_titlebar_theme` is what a new window gets
+        assert_eq!(
+            new_window_caption_color(None, Some(true), Some(false), engine),
+            Some(caption_color(true, engine))
+        );
+        // Before the frontend reported one, the OS theme avoids the light default
+        assert_eq!(
+            new_window_caption_color(None, None, Some(true), engine),
+            Some(caption_color(true, engine))
+        );
+        assert_eq!(
+            new_window_caption_color(Some(0x00112233), Some(true), None, engine),
+            Some(0x00112233)
+        );
+        assert_eq!(new_window_caption_color(None, None, None, engine), None);
+    }
+
+    #[test]
+    fn test_caption_color_without_engine_is_theme_base() {
+        assert_eq!(caption_color(true, None), DARK_CAPTION_COLOR);
+        assert_eq!(caption_color(false, None), LIGHT_CAPTION_COLOR);
+    }
+
+    #[test]
+    fn test_blend_colorref_weights() {
+        assert_eq!(blend_colorref(0x00000000, 0x00FFFFFF, 0), 0x00000000);
+        assert_eq!(blend_colorref(0x00000000, 0x00FFFFFF, 100), 0x00FFFFFF);
+        // rgb(48,48,52) with 12% of rgb(48,128,240) stays subtle
+        assert_eq!(
+            colorref_to_rgb(blend_colorref(DARK_CAPTION_COLOR, 0x00F08030, 12)),
+            (48, 57, 74)
+        );
+    }
+
+    #[test]
+    fn test_titlebar_theme_result_lists_failed_windows() {
+        assert_eq!(titlebar_theme_result(&[]), Ok(()));
+        assert_eq!(
+            titlebar_theme_result(&["main".to_string(), "session-window-a".to_string()]),
+            Err(
+                "Failed to set title bar color for 2 window(s): main, session-window-a".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_titlebar_theme_payload_hex() {
+        assert_eq!(
+            titlebar_theme_payload(caption_color(true, None)).color,
+            "#303034"
+        );
+        assert_eq!(
+            titlebar_theme_payload(caption_color(false, None)).color,
+            "#FAFAFC"
+        );
+        assert_eq!(
+            serde_json::to_value(titlebar_theme_payload(DARK_CAPTION_COLOR)).unwrap(),
+            serde_json::json!({ "color": "#303034" })
+        );
+    }
+
+    #[test]
+    fn test_colorref_to_rgb_byte_order() {
+        assert_eq!(colorref_to_rgb(0x000000FF), (255, 0, 0));
+        assert_eq!(colorref_to_rgb(0x0000FF00), (0, 255, 0));
+        assert_eq!(colorref_to_rgb(0x00FF0000), (0, 0, 255));
+    }
+
+    #[test]
+    fn test_rgb_to_colorref_byte_order() {
+        assert_eq!(rgb_to_colorref(255, 0, 0), 0x000000FF);
+        assert_eq!(rgb_to_colorref(0, 255, 0), 0x0000FF00);
+        assert_eq!(rgb_to_colorref(0, 0, 255), 0x00FF0000);
+    }
+
+    #[test]
+    fn test_rgb_to_colorref_theme_colors() {
+        assert_eq!(rgb_to_colorref(48, 48, 52), DARK_CAPTION_COLOR);
+        assert_eq!(rgb_to_colorref(250, 250, 252), LIGHT_CAPTION_COLOR);
+        for color in [DARK_CAPTION_COLOR, LIGHT_CAPTION_COLOR] {
+            let (r, g, b) = colorref_to_rgb(color);
+            assert_eq!(rgb_to_colorref(r, g, b), color);
+        }
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#FF0000"), Ok(0x000000FF));
+        assert_eq!(parse_hex_color("#303034"), Ok(DARK_CAPTION_COLOR));
+        assert_eq!(parse_hex_color("#fafafc"), Ok(LIGHT_CAPTION_COLOR));
+        for malformed in ["303034", "#30303", "#3030344", "#30303G", "", "#"] {
+            assert_eq!(
+                parse_hex_color(malformed),
+                Err(format!("Invalid color: {} (expected #RRGGBB)", malformed))
+            );
+        }
+    }
+
+    #[test]
+    fn test_record_system_theme_dedupes_reports() {
+        let mut last = None;
+        assert!(record_system_theme(&mut last, true));
+        // The other windows report the same switch
+        assert!(!record_system_theme(&mut last, true));
+        assert!(record_system_theme(&mut last, false));
+        assert_eq!(last, Some(false));
+    }
+
+    #[test]
+    fn test_only_latest_theme_request_applies() {
+        let requests = AtomicU64::new(0);
+        let tickets: Vec<u64> = (0..3)
+            .map(|_| requests.fetch_add(1, Ordering::SeqCst) + 1)
+            .collect();
+
+        // Three quick toggles: only the last one does the apply pass
+        assert!(!is_latest_request(&requests, tickets[0]));
+        assert!(!is_latest_request(&requests, tickets[1]));
+        assert!(is_latest_request(&requests, tickets[2]));
+    }
+
+    #[test]
+    fn test_native_decorated_windows_are_left_out_of_theming() {
+        let windows = ["main", "session-window-native", "session-window-a"]
+            .map(|label| (label.to_string(), ()));
+
+        let themed = without_native_decorations(windows, |label| label == "session-window-native");
+
+        let labels: Vec<&str> = themed.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, ["main", "session-window-a"]);
+        assert!(without_native_decorations([("main".to_string(), ())], |_| true).is_empty());
+    }
+
+    #[test]
+    fn test_custom_colors_cover_their_windows() {
+        let mut custom = CustomColors::default();
+        assert!(!custom.covers("session-window-a"));
+
+        // A color for one window leaves the others to the theme
+        custom
+            .windows
+            .insert("session-window-a".to_string(), 0x00112233);
+        assert!(custom.covers("session-window-a"));
+        assert!(!custom.covers("session-window-b"));
+
+        // A color for all windows covers every one of them
+        custom.all = Some(0x00112233);
+        assert!(custom.covers("session-window-b"));
+    }
+
+    #[test]
+    fn test_resolved_caption_color_prefers_the_window_color() {
+        let engine = Some(Engine::Claude);
+        let mut custom = CustomColors::default();
+        assert_eq!(
+            resolved_caption_color(&custom, "session-window-a", Some(true), None, engine),
+            Some(caption_color(true, engine))
+        );
+
+        custom.all = Some(0x00112233);
+        custom
+            .windows
+            .insert("session-window-a".to_string(), 0x00445566);
+        assert_eq!(
+            resolved_caption_color(&custom, "session-window-a", Some(true), None, engine),
+            Some(0x00445566)
+        );
+        assert_eq!(
+            resolved_caption_color(&custom, "session-window-b", Some(true), None, engine),
+            Some(0x00112233)
+        );
+    }
+
+    #[test]
+    fn test_system_theme_changed_payload() {
+        assert_eq!(
+            serde_json::to_value(SystemThemeChangedPayload { is_dark: true }).unwrap(),
+            serde_json::json!({ "is_dark": true })
+        );
+    }
+}
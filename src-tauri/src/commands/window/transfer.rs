@@ -0,0 +1,121 @@
+/**
+ * Tab Transfer Between Session Windows
+ *
+ * Backend primitive for dragging a tab from one session window into another.
+ * The source is told the tab is leaving, the target receives the session
+ * params, and a final completion event lets both sides commit their UI state.
+ */
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, EventTarget, Manager};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+use super::{session_info_from_url, SessionWindowInfo};
+
+/// Event sent to the window the tab is leaving
+const TAB_LEAVING_EVENT: &str = "tab-leaving";
+
+/// Event sent to the window the tab is moving into
+const TAB_ARRIVING_EVENT: &str = "tab-arriving";
+
+/// Event sent once both windows have been notified
+const TAB_TRANSFER_COMPLETE_EVENT: &str = "tab-transfer-complete";
+
+/// Payload of the tab transfer events
+#[derive(Debug, Clone, Serialize)]
+pub struct TabTransferPayload {
+    /// The tab being moved
+    pub tab_id: String,
+    /// Label of the window the tab is leaving
+    pub source_label: String,
+    /// Label of the window the tab is moving into
+    pub target_label: String,
+    /// Session params, when the tab is the one the source window was detached with
+    pub session: Option<SessionWindowInfo>,
+}
+
+/// Moves a tab from one session window to another
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `source_label` - The label of the window currently holding the tab
+/// * `target_label` - The label of the window receiving the tab
+/// * `tab_id` - The tab to move
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn transfer_tab_between_windows(
+    app: AppHandle,
+    source_label: String,
+    target_label: String,
+    tab_id: String,
+) -> Result<(), AppError> {
+    reported!(app, "transfer_tab_between_windows", {
+        let _scope = super::logging::LogScope::new("transfer_tab_between_windows", &source_label);
+        if source_label == target_label {
+            return Err(AppError::InvalidParam(format!(
+                "Source and target window are the same: {}",
+                source_label
+            )));
+        }
+
+        // Validate both windows before notifying either of them
+        let target = app
+            .get_webview_window(&target_label)
+            .ok_or_else(|| window_not_found(&target_label))?;
+        let source = app
+            .get_webview_window(&source_label)
+            .ok_or_else(|| window_not_found(&source_label))?;
+
+        let session = source
+            .url()
+            .ok()
+            .and_then(|url| session_info_from_url(&source_label, &url).ok())
+            .filter(|info| info.tab_id == tab_id)
+            .map(|info| SessionWindowInfo {
+                // Paths too long for the URL are only in the registry
+                project_path: info.project_path.or_else(|| {
+                    with_registry(&app, |registry| registry.get(&source_label))
+                        .flatten()
+                        .and_then(|meta| meta.project_path)
+                }),
+                ..info
+            });
+
+        let payload = TabTransferPayload {
+            tab_id,
+            source_label,
+            target_label,
+            session,
+        };
+
+        // Targeted: `emit` on a window would reach every window
+        source
+            .emit_to(
+                EventTarget::webview_window(&payload.source_label),
+                TAB_LEAVING_EVENT,
+                &payload,
+            )
+            .map_err(|e| AppError::Emit(format!("Failed to emit event: {}", e)))?;
+        target
+            .emit_to(
+                EventTarget::webview_window(&payload.target_label),
+                TAB_ARRIVING_EVENT,
+                &payload,
+            )
+            .map_err(|e| AppError::Emit(format!("Failed to emit event: {}", e)))?;
+        app.emit(TAB_TRANSFER_COMPLETE_EVENT, &payload)
+            .map_err(|e| AppError::Emit(format!("Failed to emit event: {}", e)))?;
+
+        window_log!(
+            info,
+            payload.source_label,
+            "Tab {} transferred to {}",
+            payload.tab_id,
+            payload.target_label
+        );
+
+        Ok(())
+    })
+}
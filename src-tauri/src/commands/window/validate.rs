@@ -0,0 +1,235 @@
+/**
+ * Session Window Validation
+ *
+ * Runs the checks of `create_session_window` without building anything, so
+ * the frontend can tell up front whether a detach would go through (e.g. to
+ * disable a menu item) instead of spawning a webview to find out.
+ *
+ * The checks are the ones `build_window` makes, in the same order, but all
+ * problems are collected instead of stopping at the first. Nothing is
+ * claimed, registered or emitted, and a rejection is not broadcast on
+ * `window-error`.
+ */
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use super::error::AppError;
+use super::{
+    is_session_window_label, limits, resolve_window_size, resolved_params, validate_extra_params,
+    validate_project_path, window_label_for_workspace_tab, CreateSessionWindowParams,
+    InFlightGuard,
+};
+
+/// What `create_session_window` would do with a set of parameters
+#[derive(Debug, Serialize)]
+pub struct SessionWindowValidation {
+    /// Label the window would get; None if the tab ID or workspace is malformed
+    pub window_label: Option<String>,
+    /// Whether the window is already open, so it would be focused instead
+    pub already_open: bool,
+    /// Why creation would be refused; empty if it would go through
+    pub errors: Vec<AppError>,
+    /// Things that don't stop creation but may not be what the caller meant
+    pub warnings: Vec<String>,
+    /// The parameters as the backend would resolve them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<CreateSessionWindowParams>,
+}
+
+/// State of the label a window would get
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LabelState {
+    Free,
+    Open,
+    BeingCreated,
+}
+
+/// Checks creation parameters against the open windows and the window limit
+fn validate_creation(
+    params: &CreateSessionWindowParams,
+    label_state: impl Fn(&str) -> LabelState,
+    open_session_windows: usize,
+    max_session_windows: usize,
+) -> SessionWindowValidation {
+    let mut validation = SessionWindowValidation {
+        window_label: None,
+        already_open: false,
+        errors: Vec::new(),
+        warnings: Vec::new(),
+        params: None,
+    };
+
+    match window_label_for_workspace_tab(params.workspace.as_deref(), &params.tab_id) {
+        Ok(label) => validation.window_label = Some(label),
+        Err(e) => validation.errors.push(AppError::InvalidParam(e)),
+    }
+    match resolve_window_size(params) {
+        Ok(size) => {
+            if params.width.is_some_and(|width| width != size.width) {
+                validation.warnings.push(format!(
+                    "width {} is adjusted to {} to fit the size limits",
+                    params.width.unwrap_or_default(),
+                    size.width
+                ));
+            }
+            if params.height.is_some_and(|height| height != size.height) {
+                validation.warnings.push(format!(
+                    "height {} is adjusted to {} to fit the size limits",
+                    params.height.unwrap_or_default(),
+                    size.height
+                ));
+            }
+            validation.params = resolved_params(params).ok();
+        }
+        Err(e) => validation.errors.push(AppError::InvalidParam(e)),
+    }
+
+    let state = validation
+        .window_label
+        .as_deref()
+        .map(label_state)
+        .unwrap_or(LabelState::Free);
+    match state {
+        // Focused as is; nothing else is checked
+        LabelState::Open => {
+            validation.already_open = true;
+            return validation;
+        }
+        LabelState::BeingCreated => validation
+            .warnings
+            .push("the window is already being created".to_string()),
+        LabelState::Free => {}
+    }
+
+    if let Err(e) = limits::admit_window(open_session_windows, max_session_windows) {
+        validation.errors.push(AppError::Other(e));
+    }
+    if let Some(project_path) = params.project_path.as_deref() {
+        match validate_project_path(project_path) {
+            Ok(()) => {}
+            Err(e) if params.skip_path_check => {
+                validation.warnings.push(format!("{} (check skipped)", e))
+            }
+            Err(e) => validation.errors.push(AppError::InvalidParam(e)),
+        }
+    }
+    if let Some(ref extra_params) = params.extra_params {
+        if let Err(e) = validate_extra_params(extra_params) {
+            validation.errors.push(AppError::InvalidParam(e));
+        }
+    }
+
+    validation
+}
+
+/// Checks whether a session window could be created, without creating it
+///
+/// Makes the same checks as `create_session_window`: label, size, window
+/// limit, project path and extra parameters. Problems are returned in the
+/// result rather than as an error.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `params` - Window creation parameters
+///
+/// # Returns
+/// * `Result<SessionWindowValidation, AppError>` - The would-be label, problems and warnings
+#[tauri::command]
+pub async fn validate_session_window_params(
+    app: AppHandle,
+    params: CreateSessionWindowParams,
+) -> Result<SessionWindowValidation, AppError> {
+    reported!(app, "validate_session_window_params", {
+        let windows = app.webview_windows();
+        let open_session_windows = windows
+            .keys()
+            .filter(|label| is_session_window_label(label))
+            .count();
+        let label_state = |label: &str| {
+            if windows.contains_key(label) {
+                LabelState::Open
+            } else if InFlightGuard::is_held(label) {
+                LabelState::BeingCreated
+            } else {
+                LabelState::Free
+            }
+        };
+
+        Ok(validate_creation(
+            &params,
+            label_state,
+            open_session_windows,
+            limits::max_session_windows(&app),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn params(tab_id: &str, project_path: &std::path::Path) -> CreateSessionWindowParams {
+        CreateSessionWindowParams {
+            tab_id: tab_id.to_string(),
+            title: "Session".to_string(),
+            project_path: Some(project_path.to_string_lossy().to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn kinds(validation: &SessionWindowValidation) -> Vec<serde_json::Value> {
+        validation
+            .errors
+            .iter()
+            .map(|e| serde_json::to_value(e).unwrap()["kind"].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_each_rejection_is_reported_without_creating() {
+        let dir = tempfile::tempdir().unwrap();
+        let free = |_: &str| LabelState::Free;
+
+        let valid = validate_creation(&params("tab-1", dir.path()), free, 0, 20);
+        assert_eq!(valid.window_label.as_deref(), Some("session-window-tab-1"));
+        assert!(valid.errors.is_empty() && valid.warnings.is_empty());
+        assert!(valid.params.is_some());
+
+        let malformed_label = params("tab:1", dir.path());
+        let mut bad_size = params("tab-1", dir.path());
+        bad_size.width = Some(-1.0);
+        let missing_path = params("tab-1", &dir.path().join("missing"));
+        let mut reserved_param = params("tab-1", dir.path());
+        reserved_param.extra_params =
+            Some(HashMap::from([("tab_id".to_string(), "x".to_string())]));
+        for rejected in [&malformed_label, &bad_size, &missing_path, &reserved_param] {
+            let validation = validate_creation(rejected, free, 0, 20);
+            assert_eq!(kinds(&validation), vec!["invalid_param"], "{:?}", rejected);
+        }
+
+        let at_limit = validate_creation(&params("tab-1", dir.path()), free, 20, 20);
+        assert_eq!(kinds(&at_limit), vec!["other"]);
+
+        // Everything is collected, not just the first problem
+        let mut several = params("tab:1", &dir.path().join("missing"));
+        several.height = Some(0.0);
+        assert_eq!(validate_creation(&several, free, 20, 20).errors.len(), 4);
+    }
+
+    #[test]
+    fn test_open_window_and_soft_problems_are_not_errors() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // An open window is focused, even at the limit
+        let open = validate_creation(&params("tab-1", dir.path()), |_| LabelState::Open, 20, 20);
+        assert!(open.already_open && open.errors.is_empty());
+
+        let mut soft = params("tab-1", &dir.path().join("remote"));
+        soft.skip_path_check = true;
+        soft.width = Some(100.0);
+        let validation = validate_creation(&soft, |_| LabelState::BeingCreated, 0, 20);
+        assert!(validation.errors.is_empty());
+        assert_eq!(validation.warnings.len(), 3, "{:?}", validation.warnings);
+    }
+}
@@ -0,0 +1,189 @@
+/**
+ * Window Visibility
+ *
+ * Hides a session window without closing it (a "minimize to tray" style
+ * workflow): the webview and its session keep running, and broadcasts still
+ * reach it. Or keeps it on screen but out of the taskbar, for monitoring and
+ * preview windows.
+ *
+ * Also minimizes all session windows at once for focus mode and brings them
+ * back later. Only windows minimized that way are restored; ones the user
+ * minimized by hand stay where they are.
+ */
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+use super::{is_session_window_label, restore, tab_id_from_label};
+
+/// Adds a window to or drops it from the taskbar, keeping it for restore
+fn apply_skip_taskbar(app: &AppHandle, window: &WebviewWindow, skip: bool) -> Result<(), String> {
+    window
+        .set_skip_taskbar(skip)
+        .map_err(|e| format!("Failed to set skip_taskbar: {}", e))?;
+
+    let window_label = window.label();
+    with_registry(app, |registry| {
+        registry.update(window_label, |meta| meta.skip_taskbar = skip)
+    });
+    if let Some(tab_id) = tab_id_from_label(window_label) {
+        restore::set_open_window_skip_taskbar(app, tab_id, skip);
+    }
+    Ok(())
+}
+
+/// Shows or hides a window without closing it
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `visible` - Whether the window should be shown
+/// * `skip_taskbar` - Whether to also drop the window from the taskbar
+///   (left unchanged if omitted)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_visible(
+    app: AppHandle,
+    window_label: String,
+    visible: bool,
+    skip_taskbar: Option<bool>,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_visible", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+
+        if let Some(skip) = skip_taskbar {
+            apply_skip_taskbar(&app, &window, skip)?;
+        }
+
+        if visible {
+            window
+                .show()
+                .map_err(|e| format!("Failed to show window: {}", e))?;
+        } else {
+            window
+                .hide()
+                .map_err(|e| format!("Failed to hide window: {}", e))?;
+        }
+
+        with_registry(&app, |registry| {
+            registry.update(&window_label, |meta| meta.visible = visible)
+        });
+
+        window_log!(
+            info,
+            window_label,
+            "Window {}",
+            if visible { "shown" } else { "hidden" }
+        );
+        Ok(())
+    })
+}
+
+/// Drops a window from the taskbar or puts it back
+///
+/// Unlike hiding with `set_window_visible`, the window stays on screen; it
+/// only has no taskbar entry. Kept across restarts.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `skip` - Whether to leave the window out of the taskbar
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_skip_taskbar(
+    app: AppHandle,
+    window_label: String,
+    skip: bool,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_skip_taskbar", {
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| window_not_found(&window_label))?;
+        apply_skip_taskbar(&app, &window, skip)?;
+
+        window_log!(
+            info,
+            window_label,
+            "Taskbar entry {}",
+            if skip { "removed" } else { "restored" }
+        );
+        Ok(())
+    })
+}
+
+/// Minimizes every shown session window
+///
+/// Windows that are hidden or already minimized are left alone.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+///
+/// # Returns
+/// * `Result<u32, AppError>` - The number of windows minimized
+#[tauri::command]
+pub async fn minimize_all_session_windows(app: AppHandle) -> Result<u32, AppError> {
+    reported!(app, "minimize_all_session_windows", {
+        let mut minimized = 0;
+        for (label, window) in app.webview_windows() {
+            if !is_session_window_label(&label)
+                || !window.is_visible().unwrap_or(false)
+                || window.is_minimized().unwrap_or(false)
+            {
+                continue;
+            }
+            if let Err(e) = window.minimize() {
+                window_log!(warn, label, "Failed to minimize window: {}", e);
+                continue;
+            }
+            with_registry(&app, |registry| {
+                registry.update(&label, |meta| meta.minimized_by_app = true)
+            });
+            minimized += 1;
+        }
+
+        log::info!("[Window] Minimized {} session window(s)", minimized);
+        Ok(minimized)
+    })
+}
+
+/// Brings back the session windows `minimize_all_session_windows` minimized
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+///
+/// # Returns
+/// * `Result<u32, AppError>` - The number of windows restored
+#[tauri::command]
+pub async fn restore_all_session_windows(app: AppHandle) -> Result<u32, AppError> {
+    reported!(app, "restore_all_session_windows", {
+        let labels =
+            with_registry(&app, |registry| registry.labels_minimized_by_app()).unwrap_or_default();
+
+        let mut restored = 0;
+        for label in labels {
+            with_registry(&app, |registry| {
+                registry.update(&label, |meta| meta.minimized_by_app = false)
+            });
+            let Some(window) = app.get_webview_window(&label) else {
+                continue;
+            };
+            // Already brought back some other way
+            if !window.is_minimized().unwrap_or(false) {
+                continue;
+            }
+            match window.unminimize() {
+                Ok(()) => restored += 1,
+                Err(e) => window_log!(warn, label, "Failed to unminimize window: {}", e),
+            }
+        }
+
+        log::info!("[Window] Restored {} session window(s)", restored);
+        Ok(restored)
+    })
+}
@@ -0,0 +1,161 @@
+/**
+ * Per-Window Zoom
+ *
+ * Lets each window zoom its webview independently and remembers the factor
+ * per window label, so a restored session window comes back at the same zoom.
+ */
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, EventTarget, Manager, WebviewWindow};
+
+use crate::utils::config_utils::{load_json_config, save_json_config};
+
+use super::error::{window_not_found, AppError};
+use super::registry::with_registry;
+use super::window_state_file;
+
+/// File name of the zoom store inside the app data dir
+const ZOOM_FILE: &str = "window_zoom.json";
+
+/// Serializes read-modify-write cycles on the zoom file
+static ZOOM_LOCK: Mutex<()> = Mutex::new(());
+
+/// Smallest and largest accepted zoom factor
+const MIN_ZOOM: f64 = 0.5;
+const MAX_ZOOM: f64 = 3.0;
+
+/// Event sent to a window whenever its zoom changes
+const ZOOM_CHANGED_EVENT: &str = "zoom-changed";
+
+/// On-disk layout of the zoom store
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ZoomStore {
+    /// Zoom factor keyed by window label
+    #[serde(default)]
+    windows: HashMap<String, f64>,
+}
+
+/// Checks that a zoom factor is within the supported range
+fn validate_zoom_factor(factor: f64) -> Result<f64, String> {
+    if factor.is_finite() && (MIN_ZOOM..=MAX_ZOOM).contains(&factor) {
+        Ok(factor)
+    } else {
+        Err(format!(
+            "Invalid zoom factor {}: must be between {} and {}",
+            factor, MIN_ZOOM, MAX_ZOOM
+        ))
+    }
+}
+
+fn store_zoom(app: &AppHandle, window_label: &str, factor: f64) -> Result<(), String> {
+    let path = window_state_file(app, ZOOM_FILE)?;
+    let _guard = ZOOM_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let mut store: ZoomStore = load_json_config(&path)?;
+    // 100% is the default, no need to keep it around
+    if factor == 1.0 {
+        store.windows.remove(window_label);
+    } else {
+        store.windows.insert(window_label.to_string(), factor);
+    }
+    save_json_config(&store, &path)
+}
+
+fn load_zoom(app: &AppHandle, window_label: &str) -> Option<f64> {
+    let path = window_state_file(app, ZOOM_FILE).ok()?;
+    let _guard = ZOOM_LOCK.lock().ok()?;
+
+    match load_json_config::<ZoomStore>(&path) {
+        Ok(store) => store
+            .windows
+            .get(window_label)
+            .copied()
+            .and_then(|factor| validate_zoom_factor(factor).ok()),
+        Err(e) => {
+            log::warn!("[Window] Failed to load window zoom: {}", e);
+            None
+        }
+    }
+}
+
+/// Re-applies the saved zoom of a freshly built window, if any
+pub(super) fn apply_saved_zoom(app: &AppHandle, window: &WebviewWindow) {
+    if let Some(factor) = load_zoom(app, window.label()) {
+        match window.set_zoom(factor) {
+            Ok(()) => remember_zoom(app, window.label(), factor),
+            Err(e) => window_log!(warn, window.label(), "Failed to restore zoom: {}", e),
+        }
+    }
+}
+
+fn remember_zoom(app: &AppHandle, window_label: &str, factor: f64) {
+    with_registry(app, |registry| {
+        registry.update(window_label, |meta| meta.zoom = factor)
+    });
+}
+
+/// Sets the zoom factor of a window's webview
+///
+/// The factor is remembered per window label and `zoom-changed` is emitted to
+/// the window with the new factor.
+///
+/// # Arguments
+/// * `app` - The Tauri app handle
+/// * `window_label` - The label of the window
+/// * `factor` - The zoom factor (0.5 - 3.0, 1.0 is 100%)
+///
+/// # Returns
+/// * `Result<(), AppError>` - Success or error message
+#[tauri::command]
+pub async fn set_window_zoom(
+    app: AppHandle,
+    window_label: String,
+    factor: f64,
+) -> Result<(), AppError> {
+    reported!(app, "set_window_zoom", {
+        let factor = validate_zoom_factor(factor).map_err(AppError::InvalidParam)?;
+
+        if let Some(window) = app.get_webview_window(&window_label) {
+            window
+                .set_zoom(factor)
+                .map_err(|e| format!("Failed to set zoom: {}", e))?;
+
+            remember_zoom(&app, &window_label, factor);
+            if let Err(e) = store_zoom(&app, &window_label, factor) {
+                window_log!(warn, window_label, "Failed to save zoom: {}", e);
+            }
+
+            window
+                .emit_to(
+                    EventTarget::webview_window(&window_label),
+                    ZOOM_CHANGED_EVENT,
+                    factor,
+                )
+                .map_err(|e| AppError::Emit(format!("Failed to emit event: {}", e)))?;
+            Ok(())
+        } else {
+            Err(window_not_found(&window_label))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_zoom_factor_accepts_range() {
+        assert_eq!(validate_zoom_factor(0.5), Ok(0.5));
+        assert_eq!(validate_zoom_factor(1.0), Ok(1.0));
+        assert_eq!(validate_zoom_factor(3.0), Ok(3.0));
+    }
+
+    #[test]
+    fn test_validate_zoom_factor_rejects_out_of_range() {
+        assert!(validate_zoom_factor(0.49).is_err());
+        assert!(validate_zoom_factor(3.01).is_err());
+        assert!(validate_zoom_factor(0.0).is_err());
+        assert!(validate_zoom_factor(f64::NAN).is_err());
+    }
+}
@@ -73,8 +73,29 @@ use commands::translator::{
 };
 use commands::usage::{get_session_stats, get_usage_by_date_range, get_usage_stats};
 use commands::window::{
-    broadcast_to_session_windows, close_session_window, create_session_window, emit_to_window,
-    focus_session_window, list_session_windows, set_titlebar_theme,
+    arrange_windows, broadcast_chunked, broadcast_to_engine_windows, broadcast_to_session_windows,
+    broadcast_to_session_windows_detailed, capture_window_thumbnail, close_all_session_windows,
+    close_group, close_session_window, close_session_window_saving, create_preview_window,
+    create_session_window, create_session_window_timed, duplicate_session_window,
+    emit_json_to_window, emit_to_window, emit_to_windows, focus_group, focus_session_window,
+    get_focused_session_window, get_session_window_restore_enabled, get_titlebar_theme,
+    get_window_events, get_window_meta, get_window_relative_position, get_window_scale_factor,
+    is_window_focused, list_monitors, list_session_windows, list_session_windows_detailed,
+    minimize_all_session_windows, mirror_session_in_main, move_window_to_monitor,
+    notify_window_attention, open_workspace_layout, open_workspace_layout_timed, raise_window,
+    reattach_session_window, reconcile_windows, reload_session_window, report_window_activity,
+    request_from_window, restore_all_session_windows, restore_session_windows,
+    restore_window_geometry, save_window_geometry, send_text_to_window, session_window_exists,
+    set_focus_follows_mouse, set_focus_main_on_last_close, set_max_broadcast_payload_size,
+    set_max_session_windows, set_session_window_restore_enabled, set_session_window_title,
+    set_titlebar_color, set_titlebar_theme, set_window_always_on_top, set_window_background_color,
+    set_window_busy, set_window_decorations, set_window_event_log_enabled, set_window_group,
+    set_window_icon, set_window_maximizable, set_window_opacity, set_window_relative_position,
+    set_window_resizable, set_window_size_constraints, set_window_skip_taskbar, set_window_visible,
+    set_window_visible_on_all_workspaces, set_window_zoom, snap_window, switch_window_engine,
+    take_session_snapshot, toggle_window_fullscreen, toggle_window_maximize,
+    transfer_tab_between_windows, update_window_engine, validate_session_window_params,
+    WindowEventLog, WindowLimits, WindowRegistry,
 };
 
 use commands::codex::{
@@ -183,6 +204,8 @@ fn main() {
         .plugin(
             WindowStatePlugin::default()
                 .with_state_flags(tauri_plugin_window_state::StateFlags::all())
+                // Session windows persist their own geometry (see commands::window::geometry)
+                .with_filter(|label| !commands::window::is_session_window_label(label))
                 .build(),
         )
         .setup(|app| {
@@ -206,6 +229,11 @@ fn main() {
             // Initialize Gemini process state
             app.manage(GeminiProcessState::default());
 
+            // Initialize session window registry
+            app.manage(WindowRegistry::default());
+            app.manage(WindowLimits::default());
+            app.manage(WindowEventLog::default());
+
             // Initialize auto-compact manager for context management
             let auto_compact_manager =
                 Arc::new(commands::context_manager::AutoCompactManager::new());
@@ -249,9 +277,25 @@ fn main() {
                 });
             }
 
+            // Recreate the session windows that were open at the last shutdown
+            commands::window::restore_session_windows_on_startup(app.handle());
+
             Ok(())
         })
         .on_window_event(|window, event| {
+            // Follow OS dark-mode switches in every window's title bar
+            if let WindowEvent::ThemeChanged(theme) = event {
+                commands::window::handle_system_theme_change(
+                    window.app_handle(),
+                    *theme == tauri::Theme::Dark,
+                );
+            }
+
+            // Tell the frontend when a window lands on a monitor with another DPI
+            if let WindowEvent::ScaleFactorChanged { scale_factor, .. } = event {
+                commands::window::handle_scale_factor_change(window, *scale_factor);
+            }
+
             // Handle main window close - close all session windows
             if let WindowEvent::CloseRequested { .. } = event {
                 let window_label = window.label();
@@ -260,11 +304,14 @@ fn main() {
                 if window_label == "main" {
                     log::info!("[Window] Main window closing, closing all session windows");
 
+                    // Save every session window before closing them for the next launch
                     let app = window.app_handle();
+                    commands::window::persist_windows_on_exit(app);
+
                     let windows_to_close: Vec<String> = app
                         .webview_windows()
                         .keys()
-                        .filter(|label| label.starts_with("session-window-"))
+                        .filter(|label| commands::window::is_session_window_label(label))
                         .cloned()
                         .collect();
 
@@ -500,8 +547,81 @@ fn main() {
             list_session_windows,
             focus_session_window,
             emit_to_window,
+            emit_json_to_window,
+            request_from_window,
             broadcast_to_session_windows,
+            broadcast_to_session_windows_detailed,
+            broadcast_to_engine_windows,
             set_titlebar_theme,
+            save_window_geometry,
+            restore_window_geometry,
+            restore_session_windows,
+            get_session_window_restore_enabled,
+            set_session_window_restore_enabled,
+            reattach_session_window,
+            transfer_tab_between_windows,
+            set_window_busy,
+            set_window_always_on_top,
+            toggle_window_fullscreen,
+            toggle_window_maximize,
+            set_window_zoom,
+            set_session_window_title,
+            get_window_meta,
+            list_session_windows_detailed,
+            reconcile_windows,
+            set_window_opacity,
+            notify_window_attention,
+            duplicate_session_window,
+            close_all_session_windows,
+            snap_window,
+            session_window_exists,
+            get_window_scale_factor,
+            emit_to_windows,
+            set_max_session_windows,
+            update_window_engine,
+            move_window_to_monitor,
+            set_titlebar_color,
+            report_window_activity,
+            create_preview_window,
+            set_window_visible,
+            send_text_to_window,
+            set_focus_main_on_last_close,
+            reload_session_window,
+            set_window_size_constraints,
+            arrange_windows,
+            set_window_icon,
+            is_window_focused,
+            get_focused_session_window,
+            set_window_decorations,
+            set_window_event_log_enabled,
+            get_window_events,
+            open_workspace_layout,
+            list_monitors,
+            minimize_all_session_windows,
+            restore_all_session_windows,
+            capture_window_thumbnail,
+            mirror_session_in_main,
+            set_window_visible_on_all_workspaces,
+            broadcast_chunked,
+            set_max_broadcast_payload_size,
+            set_window_background_color,
+            switch_window_engine,
+            set_window_resizable,
+            set_window_maximizable,
+            create_session_window_timed,
+            open_workspace_layout_timed,
+            set_focus_follows_mouse,
+            get_window_relative_position,
+            set_window_relative_position,
+            raise_window,
+            validate_session_window_params,
+            close_session_window_saving,
+            set_window_group,
+            close_group,
+            focus_group,
+            set_window_skip_taskbar,
+            take_session_snapshot,
+            get_titlebar_theme,
             // Google Gemini CLI Integration
             execute_gemini,
             cancel_gemini,
@@ -538,6 +658,12 @@ fn main() {
             // Gemini Usage Statistics
             get_gemini_usage_stats,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            // Quitting through the OS skips the main window's close handler
+            if let tauri::RunEvent::ExitRequested { .. } = event {
+                commands::window::persist_windows_on_exit(app);
+            }
+        });
 }
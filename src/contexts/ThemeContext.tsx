@@ -1,5 +1,6 @@
-import React, { createContext, useContext, useEffect, useState, ReactNode } from 'react';
+import React, { createContext, useContext, useEffect, useRef, useState, ReactNode } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 
 type Theme = 'light' | 'dark';
 
@@ -19,20 +20,54 @@ export const useTheme = () => {
   return context;
 };
 
+// 固定标记单独存放：旧版本每次启动都会写入 'theme'，不能据此判断用户是否手动选择过
+// The pin has its own key: older versions wrote 'theme' on every start, so
+// that key alone doesn't mean the user picked a theme
+const THEME_KEY = 'theme';
+const THEME_PINNED_KEY = 'theme-pinned';
+
+/** Whether the user picked a theme, so it no longer follows the OS */
+const loadPinned = (): boolean => localStorage.getItem(THEME_PINNED_KEY) === 'true';
+
+/** The OS theme as the webview sees it */
+const systemTheme = (): Theme =>
+  window.matchMedia?.('(prefers-color-scheme: dark)').matches ? 'dark' : 'light';
+
+/** The picked theme when pinned, else the OS theme */
+const initialTheme = (): Theme => {
+  const saved = localStorage.getItem(THEME_KEY);
+  if (loadPinned() && (saved === 'light' || saved === 'dark')) {
+    return saved;
+  }
+  return systemTheme();
+};
+
 interface ThemeProviderProps {
   children: ReactNode;
 }
 
 export const ThemeProvider: React.FC<ThemeProviderProps> = ({ children }) => {
-  const [theme, setTheme] = useState<Theme>('light');
+  const [theme, setThemeState] = useState<Theme>(initialTheme);
+  // 用户手动选择的主题会固定下来，不再跟随系统
+  // A theme the user picked is pinned and no longer follows the OS
+  const [pinned, setPinned] = useState(loadPinned);
+  // 供只注册一次的监听器读取最新的固定状态
+  // Latest pinned state, for the listener registered only once
+  const pinnedRef = useRef(pinned);
+  pinnedRef.current = pinned;
 
   useEffect(() => {
-    // 挂载时从 localStorage 加载主题
-    // Load theme from localStorage on mount
-    const savedTheme = localStorage.getItem('theme') as Theme;
-    if (savedTheme) {
-      setTheme(savedTheme);
-    }
+    // 未固定主题时跟随系统深色模式切换
+    // Follow OS dark-mode switches while no theme is pinned
+    const unlisten = listen<{ is_dark: boolean }>('system-theme-changed', (event) => {
+      if (pinnedRef.current) {
+        return;
+      }
+      setThemeState(event.payload.is_dark ? 'dark' : 'light');
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
   useEffect(() => {
@@ -46,19 +81,28 @@ export const ThemeProvider: React.FC<ThemeProviderProps> = ({ children }) => {
     // Update data-color-mode for markdown editor
     document.documentElement.setAttribute('data-color-mode', theme);
 
-    // 将主题保存到 localStorage
-    // Save theme to localStorage
-    localStorage.setItem('theme', theme);
+    // 将手动选择的主题保存到 localStorage
+    // Save a manually picked theme to localStorage
+    if (pinned) {
+      localStorage.setItem(THEME_KEY, theme);
+      localStorage.setItem(THEME_PINNED_KEY, 'true');
+    }
 
     // 更新 Windows 标题栏颜色以匹配主题
     // Update Windows title bar color to match theme
-    invoke('set_titlebar_theme', { isDark: theme === 'dark' }).catch((err) => {
+    invoke('set_titlebar_theme', { isDark: theme === 'dark', pinned }).catch((err) => {
       console.warn('Failed to update titlebar theme:', err);
     });
-  }, [theme]);
+  }, [theme, pinned]);
+
+  const setTheme = (next: Theme) => {
+    setPinned(true);
+    setThemeState(next);
+  };
 
   const toggleTheme = () => {
-    setTheme(prev => prev === 'light' ? 'dark' : 'light');
+    setPinned(true);
+    setThemeState(prev => prev === 'light' ? 'dark' : 'light');
   };
 
   return (
@@ -6,6 +6,7 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import { emit, listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 
 
 // ============================================================================
@@ -19,11 +20,109 @@ export interface CreateSessionWindowParams {
   title: string;
   /** Execution engine: 'claude' | 'codex' | 'gemini' */
   engine?: 'claude' | 'codex' | 'gemini';
+  /** Name of the monitor to open on (defaults to the main window's monitor) */
+  monitorHint?: string;
+  /** Initial inner size in logical pixels (default 1000x700) */
+  width?: number;
+  height?: number;
+  /** Minimum inner size in logical pixels (default 600x400) */
+  minWidth?: number;
+  minHeight?: number;
+  /** Maximum inner size in logical pixels (default: none) */
+  maxWidth?: number;
+  maxHeight?: number;
+  /** Prompt to submit once the session is ready */
+  initialPrompt?: string;
+  /** Skip the check that projectPath is an existing directory (remote/virtual paths) */
+  skipPathCheck?: boolean;
+  /** State to open in (defaults to the state the tab's window was last closed in) */
+  initialState?: 'normal' | 'maximized' | 'minimized' | 'fullscreen';
+  /** Workspace to namespace the window label with */
+  workspace?: string;
+  /** Use the native title bar and border instead of the custom title bar (default: frameless) */
+  decorations?: boolean;
+  /** Additional URL query parameters (theme, layout, readonly, ...); built-in keys are rejected */
+  extraParams?: Record<string, string>;
+  /** Opaque UI state (JSON: scroll position, draft input, ...) read back with `takeSessionSnapshot` */
+  sessionSnapshot?: string;
 }
 
 export interface WindowCreationResult {
   window_label: string;
   success: boolean;
+  /** Outer position and inner size in logical pixels */
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  /** Creation params as the backend resolved them (canonical project path, size defaults) */
+  params?: ResolvedSessionWindowParams;
+}
+
+/** Creation params in the backend's snake_case shape, with defaults filled in */
+export interface ResolvedSessionWindowParams {
+  tab_id: string;
+  session_id: string | null;
+  project_path: string | null;
+  title: string;
+  engine: 'claude' | 'codex' | 'gemini' | null;
+  monitor_hint: string | null;
+  width: number;
+  height: number;
+  min_width: number;
+  min_height: number;
+  max_width: number | null;
+  max_height: number | null;
+  initial_prompt: string | null;
+  skip_path_check: boolean;
+  initial_state: 'normal' | 'maximized' | 'minimized' | 'fullscreen' | null;
+  workspace: string | null;
+  decorations: boolean;
+  extra_params: Record<string, string> | null;
+}
+
+/** Backend metadata of a session window */
+export interface WindowMeta {
+  window_label: string;
+  tab_id: string;
+  session_id: string | null;
+  project_path: string | null;
+  engine: 'claude' | 'codex' | 'gemini' | null;
+  /** A run is in progress; closing asks for confirmation */
+  busy: boolean;
+  /** Current zoom factor (1.0 is 100%) */
+  zoom: number;
+  /** Current opacity (1.0 is fully opaque) */
+  opacity: number;
+  /** What the session is doing, as last reported by the window */
+  activity: WindowActivity;
+  /** Whether the window is shown (hidden windows keep running) */
+  visible: boolean;
+  /** Engine whose taskbar icon the window shows, if one was set */
+  icon: 'claude' | 'codex' | 'gemini' | null;
+  /** Whether the window uses native decorations instead of the custom title bar */
+  decorated: boolean;
+  /** Whether the window is pinned to all virtual desktops */
+  visible_on_all_workspaces: boolean;
+  /** Whether the user can resize the window */
+  resizable: boolean;
+  /** Whether the user can maximize the window */
+  maximizable: boolean;
+  /** Whether the window is left out of the taskbar */
+  skip_taskbar: boolean;
+  /** Group set with `setWindowGroup` */
+  group_id: string | null;
+  /** Whether `minimizeAllSessionWindows` minimized the window */
+  minimized_by_app: boolean;
+}
+
+export interface BroadcastResult {
+  /** Labels of the windows that received the event */
+  delivered: string[];
+  /** [label, error] pairs for the windows the event could not be delivered to */
+  failed: [string, string][];
+  /** Labels of the windows that closed while the event was on its way */
+  skipped: string[];
 }
 
 // Event types for cross-window communication
@@ -39,6 +138,31 @@ export interface WindowSyncEvent {
 // Window Management Functions
 // ============================================================================
 
+/** Converts creation params to the backend's snake_case shape */
+function toBackendParams(params: CreateSessionWindowParams): Record<string, unknown> {
+  return {
+    tab_id: params.tabId,
+    session_id: params.sessionId || null,
+    project_path: params.projectPath || null,
+    title: params.title,
+    engine: params.engine || null,
+    monitor_hint: params.monitorHint || null,
+    width: params.width ?? null,
+    height: params.height ?? null,
+    min_width: params.minWidth ?? null,
+    min_height: params.minHeight ?? null,
+    max_width: params.maxWidth ?? null,
+    max_height: params.maxHeight ?? null,
+    initial_prompt: params.initialPrompt || null,
+    skip_path_check: params.skipPathCheck ?? false,
+    initial_state: params.initialState ?? null,
+    workspace: params.workspace ?? null,
+    decorations: params.decorations ?? null,
+    extra_params: params.extraParams ?? null,
+    session_snapshot: params.sessionSnapshot ?? null,
+  };
+}
+
 /**
  * Creates a new independent window for a session
  *
@@ -48,13 +172,7 @@ export interface WindowSyncEvent {
 export async function createSessionWindow(params: CreateSessionWindowParams): Promise<string> {
   try {
     const result = await invoke<WindowCreationResult>('create_session_window', {
-      params: {
-        tab_id: params.tabId,
-        session_id: params.sessionId || null,
-        project_path: params.projectPath || null,
-        title: params.title,
-        engine: params.engine || null,
-      },
+      params: toBackendParams(params),
     });
 
     if (!result.success) {
@@ -67,6 +185,50 @@ export async function createSessionWindow(params: CreateSessionWindowParams): Pr
   }
 }
 
+/**
+ * Opens a copy of a session window with a fresh session
+ *
+ * The copy keeps the project, engine and size of the source window and is
+ * placed slightly offset from it.
+ *
+ * @param sourceLabel - Label of the session window to duplicate
+ * @returns Creation result with the new window label
+ */
+export async function duplicateSessionWindow(
+  sourceLabel: string
+): Promise<WindowCreationResult> {
+  return await invoke<WindowCreationResult>('duplicate_session_window', { sourceLabel });
+}
+
+/**
+ * Sets how many session windows can be open at once (default 20)
+ *
+ * Creating a window beyond the limit fails with "window limit reached" and
+ * emits `window-limit-reached`; see `onWindowLimitReached`.
+ *
+ * @param maxSessionWindows - The new limit (at least 1)
+ */
+export async function setMaxSessionWindows(maxSessionWindows: number): Promise<void> {
+  await invoke('set_max_session_windows', { maxSessionWindows });
+}
+
+/**
+ * Listens for session windows refused because the window limit was reached
+ *
+ * @param callback - Callback receiving the refused tab and the limit
+ * @returns Unlisten function
+ */
+export async function onWindowLimitReached(
+  callback: (payload: { tab_id: string; max_session_windows: number }) => void
+): Promise<UnlistenFn> {
+  return listen<{ tab_id: string; max_session_windows: number }>(
+    'window-limit-reached',
+    (event) => {
+      callback(event.payload);
+    }
+  );
+}
+
 /**
  * Closes an independent session window
  *
@@ -81,6 +243,100 @@ export async function closeSessionWindow(windowLabel: string): Promise<void> {
   }
 }
 
+/**
+ * Closes a session window after saving its state for the next detach of the tab
+ *
+ * The window answers through providePersistedState; one that doesn't answer
+ * within 2 seconds is closed anyway.
+ *
+ * @param windowLabel - The label of the window to close
+ * @returns Whether the state was captured
+ */
+export async function closeSessionWindowSaving(windowLabel: string): Promise<boolean> {
+  return await invoke<boolean>('close_session_window_saving', { windowLabel });
+}
+
+/**
+ * Answers the backend's `persist-state` requests before a soft close (session windows only)
+ *
+ * The snapshot is restored in the next window opened for the tab, like a
+ * detach snapshot.
+ *
+ * @param getSnapshot - Returns the UI state (drafts, scroll position) as JSON, or null
+ * @returns Unlisten function
+ */
+export async function providePersistedState(
+  getSnapshot: () => string | null | Promise<string | null>
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{ correlation_id: string }>(
+    'persist-state',
+    async (event) => {
+      emit('state-persisted', {
+        correlation_id: event.payload.correlation_id,
+        payload: await getSnapshot(),
+      });
+    }
+  );
+}
+
+/** Outcome of `closeAllSessionWindows` */
+export interface CloseAllResult {
+  closed: string[];
+  /** Busy windows that got `close-blocked` and stay open */
+  skipped_busy: string[];
+  failed: { window_label: string; error: string }[];
+}
+
+/**
+ * Closes all session windows
+ *
+ * Busy windows stay open and get `close-blocked`, as with `closeSessionWindow`.
+ *
+ * @returns Closed, busy and failed windows
+ */
+export async function closeAllSessionWindows(): Promise<CloseAllResult> {
+  return await invoke<CloseAllResult>('close_all_session_windows');
+}
+
+/**
+ * Puts a session window in a group, or takes it out with null
+ *
+ * @param windowLabel - Window label
+ * @param groupId - Group to join, or null to leave the current one
+ */
+export async function setWindowGroup(windowLabel: string, groupId: string | null): Promise<void> {
+  await invoke('set_window_group', { windowLabel, groupId });
+}
+
+/**
+ * Closes every window of a group; busy ones stay open
+ *
+ * @param groupId - The group to close
+ */
+export async function closeGroup(groupId: string): Promise<CloseAllResult> {
+  return await invoke<CloseAllResult>('close_group', { groupId });
+}
+
+/**
+ * Brings every window of a group to the front
+ *
+ * @param groupId - The group to focus
+ * @returns The number of windows brought to the front
+ */
+export async function focusGroup(groupId: string): Promise<number> {
+  return await invoke<number>('focus_group', { groupId });
+}
+
+/**
+ * Checks whether a window is open, e.g. to choose between create and focus
+ *
+ * @param windowLabel - Window label
+ * @returns Whether the window exists
+ */
+export async function sessionWindowExists(windowLabel: string): Promise<boolean> {
+  return await invoke<boolean>('session_window_exists', { windowLabel });
+}
+
 /**
  * Gets a list of all open session windows
  *
@@ -95,6 +351,27 @@ export async function listSessionWindows(): Promise<string[]> {
   }
 }
 
+/** Session window with its metadata, title, focus and geometry */
+export interface SessionWindowView extends WindowMeta {
+  title: string;
+  focused: boolean;
+  geometry: { x: number; y: number; width: number; height: number } | null;
+}
+
+/**
+ * Gets all open session windows with their metadata in one call
+ *
+ * @returns Windows sorted by label
+ */
+export async function listSessionWindowsDetailed(): Promise<SessionWindowView[]> {
+  try {
+    return await invoke<SessionWindowView[]>('list_session_windows_detailed');
+  } catch (error) {
+    console.error('[WindowManager] Failed to list session windows:', error);
+    return [];
+  }
+}
+
 /**
  * Focuses a specific session window
  *
@@ -116,6 +393,7 @@ export async function focusSessionWindow(windowLabel: string): Promise<void> {
 /**
  * Emits an event to a specific window
  *
+ * @deprecated The payload arrives as a JSON string; use {@link emitJsonToWindow}.
  * @param windowLabel - Target window label
  * @param eventName - Event name
  * @param payload - Event payload
@@ -137,21 +415,168 @@ export async function emitToWindow(
   }
 }
 
+/**
+ * Emits an event to a specific window, delivering the payload as an object
+ *
+ * @param windowLabel - Target window label
+ * @param eventName - Event name
+ * @param payload - Event payload, a plain object
+ */
+export async function emitJsonToWindow(
+  windowLabel: string,
+  eventName: string,
+  payload: Record<string, unknown>
+): Promise<void> {
+  try {
+    await invoke('emit_json_to_window', {
+      windowLabel,
+      eventName,
+      payload,
+    });
+  } catch (error) {
+    console.error('[WindowManager] Failed to emit to window:', error);
+    throw error;
+  }
+}
+
+/**
+ * Sends a request to a window and waits for its response
+ *
+ * The target receives `requestName` with `{ correlation_id, payload }` and
+ * answers by emitting `${requestName}-response` with the same correlation_id.
+ *
+ * @param targetLabel - Window to ask
+ * @param requestName - Request event name
+ * @param payload - Request payload
+ * @param timeoutMs - How long to wait for the response (default 5000ms)
+ * @returns The response payload
+ */
+export async function requestFromWindow<T = unknown>(
+  targetLabel: string,
+  requestName: string,
+  payload: unknown,
+  timeoutMs?: number
+): Promise<T> {
+  return await invoke<T>('request_from_window', {
+    targetLabel,
+    requestName,
+    payload,
+    timeoutMs: timeoutMs ?? null,
+  });
+}
+
+/**
+ * Takes the session snapshot the window was detached with (session windows only)
+ *
+ * Handed out once; call it when the session page has mounted.
+ *
+ * @returns The snapshot JSON, or null if the window was opened without one
+ */
+export async function takeSessionSnapshot(): Promise<string | null> {
+  return await invoke<string | null>('take_session_snapshot', {
+    windowLabel: getCurrentWebviewWindow().label,
+  });
+}
+
+/**
+ * Receives a project path too long for the URL (session windows only)
+ *
+ * Sent once the page has loaded when `parseSessionWindowParams` reports
+ * `projectPathPending`, before any `initial-prompt` event.
+ *
+ * @param callback - Callback receiving the project path
+ * @returns Unlisten function
+ */
+export async function onProjectPath(callback: (projectPath: string) => void): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<string>('project-path', (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Answers the backend's snapshot requests on reattach (session windows only)
+ *
+ * The snapshot comes back to the main window in the `tab-reattach-requested` payload.
+ *
+ * @param getSnapshot - Returns the current UI state as JSON, or null
+ * @returns Unlisten function
+ */
+export async function provideSessionSnapshot(
+  getSnapshot: () => string | null
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{ correlation_id: string }>(
+    'session-snapshot-request',
+    (event) => {
+      emit('session-snapshot-request-response', {
+        correlation_id: event.payload.correlation_id,
+        payload: getSnapshot(),
+      });
+    }
+  );
+}
+
+/**
+ * Captures a thumbnail of a session window, e.g. for a visual window switcher
+ *
+ * Hidden and minimized windows return the thumbnail cached when they last
+ * lost focus, or fail if there is none.
+ *
+ * @param windowLabel - Session window label
+ * @param maxWidth - Largest width in pixels; the aspect ratio is kept
+ * @returns The thumbnail as a PNG data URL
+ */
+export async function captureWindowThumbnail(
+  windowLabel: string,
+  maxWidth: number
+): Promise<string> {
+  return await invoke<string>('capture_window_thumbnail', { windowLabel, maxWidth });
+}
+
+/**
+ * Answers the backend's thumbnail requests (session windows only)
+ *
+ * There is no native webview screenshot, so the window renders its own page
+ * (e.g. with html-to-image) when asked. Also called when the window loses
+ * focus, to refresh the cached thumbnail.
+ *
+ * @param render - Renders the page as a data URL at most `maxWidth` wide, or null
+ * @returns Unlisten function
+ */
+export async function provideWindowThumbnail(
+  render: (maxWidth: number) => Promise<string | null>
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{
+    correlation_id: string;
+    payload: { max_width: number };
+  }>('window-thumbnail-request', async (event) => {
+    emit('window-thumbnail-request-response', {
+      correlation_id: event.payload.correlation_id,
+      payload: await render(event.payload.payload.max_width),
+    });
+  });
+}
+
 /**
  * Broadcasts an event to all session windows
  *
+ * Each window is targeted individually, so listen with
+ * `getCurrentWebviewWindow().listen` to receive it exactly once.
+ *
  * @param eventName - Event name
  * @param payload - Event payload
+ * @param excludeLabel - Optional window to skip (usually the sender)
  * @returns Number of windows that received the event
  */
 export async function broadcastToSessionWindows(
   eventName: string,
-  payload: any
+  payload: any,
+  excludeLabel?: string
 ): Promise<number> {
   try {
     return await invoke<number>('broadcast_to_session_windows', {
       eventName,
       payload: JSON.stringify(payload),
+      excludeLabel: excludeLabel ?? null,
     });
   } catch (error) {
     console.error('[WindowManager] Failed to broadcast:', error);
@@ -159,6 +584,137 @@ export async function broadcastToSessionWindows(
   }
 }
 
+/**
+ * Broadcasts an event to the session windows running one engine
+ *
+ * @param engine - Only windows of this engine receive the event
+ * @param eventName - Event name
+ * @param payload - Event payload
+ * @returns Number of windows that received the event
+ */
+export async function broadcastToEngineWindows(
+  engine: 'claude' | 'codex' | 'gemini',
+  eventName: string,
+  payload: any
+): Promise<number> {
+  return await invoke<number>('broadcast_to_engine_windows', {
+    engine,
+    eventName,
+    payload: JSON.stringify(payload),
+  });
+}
+
+/**
+ * Emits an event with a JSON payload to several specific windows in one call
+ *
+ * Duplicate labels receive the event once; unknown labels are reported in
+ * `failed` instead of failing the whole call.
+ *
+ * @param labels - Target window labels
+ * @param eventName - Event name
+ * @param payload - Event payload
+ * @returns Delivered and failed window labels
+ */
+export async function emitToWindows(
+  labels: string[],
+  eventName: string,
+  payload: unknown
+): Promise<BroadcastResult> {
+  return await invoke<BroadcastResult>('emit_to_windows', { labels, eventName, payload });
+}
+
+/**
+ * Broadcasts an event to all session windows and reports per-window failures
+ *
+ * @param eventName - Event name
+ * @param payload - Event payload
+ * @param excludeLabel - Optional window to skip (usually the sender)
+ * @returns Delivered and failed window labels
+ */
+export async function broadcastToSessionWindowsDetailed(
+  eventName: string,
+  payload: any,
+  excludeLabel?: string
+): Promise<BroadcastResult> {
+  return await invoke<BroadcastResult>('broadcast_to_session_windows_detailed', {
+    eventName,
+    payload: JSON.stringify(payload),
+    excludeLabel: excludeLabel ?? null,
+  });
+}
+
+/**
+ * Sets the largest payload a broadcast may carry (default 1 MB)
+ *
+ * Larger payloads are rejected; send them with `broadcastChunked`.
+ *
+ * @param maxBytes - The new limit in bytes (at least 1)
+ */
+export async function setMaxBroadcastPayloadSize(maxBytes: number): Promise<void> {
+  await invoke('set_max_broadcast_payload_size', { maxBytes });
+}
+
+/**
+ * Broadcasts a large payload to all session windows in chunks
+ *
+ * Receivers get it through `onChunkedBroadcast`.
+ *
+ * @param eventName - Event name the receivers reassemble the payload into
+ * @param payload - Event payload
+ * @param excludeLabel - Optional window to skip (usually the sender)
+ * @param chunkSize - Largest chunk in bytes (default 256 KB)
+ * @returns Delivered and failed window labels
+ */
+export async function broadcastChunked(
+  eventName: string,
+  payload: any,
+  excludeLabel?: string,
+  chunkSize?: number
+): Promise<BroadcastResult> {
+  return await invoke<BroadcastResult>('broadcast_chunked', {
+    eventName,
+    payload: JSON.stringify(payload),
+    excludeLabel: excludeLabel ?? null,
+    chunkSize: chunkSize ?? null,
+  });
+}
+
+/** One piece of a chunked broadcast */
+interface BroadcastChunk {
+  transfer_id: string;
+  event_name: string;
+  seq: number;
+  total: number;
+  data: string;
+}
+
+/**
+ * Receives chunked broadcasts of one event, reassembled (session windows only)
+ *
+ * @param eventName - Event name passed to `broadcastChunked`
+ * @param callback - Callback receiving the parsed payload
+ * @returns Unlisten function
+ */
+export async function onChunkedBroadcast<T = unknown>(
+  eventName: string,
+  callback: (payload: T) => void
+): Promise<UnlistenFn> {
+  const transfers = new Map<string, string[]>();
+  return getCurrentWebviewWindow().listen<BroadcastChunk>('broadcast-chunk', (event) => {
+    const chunk = event.payload;
+    if (chunk.event_name !== eventName) {
+      return;
+    }
+    const parts = transfers.get(chunk.transfer_id) ?? new Array<string>(chunk.total);
+    parts[chunk.seq] = chunk.data;
+    transfers.set(chunk.transfer_id, parts);
+    if (parts.filter((part) => part !== undefined).length === chunk.total) {
+      transfers.delete(chunk.transfer_id);
+      callback(JSON.parse(parts.join('')) as T);
+    }
+  });
+}
+
 // ============================================================================
 // Window Sync Events
 // ============================================================================
@@ -192,67 +748,1201 @@ export async function onWindowSyncEvent(
   });
 }
 
-// ============================================================================
-// URL Parameter Utilities
-// ============================================================================
-
 /**
- * Parses URL parameters for session window initialization
+ * Gets the backend metadata of a session window
  *
- * @returns Parsed parameters or null if not a session window
+ * @param windowLabel - Session window label
+ * @returns The window metadata
  */
-export function parseSessionWindowParams(): {
-  isSessionWindow: boolean;
-  tabId?: string;
-  sessionId?: string;
-  projectPath?: string;
-  engine?: 'claude' | 'codex';
-} {
-  const params = new URLSearchParams(window.location.search);
-  const windowType = params.get('window');
-
-  if (windowType !== 'session') {
-    return { isSessionWindow: false };
-  }
+export async function getWindowMeta(windowLabel: string): Promise<WindowMeta> {
+  return await invoke<WindowMeta>('get_window_meta', { windowLabel });
+}
 
-  const tabId = params.get('tab_id') || undefined;
-  const sessionId = params.get('session_id') || undefined;
-  const projectPath = params.get('project_path')
-    ? decodeURIComponent(params.get('project_path')!)
-    : undefined;
-  const engineParam = params.get('engine');
-  const engine = (engineParam === 'claude' || engineParam === 'codex') ? engineParam : undefined;
-  return {
-    isSessionWindow: true,
-    tabId,
-    sessionId,
-    projectPath,
-    engine,
-  };
+/** Counts returned by `reconcileWindows` */
+export interface ReconcileResult {
+  /** Registry entries dropped because their window is gone */
+  pruned: number;
+  /** Live session windows the registry had lost and re-adopted */
+  adopted: number;
 }
 
 /**
- * Checks if the current window is a detached session window
+ * Re-syncs the backend window registry with the open session windows
  *
- * @returns True if this is a session window
+ * @returns Number of pruned and adopted entries
  */
-export function isSessionWindow(): boolean {
-  const params = new URLSearchParams(window.location.search);
-  return params.get('window') === 'session';
+export async function reconcileWindows(): Promise<ReconcileResult> {
+  return await invoke<ReconcileResult>('reconcile_windows');
 }
 
-// ============================================================================
-// Window Label Utilities
-// ============================================================================
-
 /**
- * Generates a window label for a tab
+ * Records that a session window switched engines mid-session
+ *
+ * Reattach and restore then use the new engine.
+ *
+ * @param windowLabel - Session window label
+ * @param engine - The engine the session now runs on
+ */
+export async function updateWindowEngine(
+  windowLabel: string,
+  engine: 'claude' | 'codex' | 'gemini'
+): Promise<void> {
+  await invoke('update_window_engine', { windowLabel, engine });
+}
+
+/**
+ * Switches a session window to another engine without recreating it
+ *
+ * Fails while a run is in progress. The window is told through `onEngineSwitch`.
+ *
+ * @param windowLabel - Session window label
+ * @param engine - The engine to switch to
+ */
+export async function switchWindowEngine(
+  windowLabel: string,
+  engine: 'claude' | 'codex' | 'gemini'
+): Promise<void> {
+  await invoke('switch_window_engine', { windowLabel, engine });
+}
+
+/** Payload of the `engine-switch` event */
+export interface EngineSwitchPayload {
+  window_label: string;
+  engine: 'claude' | 'codex' | 'gemini';
+  previous_engine: 'claude' | 'codex' | 'gemini' | null;
+  /** The window's URL for the new engine, for `history.replaceState` */
+  url: string;
+}
+
+/**
+ * Listens for engine switches of this window (session windows only)
+ *
+ * The handler should start a session on the new engine and replace the page
+ * URL with `payload.url`, so a reload opens on the new engine.
+ *
+ * @param callback - Callback receiving the switch
+ * @returns Unlisten function
+ */
+export async function onEngineSwitch(
+  callback: (payload: EngineSwitchPayload) => void
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<EngineSwitchPayload>('engine-switch', (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Renames a session window after creation
+ *
+ * The window and the main window receive `title-changed` with
+ * `{ window_label, title }`.
+ *
+ * @param windowLabel - Window label
+ * @param title - New title (must not be blank)
+ */
+export async function setSessionWindowTitle(windowLabel: string, title: string): Promise<void> {
+  await invoke('set_session_window_title', { windowLabel, title });
+}
+
+/**
+ * Pins a window above all other windows, or unpins it
+ *
+ * The window receives `always-on-top-changed` with the new state.
+ *
+ * @param windowLabel - Window label
+ * @param onTop - Whether the window should stay on top
+ */
+export async function setWindowAlwaysOnTop(windowLabel: string, onTop: boolean): Promise<void> {
+  await invoke('set_window_always_on_top', { windowLabel, onTop });
+}
+
+/**
+ * Toggles fullscreen for a window
+ *
+ * @param windowLabel - Window label
+ * @returns Whether the window is now fullscreen
+ */
+export async function toggleWindowFullscreen(windowLabel: string): Promise<boolean> {
+  return await invoke<boolean>('toggle_window_fullscreen', { windowLabel });
+}
+
+/**
+ * Toggles maximize for a window
+ *
+ * @param windowLabel - Window label
+ * @returns Whether the window is now maximized
+ */
+export async function toggleWindowMaximize(windowLabel: string): Promise<boolean> {
+  return await invoke<boolean>('toggle_window_maximize', { windowLabel });
+}
+
+/**
+ * Sets the zoom factor of a window, remembered across restarts
+ *
+ * The window receives `zoom-changed` with the new factor.
+ *
+ * @param windowLabel - Window label
+ * @param factor - Zoom factor between 0.5 and 3.0 (1.0 is 100%)
+ */
+export async function setWindowZoom(windowLabel: string, factor: number): Promise<void> {
+  await invoke('set_window_zoom', { windowLabel, factor });
+}
+
+/**
+ * Sets the opacity of a window
+ *
+ * On platforms without native window opacity the backend emits
+ * `opacity-changed` to the window instead; see `onOpacityChanged`.
+ *
+ * @param windowLabel - Window label
+ * @param opacity - Opacity between 0.2 and 1.0
+ */
+export async function setWindowOpacity(windowLabel: string, opacity: number): Promise<void> {
+  await invoke('set_window_opacity', { windowLabel, opacity });
+}
+
+/**
+ * Listens for opacity the frontend has to apply itself (current window only)
+ *
+ * @param callback - Callback receiving the opacity
+ * @returns Unlisten function
+ */
+export async function onOpacityChanged(
+  callback: (opacity: number) => void
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<number>('opacity-changed', (event) => {
+    callback(event.payload);
+  });
+}
+
+/** How long `notifyWindowAttention` keeps drawing attention */
+export type AttentionMode = 'single' | 'continuous';
+
+/**
+ * Flashes a background window, e.g. when its run finished
+ *
+ * Windows flashes the taskbar button; elsewhere the backend emits
+ * `request-attention` to the window with the mode.
+ *
+ * @param windowLabel - Window label
+ * @param mode - Flash once (default) or until the window is focused
+ * @returns Whether attention was requested (false if the window has focus)
+ */
+export async function notifyWindowAttention(
+  windowLabel: string,
+  mode?: AttentionMode
+): Promise<boolean> {
+  return await invoke<boolean>('notify_window_attention', { windowLabel, mode });
+}
+
+/** Half or quadrant of the work area a window can be snapped to */
+export type SnapPosition =
+  | 'left'
+  | 'right'
+  | 'top'
+  | 'bottom'
+  | 'top_left'
+  | 'top_right'
+  | 'bottom_left'
+  | 'bottom_right';
+
+/**
+ * Snaps a window to a half or quadrant of its current monitor
+ *
+ * The taskbar/dock is excluded from the area the window is tiled in.
+ *
+ * @param windowLabel - Window label
+ * @param position - Where to snap the window
+ * @returns The new logical geometry of the window
+ */
+export async function snapWindow(
+  windowLabel: string,
+  position: SnapPosition
+): Promise<{ x: number; y: number; width: number; height: number }> {
+  return await invoke('snap_window', { windowLabel, position });
+}
+
+/** How `arrangeWindows` lays out the session windows */
+export type ArrangeLayout = 'grid' | 'cascade' | 'stack';
+
+/**
+ * Arranges all visible session windows on the main window's monitor
+ *
+ * Minimized and hidden windows are left where they are.
+ *
+ * @param layout - Grid, cascade or stack
+ * @param groupId - Only arrange the windows of this group
+ * @returns The number of windows arranged
+ */
+export async function arrangeWindows(layout: ArrangeLayout, groupId?: string): Promise<number> {
+  return await invoke<number>('arrange_windows', { layout, groupId: groupId ?? null });
+}
+
+/**
+ * Sets a window's taskbar icon to the one of an engine
+ *
+ * Kept across restarts; on macOS, which has a single dock icon, nothing changes.
+ *
+ * @param windowLabel - Window label
+ * @param engine - Engine whose icon to show
+ */
+export async function setWindowIcon(
+  windowLabel: string,
+  engine: 'claude' | 'codex' | 'gemini'
+): Promise<void> {
+  await invoke('set_window_icon', { windowLabel, engine });
+}
+
+/**
+ * Raises a window above the others without taking keyboard focus
+ *
+ * Unlike focusSessionWindow, whatever the user is typing into keeps focus.
+ *
+ * @param windowLabel - Window label
+ */
+export async function raiseWindow(windowLabel: string): Promise<void> {
+  await invoke('raise_window', { windowLabel });
+}
+
+/**
+ * Gets whether a window has keyboard focus
+ *
+ * @param windowLabel - Window label
+ * @returns Whether the window is focused
+ */
+export async function isWindowFocused(windowLabel: string): Promise<boolean> {
+  return await invoke<boolean>('is_window_focused', { windowLabel });
+}
+
+/**
+ * Gets the session window that currently has focus
+ *
+ * @returns The focused session window's label, or null if none has focus
+ */
+export async function getFocusedSessionWindow(): Promise<string | null> {
+  return await invoke<string | null>('get_focused_session_window');
+}
+
+/**
+ * Switches a window between native decorations and the custom title bar
+ *
+ * Kept across restarts. The window is told through `onDecorationsChanged`.
+ *
+ * @param windowLabel - Window label
+ * @param decorated - Whether to use the native title bar and border
+ */
+export async function setWindowDecorations(
+  windowLabel: string,
+  decorated: boolean
+): Promise<void> {
+  await invoke('set_window_decorations', { windowLabel, decorated });
+}
+
+/**
+ * Sets the background color shown behind a window's webview
+ *
+ * Session windows start with the current theme's background; call this when a
+ * custom theme changes the page background, so resizing doesn't flash.
+ *
+ * @param windowLabel - Window label
+ * @param colorHex - The color as `#RRGGBB`
+ */
+export async function setWindowBackgroundColor(
+  windowLabel: string,
+  colorHex: string
+): Promise<void> {
+  await invoke('set_window_background_color', { windowLabel, colorHex });
+}
+
+/**
+ * Shows a window on all virtual desktops, or only on its own one
+ *
+ * Supported on macOS and Linux (X11); a no-op on Windows. Kept across restarts.
+ *
+ * @param windowLabel - Window label
+ * @param enabled - Whether the window should be visible on all desktops
+ */
+export async function setWindowVisibleOnAllWorkspaces(
+  windowLabel: string,
+  enabled: boolean
+): Promise<void> {
+  await invoke('set_window_visible_on_all_workspaces', { windowLabel, enabled });
+}
+
+/**
+ * Listens for the current window switching decorations, to hide or show the custom title bar
+ *
+ * @param callback - Callback receiving whether native decorations are on
+ * @returns Unlisten function
+ */
+export async function onDecorationsChanged(
+  callback: (decorated: boolean) => void
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{ window_label: string; decorated: boolean }>(
+    'decorations-changed',
+    (event) => {
+      callback(event.payload.decorated);
+    }
+  );
+}
+
+export type WindowEventKind = 'created' | 'closed' | 'focused' | 'reattached';
+
+/** One entry of the local window event log */
+export interface WindowEventRecord {
+  /** Increasing sequence number, pass the last one seen as `since` */
+  seq: number;
+  /** Milliseconds since the Unix epoch */
+  timestamp: number;
+  kind: WindowEventKind;
+  window_label: string;
+  tab_id: string;
+  engine: 'claude' | 'codex' | 'gemini' | null;
+}
+
+/**
+ * Turns the local window event log on or off (off by default)
+ *
+ * Nothing leaves the machine. Turning it off discards the collected events.
+ *
+ * @param enabled - Whether to record window events
+ */
+export async function setWindowEventLogEnabled(enabled: boolean): Promise<void> {
+  await invoke('set_window_event_log_enabled', { enabled });
+}
+
+/**
+ * Gets the recorded window events, oldest first
+ *
+ * @param since - Only return events with a higher `seq`
+ * @returns The events
+ */
+export async function getWindowEvents(since?: number): Promise<WindowEventRecord[]> {
+  return await invoke<WindowEventRecord[]>('get_window_events', { since: since ?? null });
+}
+
+/** One window of a workspace layout */
+export interface WorkspaceWindowSpec extends CreateSessionWindowParams {
+  /** Outer position and inner size in logical pixels; omit to open like a detached tab */
+  geometry?: { x: number; y: number; width: number; height: number };
+}
+
+/** A named set of session windows to open together */
+export interface WorkspaceLayout {
+  name: string;
+  windows: WorkspaceWindowSpec[];
+}
+
+/** Converts a layout to the backend's snake_case shape */
+function toBackendLayout(layout: WorkspaceLayout): Record<string, unknown> {
+  return {
+    name: layout.name,
+    windows: layout.windows.map((spec) => ({
+      ...toBackendParams(spec),
+      geometry: spec.geometry ?? null,
+    })),
+  };
+}
+
+/**
+ * Opens all windows of a saved workspace layout at their saved positions
+ *
+ * A window that fails to open is skipped; saved positions that are no longer
+ * on screen are ignored.
+ *
+ * @param layout - The layout to open
+ * @returns The opened windows
+ */
+export async function openWorkspaceLayout(
+  layout: WorkspaceLayout
+): Promise<WindowCreationResult[]> {
+  return await invoke<WindowCreationResult[]>('open_workspace_layout', {
+    layout: toBackendLayout(layout),
+  });
+}
+
+/** A command result with timing metadata, for profiling */
+export interface CommandEnvelope<T> {
+  data: T;
+  /** Wall-clock time the command took, in milliseconds */
+  elapsed_ms: number;
+  /** Whether the command skipped its expensive part (e.g. focused an open window) */
+  fast_path: boolean;
+  /** Things that didn't go as asked but didn't fail the command */
+  warnings: string[];
+}
+
+/**
+ * Creates a session window and reports how long it took
+ *
+ * @param params - Window creation parameters
+ * @returns The creation result with timing
+ */
+export async function createSessionWindowTimed(
+  params: CreateSessionWindowParams
+): Promise<CommandEnvelope<WindowCreationResult>> {
+  return await invoke<CommandEnvelope<WindowCreationResult>>('create_session_window_timed', {
+    params: toBackendParams(params),
+  });
+}
+
+/** What createSessionWindow would do with a set of parameters */
+export interface SessionWindowValidation {
+  /** Label the window would get; null if the tab ID or workspace is malformed */
+  window_label: string | null;
+  /** Whether the window is already open and would be focused instead */
+  already_open: boolean;
+  /** Why creation would be refused; empty if it would go through */
+  errors: AppError[];
+  /** Things that don't stop creation but may not be intended */
+  warnings: string[];
+  params?: ResolvedSessionWindowParams;
+}
+
+/**
+ * Checks whether a session window could be created, without creating it
+ *
+ * @param params - Window creation parameters
+ * @returns The would-be label, problems and warnings
+ */
+export async function validateSessionWindowParams(
+  params: CreateSessionWindowParams
+): Promise<SessionWindowValidation> {
+  return await invoke<SessionWindowValidation>('validate_session_window_params', {
+    params: toBackendParams(params),
+  });
+}
+
+/**
+ * Opens a workspace layout and reports how long it took
+ *
+ * @param layout - The layout to open
+ * @returns The opened windows with timing; skipped windows are in the warnings
+ */
+export async function openWorkspaceLayoutTimed(
+  layout: WorkspaceLayout
+): Promise<CommandEnvelope<WindowCreationResult[]>> {
+  return await invoke<CommandEnvelope<WindowCreationResult[]>>('open_workspace_layout_timed', {
+    layout: toBackendLayout(layout),
+  });
+}
+
+/** Session details of a mirrored session window */
+export interface MirrorSessionPayload {
+  window_label: string;
+  tab_id: string;
+  session_id: string | null;
+  project_path: string | null;
+  engine: 'claude' | 'codex' | 'gemini' | null;
+}
+
+/**
+ * Shows a detached session read-only in the main window; the detached window stays open
+ *
+ * @param windowLabel - Session window label
+ */
+export async function mirrorSessionInMain(windowLabel: string): Promise<void> {
+  await invoke('mirror_session_in_main', { windowLabel });
+}
+
+/**
+ * Listens for sessions to mirror read-only (main window only)
+ *
+ * @param callback - Callback receiving the session details
+ * @returns Unlisten function
+ */
+export async function onMirrorSession(
+  callback: (payload: MirrorSessionPayload) => void
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<MirrorSessionPayload>('mirror-session', (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Gets the DPI scale factor of a window
+ *
+ * @param windowLabel - Window label
+ * @returns The scale factor (1.0 is 96 DPI)
+ */
+export async function getWindowScaleFactor(windowLabel: string): Promise<number> {
+  return await invoke<number>('get_window_scale_factor', { windowLabel });
+}
+
+/**
+ * Listens for scale factor changes of the current window, e.g. after it was
+ * dragged to a monitor with another DPI
+ *
+ * @param callback - Callback receiving the new scale factor
+ * @returns Unlisten function
+ */
+export async function onScaleFactorChanged(
+  callback: (scaleFactor: number) => void
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{ window_label: string; scale_factor: number }>(
+    'scale-factor-changed',
+    (event) => {
+      callback(event.payload.scale_factor);
+    }
+  );
+}
+
+/**
+ * Tells the backend this session window has rendered and can be shown
+ *
+ * Session windows are created hidden to avoid a white flash; the backend
+ * shows them on this signal, or after a short timeout if it never comes.
+ */
+export async function signalWindowReady(): Promise<void> {
+  const current = getCurrentWebviewWindow();
+  await current.emit('window-ready', { window_label: current.label });
+}
+
+export interface WindowMovedPayload {
+  window_label: string;
+  monitor_index: number;
+  /** Name of the target monitor, if the platform reports one */
+  monitor_name: string | null;
+  geometry: { x: number; y: number; width: number; height: number };
+}
+
+/** A connected monitor; positions and sizes are in logical pixels */
+export interface MonitorInfo {
+  /** Index to pass to `moveWindowToMonitor` */
+  index: number;
+  name: string | null;
+  bounds: { x: number; y: number; width: number; height: number };
+  /** Bounds without the taskbar, dock and menu bar */
+  work_area: { x: number; y: number; width: number; height: number };
+  scale_factor: number;
+  is_primary: boolean;
+  /** Whether the main window is on this monitor */
+  has_main_window: boolean;
+}
+
+/**
+ * Lists the connected monitors, e.g. for a "move to display" menu
+ *
+ * @returns The monitors
+ */
+export async function listMonitors(): Promise<MonitorInfo[]> {
+  return await invoke<MonitorInfo[]>('list_monitors');
+}
+
+/**
+ * Moves a window to another monitor, centered and shrunk to fit if needed
+ *
+ * @param windowLabel - Window label
+ * @param monitorIndex - Index into the list of connected monitors
+ * @returns The new logical geometry of the window
+ */
+export async function moveWindowToMonitor(
+  windowLabel: string,
+  monitorIndex: number
+): Promise<WindowMovedPayload['geometry']> {
+  return invoke('move_window_to_monitor', { windowLabel, monitorIndex });
+}
+
+/** A window position relative to the top-left corner of a monitor, in logical pixels */
+export interface RelativePosition {
+  monitor_name: string | null;
+  rel_x: number;
+  rel_y: number;
+}
+
+/**
+ * Gets a window's position relative to the monitor it is on
+ *
+ * @param windowLabel - Window label
+ */
+export async function getWindowRelativePosition(windowLabel: string): Promise<RelativePosition> {
+  return await invoke<RelativePosition>('get_window_relative_position', { windowLabel });
+}
+
+/**
+ * Moves a window to a position relative to a monitor
+ *
+ * Falls back to the primary monitor if the named one is no longer connected.
+ *
+ * @param windowLabel - Window label
+ * @param position - Position as returned by getWindowRelativePosition
+ * @returns The position applied, with the monitor actually used
+ */
+export async function setWindowRelativePosition(
+  windowLabel: string,
+  position: RelativePosition
+): Promise<RelativePosition> {
+  return await invoke<RelativePosition>('set_window_relative_position', {
+    windowLabel,
+    monitorName: position.monitor_name,
+    relX: position.rel_x,
+    relY: position.rel_y,
+  });
+}
+
+/**
+ * Listens for windows being sent to another monitor
+ *
+ * @param callback - Callback function to handle events
+ * @returns Unlisten function
+ */
+export async function onWindowMoved(
+  callback: (payload: WindowMovedPayload) => void
+): Promise<UnlistenFn> {
+  return listen<WindowMovedPayload>('window-moved', (event) => {
+    callback(event.payload);
+  });
+}
+
+/** What the session in a window is doing */
+export type WindowActivity = 'idle' | 'thinking' | 'streaming' | 'error';
+
+export interface WindowActivityPayload {
+  window_label: string;
+  tab_id: string;
+  activity: WindowActivity;
+}
+
+/**
+ * Reports what the session in a window is doing; also serves as a heartbeat
+ *
+ * @param windowLabel - Session window label
+ * @param state - The current activity
+ */
+export async function reportWindowActivity(
+  windowLabel: string,
+  state: WindowActivity
+): Promise<void> {
+  await invoke('report_window_activity', { windowLabel, state });
+}
+
+/**
+ * Listens for activity reports of session windows (main window only)
+ *
+ * @param callback - Callback function to handle events
+ * @returns Unlisten function
+ */
+export async function onWindowActivityChanged(
+  callback: (payload: WindowActivityPayload) => void
+): Promise<UnlistenFn> {
+  return listen<WindowActivityPayload>('window-activity-changed', (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Opens a small always-on-top preview of a session's latest output
+ *
+ * The preview closes by itself once it loses focus.
+ *
+ * @param tabId - Tab to preview
+ * @param anchorX - Logical x coordinate of the preview's top-left corner
+ * @param anchorY - Logical y coordinate of the preview's top-left corner
+ * @returns The preview window label
+ */
+export async function createPreviewWindow(
+  tabId: string,
+  anchorX: number,
+  anchorY: number
+): Promise<string> {
+  return invoke<string>('create_preview_window', { tabId, anchorX, anchorY });
+}
+
+/**
+ * Shows or hides a window without closing it
+ *
+ * Hidden windows keep their session running and still receive broadcasts.
+ *
+ * @param windowLabel - Window label
+ * @param visible - Whether the window should be shown
+ * @param skipTaskbar - Whether to also drop the window from the taskbar
+ */
+export async function setWindowVisible(
+  windowLabel: string,
+  visible: boolean,
+  skipTaskbar?: boolean
+): Promise<void> {
+  await invoke('set_window_visible', { windowLabel, visible, skipTaskbar: skipTaskbar ?? null });
+}
+
+/**
+ * Drops a window from the taskbar or puts it back; kept across restarts
+ *
+ * Unlike setWindowVisible(false), the window stays on screen.
+ *
+ * @param windowLabel - Window label
+ * @param skip - Whether to leave the window out of the taskbar
+ */
+export async function setWindowSkipTaskbar(windowLabel: string, skip: boolean): Promise<void> {
+  await invoke('set_window_skip_taskbar', { windowLabel, skip });
+}
+
+/**
+ * Minimizes every shown session window, e.g. for focus mode
+ *
+ * @returns The number of windows minimized
+ */
+export async function minimizeAllSessionWindows(): Promise<number> {
+  return await invoke<number>('minimize_all_session_windows');
+}
+
+/**
+ * Brings back the windows `minimizeAllSessionWindows` minimized
+ *
+ * Windows the user minimized by hand stay minimized.
+ *
+ * @returns The number of windows restored
+ */
+export async function restoreAllSessionWindows(): Promise<number> {
+  return await invoke<number>('restore_all_session_windows');
+}
+
+/** How handed-over text goes into the target's input box */
+export type InsertMode = 'replace' | 'append';
+
+/**
+ * Hands text to another window's input box (at most 1 MB)
+ *
+ * @param targetLabel - Window that receives the text
+ * @param text - The text
+ * @param mode - Whether the text replaces the input or is appended to it
+ */
+export async function sendTextToWindow(
+  targetLabel: string,
+  text: string,
+  mode: InsertMode
+): Promise<void> {
+  await invoke('send_text_to_window', { targetLabel, text, mode });
+}
+
+/**
+ * Listens for text handed to the current window
+ *
+ * @param callback - Callback receiving the text and how to insert it
+ * @returns Unlisten function
+ */
+export async function onTextReceived(
+  callback: (text: string, mode: InsertMode) => void
+): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{ text: string; mode: InsertMode }>(
+    'text-received',
+    (event) => {
+      callback(event.payload.text, event.payload.mode);
+    }
+  );
+}
+
+/**
+ * Flags whether a session window has a run in progress
+ *
+ * While busy, closing the window emits `close-blocked` to it instead; emit
+ * `confirm-close` from that window to close it anyway.
+ *
+ * @param windowLabel - Session window label
+ * @param busy - Whether a run is in progress
+ */
+export async function setWindowBusy(windowLabel: string, busy: boolean): Promise<void> {
+  await invoke('set_window_busy', { windowLabel, busy });
+}
+
+/**
+ * Sets whether the main window gets the focus back once the last session window closes
+ *
+ * @param enabled - Whether to focus the main window (default: true)
+ */
+export async function setFocusMainOnLastClose(enabled: boolean): Promise<void> {
+  await invoke('set_focus_main_on_last_close', { enabled });
+}
+
+/**
+ * Reloads a stuck session window's page, keeping its URL
+ *
+ * The window gets `window-reloading` shortly before; see `onWindowReloading`.
+ *
+ * @param windowLabel - Session window label
+ * @param hard - Whether to empty the page's caches first
+ */
+export async function reloadSessionWindow(windowLabel: string, hard = false): Promise<void> {
+  await invoke('reload_session_window', { windowLabel, hard });
+}
+
+/**
+ * Listens for the current window being about to reload, to persist in-memory state
+ *
+ * @param callback - Callback invoked right before the reload
+ * @returns Unlisten function
+ */
+export async function onWindowReloading(callback: () => void): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen('window-reloading', () => {
+    callback();
+  });
+}
+
+/**
+ * Changes the minimum and maximum inner size of a window
+ *
+ * @param windowLabel - Window label
+ * @param min - Minimum inner size in logical pixels, or null to drop it
+ * @param max - Maximum inner size in logical pixels, or null to drop it
+ */
+export async function setWindowSizeConstraints(
+  windowLabel: string,
+  min: { width: number; height: number } | null,
+  max: { width: number; height: number } | null
+): Promise<void> {
+  await invoke('set_window_size_constraints', { windowLabel, min, max });
+}
+
+/**
+ * Allows or locks resizing a window; kept across restarts
+ *
+ * @param windowLabel - Window label
+ * @param resizable - Whether the user can resize the window
+ */
+export async function setWindowResizable(windowLabel: string, resizable: boolean): Promise<void> {
+  await invoke('set_window_resizable', { windowLabel, resizable });
+}
+
+/**
+ * Allows or locks maximizing a window; kept across restarts
+ *
+ * @param windowLabel - Window label
+ * @param maximizable - Whether the user can maximize the window
+ */
+export async function setWindowMaximizable(
+  windowLabel: string,
+  maximizable: boolean
+): Promise<void> {
+  await invoke('set_window_maximizable', { windowLabel, maximizable });
+}
+
+/**
+ * Turns focus follows mouse on or off for session windows
+ *
+ * The session window under the pointer gets focus after the pointer rests on
+ * it briefly, as long as one of the app's windows has focus.
+ *
+ * @param enabled - Whether focus follows the mouse
+ * @param includeMain - Whether the main window takes part too
+ */
+export async function setFocusFollowsMouse(
+  enabled: boolean,
+  includeMain = false
+): Promise<void> {
+  await invoke('set_focus_follows_mouse', { enabled, includeMain });
+}
+
+/**
+ * Kind of failure of a window command
+ *
+ * `window_gone` means the target closed while an event was on its way; it is
+ * usually safe to ignore and isn't sent on `window-error`.
+ */
+export type AppErrorKind =
+  | 'not_found'
+  | 'build'
+  | 'emit'
+  | 'window_gone'
+  | 'invalid_param'
+  | 'other';
+
+/** Error rejected by window commands; `message` is the human-readable text */
+export interface AppError {
+  kind: AppErrorKind;
+  message: string;
+  /** Stable code to match on; set for a missing window */
+  code?: 'window_not_found';
+}
+
+/**
+ * Checks whether a rejected window command failed because the window doesn't exist
+ *
+ * @param error - The rejection value
+ */
+export function isWindowNotFound(error: unknown): boolean {
+  return (error as Partial<AppError> | null)?.code === 'window_not_found';
+}
+
+export interface WindowErrorPayload {
+  /** Command or background task that failed */
+  source: string;
+  error: AppError;
+}
+
+/**
+ * Listens for every window command or background failure, e.g. to show a toast
+ *
+ * @param callback - Callback function to handle events
+ * @returns Unlisten function
+ */
+export async function onWindowError(
+  callback: (payload: WindowErrorPayload) => void
+): Promise<UnlistenFn> {
+  return listen<WindowErrorPayload>('window-error', (event) => {
+    callback(event.payload);
+  });
+}
+
+/** Lifecycle events the backend sends to the main window for each session window */
+export type SessionWindowLifecycleEvent =
+  | 'session-window-focused'
+  | 'session-window-blurred'
+  | 'session-window-moved'
+  | 'session-window-resized'
+  | 'session-window-closed';
+
+export interface SessionWindowLifecyclePayload {
+  window_label: string;
+  tab_id: string;
+  /** Logical geometry, only for move and resize events */
+  geometry?: { x: number; y: number; width: number; height: number };
+  /**
+   * Only for `session-window-closed`: `programmatic` when closed through a
+   * command (the tab is already cleaned up), `user` otherwise
+   */
+  origin?: 'user' | 'programmatic';
+}
+
+/**
+ * Listens for a session window lifecycle event (main window only)
+ *
+ * @param eventName - Lifecycle event to listen for
+ * @param callback - Callback function to handle events
+ * @returns Unlisten function
+ */
+export async function onSessionWindowLifecycle(
+  eventName: SessionWindowLifecycleEvent,
+  callback: (payload: SessionWindowLifecyclePayload) => void
+): Promise<UnlistenFn> {
+  return listen<SessionWindowLifecyclePayload>(eventName, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Dims session windows while they don't have focus (main window only)
+ *
+ * @param inactiveOpacity - Opacity of unfocused session windows
+ * @returns Unlisten function that stops dimming
+ */
+export async function enableInactiveWindowDimming(
+  inactiveOpacity = 0.7
+): Promise<UnlistenFn> {
+  const unlisteners = await Promise.all([
+    onSessionWindowLifecycle('session-window-blurred', ({ window_label }) => {
+      setWindowOpacity(window_label, inactiveOpacity).catch((error) =>
+        console.error('[WindowManager] Failed to dim window:', error)
+      );
+    }),
+    onSessionWindowLifecycle('session-window-focused', ({ window_label }) => {
+      setWindowOpacity(window_label, 1).catch((error) =>
+        console.error('[WindowManager] Failed to restore window opacity:', error)
+      );
+    }),
+  ]);
+
+  return () => unlisteners.forEach((unlisten) => unlisten());
+}
+
+/**
+ * Sets a custom title bar color, e.g. to match an accent theme
+ *
+ * Applied to windows opened later too when no label is given, until the
+ * theme is set again.
+ *
+ * @param colorHex - The color as `#RRGGBB`
+ * @param windowLabel - Window to color; all windows if omitted
+ */
+export async function setTitlebarColor(colorHex: string, windowLabel?: string): Promise<void> {
+  await invoke('set_titlebar_color', { windowLabel: windowLabel ?? null, colorHex });
+}
+
+/**
+ * Gets the color this window's custom title bar should have (Linux only)
+ *
+ * Call it once mounted; later changes arrive through `onTitlebarTheme`.
+ *
+ * @returns The color as `#RRGGBB`, or null where the OS paints the title bar
+ */
+export async function getTitlebarTheme(): Promise<string | null> {
+  const theme = await invoke<{ color: string } | null>('get_titlebar_theme', {
+    windowLabel: getCurrentWebviewWindow().label,
+  });
+  return theme?.color ?? null;
+}
+
+/**
+ * Listens for title bar color changes of this window (Linux only)
+ *
+ * @param callback - Callback receiving the color as `#RRGGBB`
+ * @returns Unlisten function
+ */
+export async function onTitlebarTheme(callback: (color: string) => void): Promise<UnlistenFn> {
+  return getCurrentWebviewWindow().listen<{ color: string }>('titlebar-theme', (event) => {
+    callback(event.payload.color);
+  });
+}
+
+/**
+ * Listens for OS dark-mode switches the title bars followed
+ *
+ * Not emitted while the frontend has pinned a theme via `set_titlebar_theme`.
+ *
+ * @param callback - Callback receiving whether the OS switched to dark mode
+ * @returns Unlisten function
+ */
+export async function onSystemThemeChanged(
+  callback: (isDark: boolean) => void
+): Promise<UnlistenFn> {
+  return listen<{ is_dark: boolean }>('system-theme-changed', (event) => {
+    callback(event.payload.is_dark);
+  });
+}
+
+// ============================================================================
+// URL Parameter Utilities
+// ============================================================================
+
+/** Query keys the backend sets itself (matches the backend) */
+const RESERVED_QUERY_KEYS = [
+  'window',
+  'tab_id',
+  'session_id',
+  'project_path',
+  'project_path_event',
+  'engine',
+  'decorated',
+  'initial_prompt',
+  'initial_prompt_event',
+];
+
+/**
+ * Parses URL parameters for session window initialization
+ *
+ * @returns Parsed parameters or null if not a session window
+ */
+export function parseSessionWindowParams(): {
+  isSessionWindow: boolean;
+  tabId?: string;
+  sessionId?: string;
+  projectPath?: string;
+  /** The project path was too long for the URL and follows as a `project-path` event */
+  projectPathPending: boolean;
+  engine?: 'claude' | 'codex' | 'gemini';
+  /** The window has the native title bar; don't render the custom one */
+  decorated: boolean;
+  /** Prompt passed in the URL */
+  initialPrompt?: string;
+  /** The prompt was too long for the URL and follows as an `initial-prompt` event */
+  initialPromptPending: boolean;
+  /** The `extraParams` the window was created with */
+  extraParams: Record<string, string>;
+} {
+  const params = new URLSearchParams(window.location.search);
+  const windowType = params.get('window');
+
+  if (windowType !== 'session') {
+    return {
+      isSessionWindow: false,
+      projectPathPending: false,
+      decorated: false,
+      initialPromptPending: false,
+      extraParams: {},
+    };
+  }
+
+  const tabId = params.get('tab_id') || undefined;
+  const sessionId = params.get('session_id') || undefined;
+  // URLSearchParams already percent-decodes every value
+  const projectPath = params.get('project_path') || undefined;
+  const projectPathPending = params.get('project_path_event') === '1';
+  const engineParam = params.get('engine');
+  const engine = (engineParam === 'claude' || engineParam === 'codex' || engineParam === 'gemini')
+    ? engineParam
+    : undefined;
+  const decorated = params.get('decorated') === '1';
+  const initialPrompt = params.get('initial_prompt') || undefined;
+  const initialPromptPending = params.get('initial_prompt_event') === '1';
+  const extraParams: Record<string, string> = {};
+  params.forEach((value, key) => {
+    if (!RESERVED_QUERY_KEYS.includes(key)) {
+      extraParams[key] = value;
+    }
+  });
+  return {
+    isSessionWindow: true,
+    tabId,
+    sessionId,
+    projectPath,
+    projectPathPending,
+    engine,
+    decorated,
+    initialPrompt,
+    initialPromptPending,
+    extraParams,
+  };
+}
+
+/**
+ * Checks if the current window is a session preview window
+ *
+ * @returns True if this is a preview window
+ */
+export function isPreviewWindow(): boolean {
+  const params = new URLSearchParams(window.location.search);
+  return params.get('window') === 'preview';
+}
+
+/**
+ * Checks if the current window is a detached session window
+ *
+ * @returns True if this is a session window
+ */
+export function isSessionWindow(): boolean {
+  const params = new URLSearchParams(window.location.search);
+  return params.get('window') === 'session';
+}
+
+// ============================================================================
+// Window Label Utilities
+// ============================================================================
+
+/** Prefix of every session window label (matches the backend) */
+export const WINDOW_LABEL_PREFIX = 'session-window-';
+
+/**
+ * Generates a window label for a tab
  *
  * @param tabId - The tab ID
- * @returns The window label
+ * @param workspace - Optional workspace to namespace the label with
+ * @returns The window label (`session-window-{workspace}:{tabId}` with a workspace)
+ */
+export function getWindowLabelForTab(tabId: string, workspace?: string): string {
+  return workspace
+    ? `${WINDOW_LABEL_PREFIX}${workspace}:${tabId}`
+    : `${WINDOW_LABEL_PREFIX}${tabId}`;
+}
+
+/**
+ * Checks whether a label belongs to a session window
+ *
+ * @param windowLabel - The window label
+ */
+export function isSessionWindowLabel(windowLabel: string): boolean {
+  return windowLabel.startsWith(WINDOW_LABEL_PREFIX);
+}
+
+/**
+ * Splits a session window label into its workspace and tab ID
+ *
+ * @param windowLabel - The window label
+ * @returns The parts, or null if it isn't a session window label
  */
-export function getWindowLabelForTab(tabId: string): string {
-  return `session-window-${tabId}`;
+export function parseSessionWindowLabel(
+  windowLabel: string
+): { workspace: string | null; tabId: string } | null {
+  const match = windowLabel.match(/^session-window-(?:([^:]+):)?([^:]+)$/);
+  return match ? { workspace: match[1] ?? null, tabId: match[2] } : null;
 }
 
 /**
@@ -262,6 +1952,5 @@ export function getWindowLabelForTab(tabId: string): string {
  * @returns The tab ID or null
  */
 export function getTabIdFromWindowLabel(windowLabel: string): string | null {
-  const match = windowLabel.match(/^session-window-(.+)$/);
-  return match ? match[1] : null;
+  return parseSessionWindowLabel(windowLabel)?.tabId ?? null;
 }
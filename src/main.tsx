@@ -7,7 +7,7 @@ import "./assets/shimmer.css";
 import "./styles.css";
 import "./i18n"; // ✅ i18n 必须同步加载（App 立即需要使用）
 import { getCurrentWindow } from '@tauri-apps/api/window';
-import { isSessionWindow } from "./lib/windowManager";
+import { isSessionWindow, signalWindowReady } from "./lib/windowManager";
 
 // ⚡ 优化：只异步加载 toolRegistry（可以延迟）
 // import { initializeToolRegistry } from "./lib/toolRegistryInit"; // ❌ 改为异步
@@ -34,6 +34,11 @@ const AppWrapper: React.FC = () => {
     // 在React应用完全挂载后显示窗口
     const showWindow = async () => {
       try {
+        // Session windows are shown (and focused) by the backend once ready
+        if (isDetachedWindow) {
+          await signalWindowReady();
+          return;
+        }
         const window = getCurrentWindow();
         await window.show();
         await window.setFocus();
@@ -48,7 +53,7 @@ const AppWrapper: React.FC = () => {
     // 立即显示窗口（生产模式已优化，不需要长延迟）
     const timer = setTimeout(showWindow, 50);
     return () => clearTimeout(timer);
-  }, []);
+  }, [isDetachedWindow]);
 
   // 🆕 根据窗口类型渲染不同的组件
   if (isDetachedWindow) {
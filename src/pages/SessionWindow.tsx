@@ -5,8 +5,16 @@
  * It initializes based on URL parameters passed when the window was created.
  */
 
-import React, { useEffect, useState, useMemo } from 'react';
-import { parseSessionWindowParams, onWindowSyncEvent, emitWindowSyncEvent } from '@/lib/windowManager';
+import React, { useEffect, useState, useMemo, useRef } from 'react';
+import {
+  parseSessionWindowParams,
+  onWindowSyncEvent,
+  emitWindowSyncEvent,
+  takeSessionSnapshot,
+  provideSessionSnapshot,
+  getTitlebarTheme,
+  onTitlebarTheme,
+} from '@/lib/windowManager';
 import { ClaudeCodeSession } from '@/components/ClaudeCodeSession';
 import { MessagesProvider } from '@/contexts/MessagesContext';
 import { PlanModeProvider } from '@/contexts/PlanModeContext';
@@ -25,6 +33,15 @@ interface SessionWindowState {
   engine: 'claude' | 'codex' | null;
 }
 
+/**
+ * UI state carried across detach and reattach (see `takeSessionSnapshot`)
+ */
+interface SessionWindowSnapshot {
+  /** The session shown, including one that only got its ID after the window opened */
+  session?: Session | null;
+  projectPath?: string | null;
+}
+
 /**
  * SessionWindow Component
  *
@@ -44,6 +61,14 @@ export const SessionWindow: React.FC = () => {
   // Parse URL parameters on mount
   const windowParams = useMemo(() => parseSessionWindowParams(), []);
 
+  // Latest state, for the snapshot responder registered only once
+  const stateRef = useRef(state);
+  stateRef.current = state;
+  // Session info reported by the session once it started
+  const sessionInfoRef = useRef<Session | null>(null);
+  // Title bar color from the backend (Linux only; elsewhere the OS paints it)
+  const [titlebarColor, setTitlebarColor] = useState<string | null>(null);
+
   // Initialize session from URL parameters
   useEffect(() => {
     const initializeSession = async () => {
@@ -102,6 +127,76 @@ export const SessionWindow: React.FC = () => {
     initializeSession();
   }, [windowParams]);
 
+  // Pick up the snapshot the window was detached with, now that the page is mounted
+  useEffect(() => {
+    if (!windowParams.isSessionWindow) return;
+
+    takeSessionSnapshot()
+      .then((raw) => {
+        if (!raw) return;
+        const snapshot = JSON.parse(raw) as SessionWindowSnapshot;
+        setState(prev => ({
+          ...prev,
+          // The URL wins; the snapshot fills in what it couldn't carry
+          session: prev.session ?? snapshot.session ?? null,
+          projectPath: prev.projectPath ?? snapshot.projectPath ?? null,
+        }));
+      })
+      .catch((error) => {
+        console.warn('[SessionWindow] Failed to restore session snapshot:', error);
+      });
+  }, [windowParams]);
+
+  // Follow the title bar color; asked for once mounted, since the event sent
+  // while the page was loading went unheard
+  useEffect(() => {
+    if (!windowParams.isSessionWindow) return;
+
+    const unlisten = onTitlebarTheme(setTitlebarColor);
+    getTitlebarTheme()
+      .then((color) => {
+        if (color) setTitlebarColor(color);
+      })
+      .catch((error) => {
+        console.warn('[SessionWindow] Failed to get title bar color:', error);
+      });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [windowParams]);
+
+  // Hand the current state back when the tab is reattached
+  useEffect(() => {
+    if (!windowParams.isSessionWindow) return;
+
+    const unlisten = provideSessionSnapshot(() => {
+      const current = stateRef.current;
+      const snapshot: SessionWindowSnapshot = {
+        session: current.session ?? sessionInfoRef.current,
+        projectPath: current.projectPath,
+      };
+      return JSON.stringify(snapshot);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [windowParams]);
+
+  const handleSessionInfoChange = (info: {
+    sessionId: string;
+    projectId: string;
+    projectPath: string;
+    engine?: 'claude' | 'codex' | 'gemini';
+  }) => {
+    sessionInfoRef.current = {
+      id: info.sessionId,
+      project_id: info.projectId,
+      project_path: info.projectPath,
+      created_at: Date.now() / 1000,
+      engine: info.engine,
+    };
+  };
+
   // Listen for window sync events
   useEffect(() => {
     let unlisten: (() => void) | null = null;
@@ -209,6 +304,11 @@ export const SessionWindow: React.FC = () => {
     WebkitAppRegion: 'drag',
   } as React.CSSProperties;
 
+  // Title bar background, when the backend hands one over
+  const titleBarStyle: React.CSSProperties = titlebarColor
+    ? { ...dragRegionStyle, backgroundColor: titlebarColor }
+    : dragRegionStyle;
+
   // No-drag style for interactive elements within drag region
   const noDragStyle: React.CSSProperties = {
     WebkitAppRegion: 'no-drag',
@@ -236,7 +336,7 @@ export const SessionWindow: React.FC = () => {
     <div
       className="flex-shrink-0 h-10 flex items-center justify-between px-3 border-b border-border bg-muted/30"
       data-tauri-drag-region
-      style={dragRegionStyle}
+      style={titleBarStyle}
       onMouseDown={handleDragStart}
     >
       <div className="flex items-center gap-2" data-tauri-drag-region style={dragRegionStyle}>
@@ -331,7 +431,7 @@ export const SessionWindow: React.FC = () => {
       <div
         className="flex-shrink-0 h-10 flex items-center justify-between px-3 border-b border-border bg-muted/30"
         data-tauri-drag-region
-        style={dragRegionStyle}
+        style={titleBarStyle}
         onMouseDown={handleDragStart}
       >
         {/* Left: Window title */}
@@ -409,6 +509,7 @@ export const SessionWindow: React.FC = () => {
               key={state.tabId || 'detached-session'}
               initialProjectPath={state.projectPath || undefined}
               session={state.session || undefined}
+              onSessionInfoChange={handleSessionInfoChange}
               isActive={true}
             />
           </PlanModeProvider>